@@ -0,0 +1,80 @@
+use crate::prelude::*;
+
+use crate::mawu_value::MawuValue;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// A headed CSV file, returned by `csv::read_table` and `csv::from_str_table_with`.
+///
+/// Unlike a `MawuValue::CSVObject`, which builds a `HashMap` for every row, the header is stored
+/// once and every row is a `Vec` of its values in column order.
+/// This keeps the order of the columns and needs far less memory for files with many columns.
+/// Convert it with `MawuValue::from` where a `MawuValue::CSVObject` is needed.
+///
+/// ## Example
+/// ```rust
+/// use mawu::{csv::{from_str_table_with, CsvParseOptions}, mawu_value::MawuValue};
+///
+/// let table = from_str_table_with("name,age\nAda,36\n", &CsvParseOptions::default()).unwrap();
+/// assert_eq!(table.headers, vec!["name".to_string(), "age".to_string()]);
+/// assert_eq!(table.get(0, "age"), Some(&MawuValue::Uint(36)));
+///
+/// let csv_value = MawuValue::from(table);
+/// assert_eq!(csv_value.as_csv_object().unwrap()[0].get("age").unwrap(), &MawuValue::Uint(36));
+/// ```
+pub struct CsvTable {
+    /// The names of the columns, in order
+    pub headers: Vec<String>,
+    /// The records, each holding one value per column, in the same order as `headers`
+    pub rows: Vec<Vec<MawuValue>>,
+}
+
+impl CsvTable {
+    /// Returns the index of the column called `name`, or `None` if there is no such column.
+    ///
+    /// Looking a column up searches the header, use the index with `rows` directly when reading
+    /// the same column of many rows.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.headers.iter().position(|header| header == name)
+    }
+
+    /// Returns the value of the column called `column` in the `row`-th record,
+    /// or `None` if either does not exist.
+    pub fn get(&self, row: usize, column: &str) -> Option<&MawuValue> {
+        self.rows.get(row)?.get(self.column_index(column)?)
+    }
+
+    /// Returns the number of records, the header not included
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if there are no records, even if there is a header
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+impl From<CsvTable> for MawuValue {
+    /// Pairs every value with its column name, returning a `MawuValue::CSVObject`
+    fn from(table: CsvTable) -> Self {
+        let CsvTable { headers, rows } = table;
+        // collecting lets every map reserve space for all columns at once
+        MawuValue::CSVObject(rows.into_iter().map(|row| headers.iter().cloned().zip(row).collect()).collect())
+    }
+}
+
+#[test]
+fn table_accessors() {
+    let table = CsvTable {
+        headers: vec!["a".to_string(), "b".to_string()],
+        rows: vec![vec![MawuValue::Uint(1), MawuValue::None], vec![MawuValue::from("x"), MawuValue::Bool(true)]],
+    };
+    assert_eq!(table.len(), 2);
+    assert_eq!(table.column_index("b"), Some(1));
+    assert_eq!(table.get(1, "a"), Some(&MawuValue::from("x")));
+    assert_eq!(table.get(2, "a"), None);
+    assert_eq!(table.get(0, "c"), None);
+    let csv_value = MawuValue::from(table);
+    assert_eq!(csv_value.as_csv_object().unwrap()[1].get("b").unwrap(), &MawuValue::Bool(true));
+    assert!(CsvTable::default().is_empty());
+}
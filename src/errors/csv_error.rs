@@ -1,4 +1,5 @@
-use std::fmt;
+use crate::prelude::*;
+use core::fmt;
 
 #[derive(Debug)]
 /// CsvError wraps all errors the CSV side of Mawu can throw
@@ -7,15 +8,18 @@ pub enum CsvError {
     ParseError(CsvParseError),
     /// A wrapper for all writing errors
     WriteError(CsvWriteError),
+    /// A row could not be deserialized into the requested type, holds the row and the serde error
+    DeserializeError(String),
 }
 
-pub type Result<T> = std::result::Result<T, CsvError>;
+pub type Result<T> = core::result::Result<T, CsvError>;
 
 impl fmt::Display for CsvError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             CsvError::ParseError(ref e) => e.fmt(f),
             CsvError::WriteError(ref e) => e.fmt(f),
+            CsvError::DeserializeError(ref s) => write!(f, "Deserialization failed: {}", s),
         }
     }
 }
@@ -27,6 +31,15 @@ pub enum CsvWriteError {
     NotCSV,
     /// Unallowed MawuValue
     UnallowedType(String),
+    /// The value would have to be quoted, but `QuoteStyle::Never` is used
+    NeedsQuoting(String),
+    /// Supplied value is neither a `MawuValue::CSVObject` nor a `MawuValue::CSVArray`, holds its type
+    NotCsvType(String),
+    /// A row of a `MawuValue::CSVObject` does not have the same columns as the first row
+    InconsistentColumns(String),
+    /// Writing to a writer failed, requires the `std` feature
+    #[cfg(feature = "std")]
+    IoError(std::io::Error),
 }
 
 impl fmt::Display for CsvWriteError {
@@ -34,6 +47,11 @@ impl fmt::Display for CsvWriteError {
         match *self {
             CsvWriteError::NotCSV => write!(f, "Supplied value is not a CSV value"),
             CsvWriteError::UnallowedType(ref s) => write!(f, "Unallowed type: {}", s),
+            CsvWriteError::NeedsQuoting(ref s) => write!(f, "Value needs quoting: {}", s),
+            CsvWriteError::NotCsvType(ref s) => write!(f, "Not CSV type: {}", s),
+            CsvWriteError::InconsistentColumns(ref s) => write!(f, "Inconsistent columns: {}", s),
+            #[cfg(feature = "std")]
+            CsvWriteError::IoError(ref e) => write!(f, "Unable to write CSV: {}", e),
         }
     }
 }
@@ -53,6 +71,10 @@ pub enum CsvParseError {
     UnrecognizedHeader(String),
     /// Encountered an unexpected newline
     UnexpectedNewline,
+    /// The delimiter could not be detected unambiguously
+    UndetectableDelimiter,
+    /// A header name is used more than once, see `DuplicateHeaders::Error`
+    DuplicateHeader(String),
 }
 
 impl fmt::Display for CsvParseError {
@@ -64,6 +86,8 @@ impl fmt::Display for CsvParseError {
             CsvParseError::ExtraValue(ref s) => write!(f, "Extra value: {}", s),
             CsvParseError::UnexpectedNewline => write!(f, "Unexpected newline"),
             CsvParseError::UnrecognizedHeader(ref s) => write!(f, "Unrecognized header: {}", s),
+            CsvParseError::UndetectableDelimiter => write!(f, "Unable to detect the delimiter"),
+            CsvParseError::DuplicateHeader(ref s) => write!(f, "Duplicate header: {}", s),
         }
     }
 }
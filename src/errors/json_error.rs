@@ -1,4 +1,5 @@
-use std::fmt;
+use crate::prelude::*;
+use core::fmt;
 
 #[derive(Debug)]
 /// JsonError wraps all errors the JSON side of Mawu can throw
@@ -7,26 +8,43 @@ pub enum JsonError {
     ParseError(JsonParseError),
     /// A wrapper for all JSON writing errors
     WriteError(JsonWriteError),
+    /// A wrapper for all JSON Pointer errors
+    PointerError(JsonPointerError),
+    /// A wrapper for all JSON Patch errors
+    PatchError(JsonPatchError),
+    /// The parsed value could not be deserialized into the requested type, holds the serde error
+    DeserializeError(String),
+    /// The value could not be serialized into a `MawuValue`, holds the serde error
+    SerializeError(String),
 }
 
-pub type Result<T> = std::result::Result<T, JsonError>;
+pub type Result<T> = core::result::Result<T, JsonError>;
 
 impl fmt::Display for JsonError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             JsonError::ParseError(ref e) => e.fmt(f),
             JsonError::WriteError(ref e) => e.fmt(f),
+            JsonError::PointerError(ref e) => e.fmt(f),
+            JsonError::PatchError(ref e) => e.fmt(f),
+            JsonError::DeserializeError(ref s) => write!(f, "Deserialization failed: {}", s),
+            JsonError::SerializeError(ref s) => write!(f, "Serialization failed: {}", s),
         }
     }
 }
 
 #[derive(Debug)]
-/// CsvWriteError wraps all writing errors
+/// JsonWriteError wraps all writing errors
 pub enum JsonWriteError {
     /// Supplied value is not a JSON value
     NotJSON,
     /// Supplied value is not a JSON value
     NotJSONType(String),
+    /// Supplied number can not be represented in JSON, like `NaN` or `inf`
+    InvalidNumber(String),
+    /// The writer passed to `json::to_writer` failed, requires the `std` feature
+    #[cfg(feature = "std")]
+    IoError(std::io::Error),
 }
 
 impl fmt::Display for JsonWriteError {
@@ -34,6 +52,9 @@ impl fmt::Display for JsonWriteError {
         match *self {
             JsonWriteError::NotJSON => write!(f, "Supplied value is not a JSON value"),
             JsonWriteError::NotJSONType(ref s) => write!(f, "Not JSON type: {}", s),
+            JsonWriteError::InvalidNumber(ref s) => write!(f, "Invalid JSON number: {}", s),
+            #[cfg(feature = "std")]
+            JsonWriteError::IoError(ref e) => write!(f, "Unable to write JSON: {}", e),
         }
     }
 }
@@ -67,8 +88,22 @@ pub enum JsonParseError {
     ExpectedValue,
     /// Expected end of object, got something else
     ExpectedEndOfObject,
-    /// Encountered `NaN` or `Infinity`
+    /// Encountered `NaN`, `Infinity` or a number not following the JSON number grammar
     InvalidNumber(String),
+    /// Encountered a `\u` escaped UTF-16 surrogate that is not part of a valid surrogate pair
+    UnpairedSurrogate(String),
+    /// Arrays and objects are nested deeper than `JsonParseOptions::max_depth` allows
+    MaxDepthExceeded(usize),
+    /// The top-level value is not an object, holds the type that was found instead
+    ExpectedTopLevelObject(String),
+    /// The top-level value is not an array, holds the type that was found instead
+    ExpectedTopLevelArray(String),
+    /// Encountered a byte order mark (`U+FEFF`) anywhere but at the very start, outside of a string
+    UnexpectedBom,
+    /// Encountered more than whitespace after the top-level value
+    TrailingCharacters,
+    /// Encountered a number too large to be represented in 64 bits
+    NumberOutOfRange(String),
 }
 
 impl fmt::Display for JsonParseError {
@@ -92,6 +127,122 @@ impl fmt::Display for JsonParseError {
             JsonParseError::UnexpectedCharacter(ref s) => write!(f, "Unexpected character: {}", s),
             JsonParseError::ExpectedEndOfObject => write!(f, "Expected end of object"),
             JsonParseError::InvalidNumber(ref s) => write!(f, "Invalid number: {}", s),
+            JsonParseError::UnpairedSurrogate(ref s) => write!(f, "Unpaired surrogate: \\u{}", s),
+            JsonParseError::MaxDepthExceeded(d) => write!(f, "Maximum nesting depth of {} exceeded", d),
+            JsonParseError::ExpectedTopLevelObject(ref s) => {
+                write!(f, "Expected an object at the top level, found MawuValue::{}", s)
+            }
+            JsonParseError::ExpectedTopLevelArray(ref s) => {
+                write!(f, "Expected an array at the top level, found MawuValue::{}", s)
+            }
+            JsonParseError::UnexpectedBom => write!(f, "Unexpected byte order mark"),
+            JsonParseError::TrailingCharacters => write!(f, "Trailing characters after the top-level value"),
+            JsonParseError::NumberOutOfRange(ref s) => write!(f, "Number out of range: {}", s),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// JsonPointerError wraps all errors of JSON Pointers (RFC 6901)
+pub enum JsonPointerError {
+    /// The pointer is neither empty nor starts with a `/`, or contains a `~` not followed by `0` or `1`
+    InvalidPointer(String),
+    /// The pointer does not point to an existing value
+    PathNotFound(String),
+    /// The reference token is not a valid index of the array it points into
+    InvalidIndex(String),
+}
+
+impl fmt::Display for JsonPointerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JsonPointerError::InvalidPointer(ref s) => write!(f, "Invalid JSON pointer: {}", s),
+            JsonPointerError::PathNotFound(ref s) => write!(f, "Path not found: {}", s),
+            JsonPointerError::InvalidIndex(ref s) => write!(f, "Invalid array index: {}", s),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// JsonPatchError wraps all errors of JSON Patches (RFC 6902)
+pub enum JsonPatchError {
+    /// The patch is not an array of objects
+    NotAPatch,
+    /// The operation is missing or not one of `add`, `remove`, `replace`, `move`, `copy` or `test`
+    InvalidOperation(String),
+    /// A member the operation requires is missing or of the wrong type, e.g. `path` or `value`
+    MissingMember(String),
+    /// A `test` operation failed, holds the path of the tested value
+    TestFailed(String),
+    /// A `move` operation tried to move a value into one of its own children
+    MoveIntoChild(String),
+}
+
+impl fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            JsonPatchError::NotAPatch => write!(f, "Patch is not an array of operations"),
+            JsonPatchError::InvalidOperation(ref s) => write!(f, "Invalid patch operation: {}", s),
+            JsonPatchError::MissingMember(ref s) => write!(f, "Missing patch member: {}", s),
+            JsonPatchError::TestFailed(ref s) => write!(f, "Test failed at path: {}", s),
+            JsonPatchError::MoveIntoChild(ref s) => {
+                write!(f, "Can not move a value into its own child: {}", s)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// ValidationError describes one value not matching its JSON Schema, returned by `validate::against_schema`
+pub struct ValidationError {
+    /// JSON Pointer (RFC 6901) to the value that failed validation, `""` for the whole document
+    pub path: String,
+    /// What is wrong with the value
+    pub kind: ValidationErrorKind,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "At document root: {}", self.kind)
+        } else {
+            write!(f, "At {}: {}", self.path, self.kind)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// ValidationErrorKind lists all ways a value can fail JSON Schema validation
+pub enum ValidationErrorKind {
+    /// The value is not of the `type` the schema expects, holds the expected type(s)
+    WrongType(String),
+    /// A property listed in `required` is missing, holds its name
+    MissingRequired(String),
+    /// The value is not one of the values listed in `enum`
+    NotInEnum,
+    /// The number is smaller than `minimum`, holds the minimum
+    BelowMinimum(f64),
+    /// The number is larger than `maximum`, holds the maximum
+    AboveMaximum(f64),
+    /// The string has fewer characters than `minLength`, holds the minimum length
+    TooShort(usize),
+    /// The string has more characters than `maxLength`, holds the maximum length
+    TooLong(usize),
+    /// The schema itself is malformed, holds the offending keyword
+    InvalidSchema(String),
+}
+
+impl fmt::Display for ValidationErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ValidationErrorKind::WrongType(ref s) => write!(f, "Expected type: {}", s),
+            ValidationErrorKind::MissingRequired(ref s) => write!(f, "Missing required property: {}", s),
+            ValidationErrorKind::NotInEnum => write!(f, "Value is not one of the allowed values"),
+            ValidationErrorKind::BelowMinimum(n) => write!(f, "Number is below the minimum of {}", n),
+            ValidationErrorKind::AboveMaximum(n) => write!(f, "Number is above the maximum of {}", n),
+            ValidationErrorKind::TooShort(n) => write!(f, "String is shorter than {} characters", n),
+            ValidationErrorKind::TooLong(n) => write!(f, "String is longer than {} characters", n),
+            ValidationErrorKind::InvalidSchema(ref s) => write!(f, "Invalid schema keyword: {}", s),
         }
     }
 }
@@ -1,4 +1,5 @@
-use std::{fmt, result};
+use crate::prelude::*;
+use core::{fmt, result};
 
 /// Module holding all possible csv errors
 pub mod csv_error;
@@ -9,7 +10,8 @@ pub mod json_error;
 /// MawuError wraps all errors that can occur in Mawu.
 /// These are mainly `IoError`'s and parsing errors.
 pub enum MawuError {
-    /// A wrapper for `std::io::Error` only used for file handling
+    /// A wrapper for `std::io::Error` only used for file handling, requires the `std` feature
+    #[cfg(feature = "std")]
     IoError(std::io::Error),
     /// A wrapper for `csv::Error` containing all errors for CSV
     CsvError(csv_error::CsvError),
@@ -24,6 +26,7 @@ pub type Result<T> = result::Result<T, MawuError>;
 impl fmt::Display for MawuError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
+            #[cfg(feature = "std")]
             MawuError::IoError(ref e) => e.fmt(f),
             MawuError::CsvError(ref e) => e.fmt(f),
             MawuError::JsonError(ref e) => e.fmt(f),
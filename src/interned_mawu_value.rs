@@ -0,0 +1,202 @@
+use crate::prelude::*;
+use alloc::{collections::BTreeSet, sync::Arc};
+
+use crate::{
+    mawu_value::{MawuObject, MawuValue},
+    mawu_value_ref::MawuValueRef,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+/// A `MawuValue` whose object keys are interned, returned by `json::from_str_interned`.
+///
+/// Every distinct key is allocated once as an `Arc<str>` and shared by all objects using it,
+/// so an array of many objects with the same keys holds each key only once.
+/// Objects keep their key-value-pairs in the order of the input, if a key is used more than once
+/// the last value wins, just like in a `MawuValueRef::Object`.
+/// Use `to_owned` or `into_owned` to convert into a `MawuValue`.
+pub enum InternedMawuValue {
+    /// Represents a JSON Object, with shared keys and values made of `InternedMawuValue`'s
+    Object(Vec<(Arc<str>, InternedMawuValue)>),
+    /// Represents a JSON Array, made of `InternedMawuValue`'s
+    Array(Vec<InternedMawuValue>),
+    /// Represents a positive integer
+    Uint(u64),
+    /// Represents a negative integer
+    Int(i64),
+    /// Represents a floating point number
+    Float(f64),
+    /// Represents a string
+    String(String),
+    /// Represents a boolean
+    Bool(bool),
+    /// Represents `null`
+    None,
+}
+
+impl InternedMawuValue {
+    /// Returns the value of the given key, or `None` if the value is not an object or has no such key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::from_str_interned;
+    ///
+    /// let value = from_str_interned(r#"{"key": "value", "key": "last"}"#).unwrap();
+    /// assert_eq!(value.get("key").unwrap().as_str(), Some("last"));
+    /// assert!(value.get("other").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&InternedMawuValue> {
+        match self {
+            InternedMawuValue::Object(pairs) => pairs.iter().rev().find(|(k, _)| &**k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the key-value-pairs of an object in the order of the input, or `None` if the value is not an object
+    pub fn as_object(&self) -> Option<&Vec<(Arc<str>, InternedMawuValue)>> {
+        match self {
+            InternedMawuValue::Object(pairs) => Some(pairs),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of an array, or `None` if the value is not an array
+    pub fn as_array(&self) -> Option<&Vec<InternedMawuValue>> {
+        match self {
+            InternedMawuValue::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, or `None` if the value is not a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            InternedMawuValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the positive integer, or `None` if the value is not a `Uint`
+    pub fn as_uint(&self) -> Option<u64> {
+        match self {
+            InternedMawuValue::Uint(u) => Some(*u),
+            _ => None,
+        }
+    }
+
+    /// Returns the negative integer, or `None` if the value is not an `Int`
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            InternedMawuValue::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the float, or `None` if the value is not a `Float`
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            InternedMawuValue::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, or `None` if the value is not a `Bool`
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            InternedMawuValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the value is `null`
+    pub fn is_none(&self) -> bool {
+        matches!(self, InternedMawuValue::None)
+    }
+
+    /// Converts into an owned `MawuValue`, copying every key into its own `String`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str, from_str_interned};
+    ///
+    /// let input = r#"[{"name": "mawu", "stars": 5}, {"name": "json", "stars": -1}]"#;
+    /// assert_eq!(from_str_interned(input).unwrap().to_owned(), from_str(input).unwrap());
+    /// ```
+    pub fn to_owned(&self) -> MawuValue {
+        self.clone().into_owned()
+    }
+
+    /// Converts into an owned `MawuValue`, reusing the strings of all values
+    pub fn into_owned(self) -> MawuValue {
+        match self {
+            InternedMawuValue::Object(pairs) => MawuValue::Object(
+                pairs
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.into_owned()))
+                    .collect::<MawuObject>(),
+            ),
+            InternedMawuValue::Array(values) => MawuValue::Array(values.into_iter().map(|v| v.into_owned()).collect()),
+            InternedMawuValue::Uint(u) => MawuValue::Uint(u),
+            InternedMawuValue::Int(i) => MawuValue::Int(i),
+            InternedMawuValue::Float(f) => MawuValue::Float(f),
+            InternedMawuValue::String(s) => MawuValue::String(s),
+            InternedMawuValue::Bool(b) => MawuValue::Bool(b),
+            InternedMawuValue::None => MawuValue::None,
+        }
+    }
+}
+
+impl From<InternedMawuValue> for MawuValue {
+    fn from(value: InternedMawuValue) -> Self {
+        value.into_owned()
+    }
+}
+
+impl<'a> From<MawuValueRef<'a>> for InternedMawuValue {
+    fn from(value: MawuValueRef<'a>) -> Self {
+        intern(value, &mut BTreeSet::new())
+    }
+}
+
+/// Converts `value`, taking every key out of `pool` or adding it if it was not used before
+fn intern(value: MawuValueRef<'_>, pool: &mut BTreeSet<Arc<str>>) -> InternedMawuValue {
+    match value {
+        MawuValueRef::Object(pairs) => InternedMawuValue::Object(
+            pairs
+                .into_iter()
+                .map(|(k, v)| {
+                    let key = match pool.get(&*k) {
+                        Some(key) => Arc::clone(key),
+                        None => {
+                            let key: Arc<str> = Arc::from(&*k);
+                            pool.insert(Arc::clone(&key));
+                            key
+                        }
+                    };
+                    (key, intern(v, pool))
+                })
+                .collect(),
+        ),
+        MawuValueRef::Array(values) => InternedMawuValue::Array(values.into_iter().map(|v| intern(v, pool)).collect()),
+        MawuValueRef::Uint(u) => InternedMawuValue::Uint(u),
+        MawuValueRef::Int(i) => InternedMawuValue::Int(i),
+        MawuValueRef::Float(f) => InternedMawuValue::Float(f),
+        MawuValueRef::String(s) => InternedMawuValue::String(s.into_owned()),
+        MawuValueRef::Bool(b) => InternedMawuValue::Bool(b),
+        MawuValueRef::None => InternedMawuValue::None,
+    }
+}
+
+#[test]
+fn repeated_keys_are_shared() {
+    let value = InternedMawuValue::from(MawuValueRef::Array(vec![
+        MawuValueRef::Object(vec![("a".into(), MawuValueRef::Uint(1)), ("b\n".into(), MawuValueRef::None)]),
+        MawuValueRef::Object(vec![(String::from("a").into(), MawuValueRef::Uint(2)), ("b\n".into(), MawuValueRef::Bool(true))]),
+    ]));
+    let objects = value.as_array().unwrap();
+    let (first, second) = (objects[0].as_object().unwrap(), objects[1].as_object().unwrap());
+    assert!(Arc::ptr_eq(&first[0].0, &second[0].0));
+    assert!(Arc::ptr_eq(&first[1].0, &second[1].0));
+    assert!(!Arc::ptr_eq(&first[0].0, &first[1].0));
+    assert_eq!(objects[1].get("a").unwrap().as_uint(), Some(2));
+    assert_eq!(value.into_owned().as_array().unwrap()[1].get("b\n").unwrap(), &MawuValue::Bool(true));
+}
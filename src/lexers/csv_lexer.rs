@@ -1,58 +1,305 @@
-use std::{char, collections::{HashMap, VecDeque}};
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+use core::char;
 
 use crate::{
     errors::{
         csv_error::{CsvError, CsvParseError},
         MawuError,
     },
+    csv_table::CsvTable,
+    lexers::csv_parse_options::{CsvParseOptions, DuplicateHeaders, RaggedRows},
     mawu_value::MawuValue,
     utils::is_newline,
 };
 
+/// Used by `read::csv_headed`, which trims unquoted values so padded and pretty written files read back in
+#[cfg(feature = "std")]
 pub fn headed(file_contents: VecDeque<char>) -> Result<MawuValue, MawuError> {
-    let (head, left_content) = make_head(file_contents)?;
-    let body = parse_csv_body(left_content, head.len())?;
-    let mut out: Vec<HashMap<String, MawuValue>> = Default::default();
+    headed_with(file_contents, &CsvParseOptions::default().trim_fields(true))
+}
+
+pub fn headed_with(file_contents: VecDeque<char>, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+    table_with(file_contents, options).map(MawuValue::from)
+}
+
+/// Parses a headed file into a `CsvTable`, the first record always is the header
+pub fn table_with(file_contents: VecDeque<char>, options: &CsvParseOptions) -> Result<CsvTable, MawuError> {
+    if is_blank(&file_contents) {
+        return Ok(CsvTable::default());
+    }
+    let file_contents = strip_comments(file_contents, options);
+    let (head, left_content) = make_head(file_contents, options)?;
+    let head = deduplicate_head(head, options)?;
+    let body = parse_csv_body(left_content, head.len(), options)?;
+    let mut rows: Vec<Vec<MawuValue>> = Vec::with_capacity(body.len());
     for entry in body {
-        let mut tmp_bind: HashMap<String, MawuValue> = Default::default();
-        if entry.len() == head.len() {
-            for (index, value) in entry.iter().enumerate() {
-                tmp_bind.insert(head[index].clone(), value.clone());
-            }
-        } else {
+        rows.push(fit_row(head.len(), entry, options)?);
+    }
+    Ok(CsvTable { headers: head, rows })
+}
+
+/// Applies `CsvParseOptions::duplicate_headers` to the header, so no column is lost when the rows are built.
+///
+/// The n-th use of a name is suffixed with `_n`, skipping any suffixed name that is already taken,
+/// so `a,a,a_2` becomes `a`, `a_3` and `a_2`.
+fn deduplicate_head(head: Vec<String>, options: &CsvParseOptions) -> Result<Vec<String>, MawuError> {
+    let mut out: Vec<String> = Vec::with_capacity(head.len());
+    for (index, name) in head.iter().enumerate() {
+        if !head[..index].contains(name) {
+            out.push(name.clone());
+            continue;
+        }
+        if options.duplicate_headers == DuplicateHeaders::Error {
             return Err(MawuError::CsvError(CsvError::ParseError(
-                CsvParseError::ExtraValue(format!("{:?}", entry)),
+                CsvParseError::DuplicateHeader(name.clone()),
             )));
-        };
-        out.push(tmp_bind);
+        }
+        let mut n = 2;
+        while head.contains(&format!("{}_{}", name, n)) || out.contains(&format!("{}_{}", name, n)) {
+            n += 1;
+        }
+        out.push(format!("{}_{}", name, n));
+    }
+    Ok(out)
+}
+
+/// Checks that a record has one value per column, after applying `CsvParseOptions::ragged_rows`
+fn fit_row(
+    head_length: usize,
+    mut entry: Vec<MawuValue>,
+    options: &CsvParseOptions,
+) -> Result<Vec<MawuValue>, MawuError> {
+    if options.ragged_rows == RaggedRows::Fit {
+        entry.resize(head_length, MawuValue::None);
+    }
+    if entry.len() == head_length {
+        Ok(entry)
+    } else {
+        Err(MawuError::CsvError(CsvError::ParseError(
+            CsvParseError::ExtraValue(format!("{:?}", entry)),
+        )))
+    }
+}
+
+/// How many characters a chunk of `headed_parallel_with` holds at least, smaller files are not split up
+#[cfg(feature = "rayon")]
+const MIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Works like `headed_with`, but splits the body into chunks of whole records that are parsed
+/// across the threads of rayon, and concatenated in order afterwards.
+#[cfg(feature = "rayon")]
+pub fn headed_parallel_with(file_contents: VecDeque<char>, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+    let chunk_size = (file_contents.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_SIZE);
+    headed_chunked_with(file_contents, options, chunk_size)
+}
+
+#[cfg(feature = "rayon")]
+fn headed_chunked_with(
+    file_contents: VecDeque<char>,
+    options: &CsvParseOptions,
+    chunk_size: usize,
+) -> Result<MawuValue, MawuError> {
+    use rayon::prelude::*;
+
+    if is_blank(&file_contents) {
+        return Ok(MawuValue::CSVObject(Vec::new()));
     }
-    Ok(MawuValue::CSVObject(out))
+    let file_contents = strip_comments(file_contents, options);
+    let (head, left_content) = make_head(file_contents, options)?;
+    let head = deduplicate_head(head, options)?;
+    let body: Vec<char> = left_content.into();
+    let mut starts = vec![0];
+    starts.extend(record_boundaries(&body, chunk_size, options));
+    let last = starts.len() - 1;
+    let chunks = starts
+        .iter()
+        .enumerate()
+        .map(|(index, start)| &body[*start..starts.get(index + 1).copied().unwrap_or(body.len())])
+        .collect::<Vec<&[char]>>();
+    let rows = chunks
+        .into_par_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            parse_csv_records(chunk.iter().copied().collect(), head.len(), options, index == last)?
+                .into_iter()
+                .map(|entry| fit_row(head.len(), entry, options))
+                .collect::<Result<Vec<Vec<MawuValue>>, MawuError>>()
+        })
+        .collect::<Result<Vec<Vec<Vec<MawuValue>>>, MawuError>>()?;
+    Ok(MawuValue::from(CsvTable { headers: head, rows: rows.into_iter().flatten().collect() }))
 }
 
+/// Returns the indices the chunks after the first start at, every `chunk_size` characters or later.
+///
+/// A chunk only starts after a newline that is neither inside of quotes nor followed by another
+/// newline, so every chunk is parsed exactly like the whole body would have been at that point.
+/// Quotes only open a value at the start of a field, like in `parse_csv_body`.
+#[cfg(feature = "rayon")]
+fn record_boundaries(body: &[char], chunk_size: usize, options: &CsvParseOptions) -> Vec<usize> {
+    let mut boundaries: Vec<usize> = Default::default();
+    let mut next_boundary = chunk_size;
+    let mut in_quotes = false;
+    let mut field_start = true;
+    let mut index = 0;
+    while index < body.len() {
+        let c = body[index];
+        if in_quotes {
+            if c == options.quote {
+                if body.get(index + 1) == Some(&options.quote) {
+                    index += 1;
+                } else {
+                    in_quotes = false;
+                    field_start = true;
+                }
+            }
+        } else if is_newline(&c) {
+            field_start = true;
+            if index + 1 >= next_boundary && body.get(index + 1).is_some_and(|n| !is_newline(n)) {
+                boundaries.push(index + 1);
+                next_boundary = index + 1 + chunk_size;
+            }
+        } else if c == options.quote && field_start {
+            in_quotes = true;
+        } else {
+            field_start = c == options.delimiter || field_start && is_padding(c, options.delimiter);
+        }
+        index += 1;
+    }
+    boundaries
+}
+
+/// Used by `read::csv_headless`, which trims unquoted values just like `headed`
+#[cfg(feature = "std")]
 pub fn headless(file_contents: VecDeque<char>) -> Result<MawuValue, MawuError> {
-    let (head, left_content) = make_head(file_contents)?;
-    let mut body = parse_csv_body(left_content, head.len())?;
+    headless_with(file_contents, &CsvParseOptions::default().has_headers(false).trim_fields(true))
+}
+
+pub fn headless_with(file_contents: VecDeque<char>, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+    if is_blank(&file_contents) {
+        return Ok(MawuValue::CSVArray(Vec::new()));
+    }
+    let file_contents = strip_comments(file_contents, options);
+    let (head, left_content) = make_head(file_contents, options)?;
+    let mut body = parse_csv_body(left_content, head.len(), options)?;
     body.insert(
         0,
         head.into_iter()
-            .map(|s| MawuValue::from(s))
+            .map(|s| to_value(s, options))
             .collect::<Vec<MawuValue>>(),
     );
     Ok(MawuValue::CSVArray(body))
 }
 
+/// Parses the file into a `MawuValue::CSVObject` or `MawuValue::CSVArray`, depending on `CsvParseOptions::has_headers`
+pub fn csv_lexer_with(file_contents: VecDeque<char>, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+    if options.has_headers {
+        headed_with(file_contents, options)
+    } else {
+        headless_with(file_contents, options)
+    }
+}
+
+/// Turns the fields of a record into values, missing values are always `MawuValue::None`
+fn to_row(row_data: Vec<Option<String>>, options: &CsvParseOptions) -> Vec<MawuValue> {
+    row_data.into_iter().map(|s| s.map_or(MawuValue::None, |s| to_value(s, options))).collect()
+}
+
+/// Takes the field by value, so strings are moved into the `MawuValue` instead of copied
+fn to_value(s: String, options: &CsvParseOptions) -> MawuValue {
+    if (s.is_empty() && options.empty_as_none) || options.is_null_token(&s) {
+        MawuValue::None
+    } else if s.is_empty() || !options.infer_types || options.keeps_leading_zero(&s) {
+        MawuValue::String(s)
+    } else if let Some(b) = options.bool_token(&s) {
+        MawuValue::Bool(b)
+    } else {
+        MawuValue::from(s)
+    }
+}
+
+/// Removes every record starting with the comment character, newlines inside of quotes do not start a record
+fn strip_comments(file_contents: VecDeque<char>, options: &CsvParseOptions) -> VecDeque<char> {
+    let comment = match options.comment {
+        Some(c) => c,
+        None => return file_contents,
+    };
+    let mut out: VecDeque<char> = VecDeque::with_capacity(file_contents.len());
+    let mut in_quotes = false;
+    let mut record_start = true;
+    let mut in_comment = false;
+    let mut after_comment = false;
+    for c in file_contents {
+        if in_comment {
+            if is_newline(&c) {
+                in_comment = false;
+                after_comment = true;
+                record_start = true;
+            }
+            continue;
+        }
+        // the rest of a `\r\n` ending the comment
+        if after_comment && is_newline(&c) {
+            continue;
+        }
+        after_comment = false;
+        if record_start && c == comment {
+            in_comment = true;
+            continue;
+        }
+        if c == options.quote {
+            in_quotes = !in_quotes;
+        }
+        record_start = !in_quotes && is_newline(&c);
+        out.push_back(c);
+    }
+    out
+}
+
+/// Spaces and tabs around a quoted value are padding, unless they are the delimiter
+fn is_padding(c: char, delimiter: char) -> bool {
+    (c == ' ' || c == '\t') && c != delimiter
+}
+
+/// An empty or whitespace-only file holds no data, not even a header
+fn is_blank(file_contents: &VecDeque<char>) -> bool {
+    file_contents.iter().all(|c| c.is_whitespace())
+}
+
 fn parse_csv_body(
+    csv_body: VecDeque<char>,
+    head_length: usize,
+    options: &CsvParseOptions,
+) -> Result<Vec<Vec<MawuValue>>, MawuError> {
+    parse_csv_records(csv_body, head_length, options, true)
+}
+
+/// `end_of_file` is `false` for the chunks of `headed_parallel_with` that are followed by
+/// another one, as their last newline does not end the file
+fn parse_csv_records(
     mut csv_body: VecDeque<char>,
     head_length: usize,
+    options: &CsvParseOptions,
+    end_of_file: bool,
 ) -> Result<Vec<Vec<MawuValue>>, MawuError> {
+    let delimiter = options.delimiter;
+    let quote = options.quote;
     let mut out: Vec<Vec<MawuValue>> = Default::default();
-    let mut row_data: Vec<String> = Default::default();
+    // a well formed record has as many fields as the header, `None` marks a value missing from a short record
+    let mut row_data: Vec<Option<String>> = Vec::with_capacity(head_length);
     let mut last_char = None;
     while csv_body.front().is_some() {
         if let Some(h) = csv_body.pop_front() {
-            if h == '\n' && csv_body.is_empty() {
-                out.push(row_data.iter().map(|s| MawuValue::from(s)).collect());
-                row_data = Default::default();
+            if is_padding(h, delimiter)
+                && (last_char == Some(quote)
+                    || csv_body.iter().find(|c| !is_padding(**c, delimiter)) == Some(&quote))
+            {
+                // whitespace around a quoted value is skipped, `last_char` is left untouched
+                continue;
+            }
+            if h == '\n' && csv_body.is_empty() && end_of_file {
+                out.push(to_row(row_data, options));
+                row_data = Vec::new();
                 break;
             }
             let is_next_newline: bool = {
@@ -68,89 +315,133 @@ fn parse_csv_body(
                 }
             };
             if is_newline(&h) {
-                if last_char.is_none() && head_length > row_data.len() || last_char.unwrap() == ',' && head_length > row_data.len() {
-                    for _ in 0..(head_length - row_data.len()) {
-                        row_data.push(String::from(""));
-                    }
-                } else if head_length > row_data.len() {
-                    for _ in 0..(head_length - row_data.len()) {
-                        row_data.push(String::from(""));
-                    }
+                if head_length > row_data.len() {
+                    row_data.resize(head_length, None);
                 }
                 if is_next_newline {
                     let _ = csv_body.pop_front();
                 }
-                out.push(row_data.iter().map(|s| MawuValue::from(s)).collect());
+                out.push(to_row(row_data, options));
                 // assignment is only overwritten before being read if the very first character IS a newline and thus, probably, maybe, fine.
-                row_data = Default::default();
-            }  else if h == ',' {
+                row_data = Vec::with_capacity(head_length);
+            }  else if h == delimiter {
                 if is_next_newline && head_length > row_data.len() {
-                    // push as many nulls as needed to fill in the missing data
-                    for _ in 0..(head_length - row_data.len()) {
-                        row_data.push(String::from(""));
+                    if last_char.is_none_or(|c| c == delimiter || is_newline(&c)) {
+                        row_data.push(Some(String::new()));
                     }
-                } else if last_char.is_none() || last_char.unwrap() == ',' {
-                    row_data.push(String::from(""));
+                    // the empty field ending the record, then as many missing values as needed to fill in the rest
+                    if head_length > row_data.len() {
+                        row_data.push(Some(String::new()));
+                    }
+                    row_data.resize(head_length, None);
+                } else if last_char.is_none_or(|c| c == delimiter || is_newline(&c)) {
+                    // an empty field at the start of a record
+                    row_data.push(Some(String::new()));
                 }
-            } else if h == '\"' {
-                let mut value: String = Default::default();
+            } else if h == quote {
+                // the next quote ends the value, unless it is escaped
+                let mut value = String::with_capacity(csv_body.iter().position(|c| *c == quote).unwrap_or(0));
                 let mut open_quote = true;
                 while open_quote {
-                    if csv_body.front() == Some(&'\"') && csv_body.get(1) == Some(&'\"') {
-                        value.push('\"');
+                    if csv_body.front() == Some(&quote) && csv_body.get(1) == Some(&quote) {
+                        value.push(quote);
                         let _ = csv_body.pop_front();
                         let _ = csv_body.pop_front();
-                    } else if csv_body.front() == Some(&'\"') {
+                    } else if csv_body.front() == Some(&quote) {
                         let _ = csv_body.pop_front();
                         open_quote = false;
+                    } else if let Some(t) = csv_body.pop_front() {
+                        value.push(t);
                     } else {
-                        if let Some(t) = csv_body.pop_front() {
-                            value.push(t);
-                        }
+                        return Err(MawuError::CsvError(CsvError::ParseError(
+                            CsvParseError::UnterminatedQuote,
+                        )));
                     }
                 }
-                row_data.push(value);
-            } else if h == ' ' || h == '\t' {
-                let _ = h;
+                row_data.push(Some(value));
             } else {
-                let mut value: String = h.to_string();
-                while csv_body.front() != Some(&',')
-                    && !is_newline(csv_body.front().unwrap_or(&'\n'))
-                {
-                    if let Some(t) = csv_body.pop_front() {
-                        let mut entry = t.to_string();
-                        while csv_body.front() != Some(&',')
-                            && !is_newline(csv_body.front().unwrap_or(&'\n'))
-                        {
-                            if let Some(g) = csv_body.pop_front() {
-                                entry.push(g);
-                            }
-                        }
-                        entry = entry.trim_end().to_string();
-                        value.push_str(&entry);
-                    }
+                let end = csv_body
+                    .iter()
+                    .position(|c| *c == delimiter || is_newline(c))
+                    .unwrap_or(csv_body.len());
+                let mut value = String::with_capacity(end + 1);
+                value.push(h);
+                value.extend(csv_body.drain(..end));
+                if options.trim_fields {
+                    value = value.trim_matches(|c: char| c.is_ascii_whitespace()).to_string();
                 }
-                row_data.push(value);
+                row_data.push(Some(value));
             }
             last_char = Some(h)
         }
     }
     if !row_data.is_empty() {
-        out.push(row_data.iter().map(|s| MawuValue::from(s)).collect());
+        out.push(to_row(row_data, options));
     }
     Ok(out)
 }
 
+/// Splits a single record into its fields, unquoting them like `parse_csv_records` does.
+/// One trailing line ending is ignored, an empty record has no fields.
+pub fn split_record(line: &str, delimiter: char, quote: char) -> Result<Vec<String>, MawuError> {
+    let parse_error = |error: CsvParseError| MawuError::CsvError(CsvError::ParseError(error));
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    let line = line.strip_suffix('\r').unwrap_or(line);
+    let mut fields = Vec::new();
+    if line.is_empty() {
+        return Ok(fields);
+    }
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut value = String::new();
+        // whitespace around a quoted value is skipped
+        let leading = chars.clone().take_while(|c| is_padding(*c, delimiter)).count();
+        if chars.clone().nth(leading) == Some(quote) {
+            chars.nth(leading);
+            loop {
+                match chars.next() {
+                    Some(c) if c == quote && chars.peek() == Some(&quote) => {
+                        value.push(quote);
+                        chars.next();
+                    }
+                    Some(c) if c == quote => break,
+                    Some(c) => value.push(c),
+                    None => return Err(parse_error(CsvParseError::UnterminatedQuote)),
+                }
+            }
+            while chars.next_if(|c| is_padding(*c, delimiter)).is_some() {}
+            match chars.peek() {
+                Some(c) if *c != delimiter => return Err(parse_error(CsvParseError::UnescapedCharacter(*c))),
+                _ => {}
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != delimiter) {
+                if is_newline(&c) {
+                    return Err(parse_error(CsvParseError::UnexpectedNewline));
+                }
+                value.push(c);
+            }
+        }
+        fields.push(value);
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
 fn make_head(
     mut file_contents: VecDeque<char>,
+    options: &CsvParseOptions,
 ) -> Result<(Vec<String>, VecDeque<char>), MawuError> {
+    let delimiter = options.delimiter;
+    let quote = options.quote;
     let mut head_done = false;
     let mut head_out: Vec<String> = Default::default();
     while !head_done {
         if let Some(content) = file_contents.pop_front() {
             if is_newline(&content) {
                 head_done = true;
-            } else if content == ',' {
+            } else if content == delimiter {
                 // do literally nothing
                 let _ = content;
                 continue;
@@ -159,29 +450,31 @@ fn make_head(
                 let _ = content;
                 continue;
             } else {
-                if content == '\"' {
+                if content == quote {
                     let mut value: String = Default::default();
                     let mut open_quote = true;
                     while open_quote {
-                        if file_contents.front() == Some(&'\"')
-                            && file_contents.get(1) == Some(&'\"')
+                        if file_contents.front() == Some(&quote)
+                            && file_contents.get(1) == Some(&quote)
                         {
-                            value.push('\"');
+                            value.push(quote);
                             let _ = file_contents.pop_front();
                             let _ = file_contents.pop_front();
-                        } else if file_contents.front() == Some(&'\"') {
+                        } else if file_contents.front() == Some(&quote) {
                             let _ = file_contents.pop_front();
                             open_quote = false;
+                        } else if let Some(t) = file_contents.pop_front() {
+                            value.push(t);
                         } else {
-                            if let Some(t) = file_contents.pop_front() {
-                                value.push(t);
-                            }
+                            return Err(MawuError::CsvError(CsvError::ParseError(
+                                CsvParseError::UnterminatedQuote,
+                            )));
                         }
                     }
                     head_out.push(value);
                 } else {
                     let mut value: String = content.to_string();
-                    while file_contents.front() != Some(&',')
+                    while file_contents.front() != Some(&delimiter)
                         && !is_newline(file_contents.front().ok_or_else(|| {
                             MawuError::CsvError(CsvError::ParseError(
                                 CsvParseError::UnexpectedNewline,
@@ -190,7 +483,7 @@ fn make_head(
                     {
                         if let Some(t) = file_contents.pop_front() {
                             let mut entry = t.to_string();
-                            while file_contents.front() != Some(&',')
+                            while file_contents.front() != Some(&delimiter)
                                 && !is_newline(file_contents.front().ok_or_else(|| {
                                     MawuError::CsvError(CsvError::ParseError(
                                         CsvParseError::UnrecognizedHeader("".to_string()),
@@ -223,3 +516,21 @@ fn make_head(
     }
     Ok((head_out, file_contents))
 }
+
+#[test]
+#[cfg(feature = "rayon")]
+fn chunked_matches_serial() {
+    let contents = "id,text,n\r\n1,\"multi\nline, \"\"quoted\"\"\",2\r\n,\"\n\n\",3\n4,plain \"text,\n\n5,\"a\",\n6\n";
+    let options = CsvParseOptions::default().ragged_rows(RaggedRows::Fit);
+    let serial = headed_with(contents.chars().collect(), &options).unwrap();
+    // the empty line after the fourth record is skipped
+    assert_eq!(serial.as_csv_object().unwrap().len(), 5);
+    assert_eq!(serial.as_csv_object().unwrap()[1].get("id").unwrap(), &MawuValue::None);
+    // every possible chunk size, down to one record per chunk
+    for chunk_size in 1..contents.len() {
+        let chunked = headed_chunked_with(contents.chars().collect(), &options, chunk_size).unwrap();
+        assert_eq!(chunked, serial, "chunk size {}", chunk_size);
+    }
+    let boundaries = record_boundaries(&contents.chars().skip(9).collect::<Vec<char>>(), 1, &options);
+    assert_eq!(boundaries.len(), 5);
+}
@@ -0,0 +1,252 @@
+use crate::prelude::*;
+
+/// What to do with rows of a headed CSV file that do not have as many values as the header.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RaggedRows {
+    /// Return a `CsvParseError::ExtraValue`
+    #[default]
+    Error,
+    /// Cut off any extra values, and fill in missing values with `MawuValue::None`
+    Fit,
+}
+
+/// What to do with header names that are used more than once in a headed CSV file.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DuplicateHeaders {
+    /// Return a `CsvParseError::DuplicateHeader`
+    Error,
+    /// Keep every column by adding a suffix to the repeated names, so `name,name` becomes `name` and `name_2`
+    #[default]
+    Suffix,
+}
+
+/// Options changing how CSV is parsed.
+///
+/// `CsvParseOptions::default()` and `new()` return the options `read::csv_headed` uses, except for
+/// `trim_fields`, which `read::csv_headed` turns on. Every option can then be changed with the method of the same name.
+///
+/// ## Example
+/// ```rust
+/// use mawu::csv::CsvParseOptions;
+///
+/// let options = CsvParseOptions::new()
+///     .delimiter(';')
+///     .comment(Some('#'))
+///     .has_headers(false);
+/// assert_ne!(options, CsvParseOptions::default());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvParseOptions {
+    pub(crate) delimiter: char,
+    pub(crate) quote: char,
+    pub(crate) comment: Option<char>,
+    pub(crate) infer_types: bool,
+    pub(crate) preserve_leading_zeros: bool,
+    pub(crate) ragged_rows: RaggedRows,
+    pub(crate) has_headers: bool,
+    pub(crate) empty_as_none: bool,
+    pub(crate) null_tokens: Vec<String>,
+    pub(crate) null_tokens_ignore_case: bool,
+    pub(crate) true_tokens: Vec<String>,
+    pub(crate) false_tokens: Vec<String>,
+    pub(crate) bool_tokens_ignore_case: bool,
+    pub(crate) trim_fields: bool,
+    pub(crate) duplicate_headers: DuplicateHeaders,
+}
+
+impl Default for CsvParseOptions {
+    fn default() -> Self {
+        CsvParseOptions {
+            delimiter: ',',
+            quote: '\"',
+            comment: None,
+            infer_types: true,
+            preserve_leading_zeros: false,
+            ragged_rows: RaggedRows::Error,
+            has_headers: true,
+            empty_as_none: true,
+            null_tokens: Vec::new(),
+            null_tokens_ignore_case: false,
+            true_tokens: Vec::new(),
+            false_tokens: Vec::new(),
+            bool_tokens_ignore_case: false,
+            trim_fields: false,
+            duplicate_headers: DuplicateHeaders::Suffix,
+        }
+    }
+}
+
+impl CsvParseOptions {
+    /// Creates new `CsvParseOptions`, identical to `CsvParseOptions::default()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The character separating the values, must not be the quote character or a newline.
+    ///
+    /// Default: `,`
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// The character used to quote values. Inside of a quoted value, it is escaped by doubling it.
+    ///
+    /// Default: `"`
+    pub fn quote(mut self, quote: char) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    /// If `Some`, every record starting with this character is skipped, including the header.
+    ///
+    /// Default: `None`
+    pub fn comment(mut self, comment: Option<char>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    /// If `true`, values are parsed into the matching `MawuValue` type, e.g. `1` into a `MawuValue::Uint`.
+    /// If `false`, every value is kept as a `MawuValue::String`. Empty values follow `empty_as_none` either way.
+    ///
+    /// Default: `true`
+    pub fn infer_types(mut self, infer: bool) -> Self {
+        self.infer_types = infer;
+        self
+    }
+
+    /// If `true`, numbers with a leading zero, like `007` or `-01`, are kept as a `MawuValue::String`,
+    /// as they are usually identifiers like zip codes whose zeros would be lost as a number.
+    /// A single `0` and numbers like `0.5` are not affected. Only used if `infer_types` is `true`.
+    ///
+    /// Default: `false`, `007` is parsed into `MawuValue::Uint(7)`
+    pub fn preserve_leading_zeros(mut self, preserve: bool) -> Self {
+        self.preserve_leading_zeros = preserve;
+        self
+    }
+
+    /// What to do with rows of a headed file that do not have as many values as the header.
+    /// Headless files have no header to compare against, and keep every row as it is.
+    ///
+    /// Default: `RaggedRows::Error`
+    pub fn ragged_rows(mut self, ragged_rows: RaggedRows) -> Self {
+        self.ragged_rows = ragged_rows;
+        self
+    }
+
+    /// If `true`, the first record is used as the header and a `MawuValue::CSVObject` is returned.
+    /// If `false`, a `MawuValue::CSVArray` holding every record is returned.
+    ///
+    /// Default: `true`
+    pub fn has_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// If `true`, empty values, like the middle one of `a,,c`, are parsed into `MawuValue::None`,
+    /// the same value a JSON `null` is parsed into.
+    /// If `false`, they are kept as an empty `MawuValue::String`, so an empty value can be told apart from
+    /// an explicit null, which can then be marked with one of the `null_tokens`.
+    /// Values missing from a short row are not empty but absent, and are always `MawuValue::None`.
+    ///
+    /// Default: `true`
+    pub fn empty_as_none(mut self, empty_as_none: bool) -> Self {
+        self.empty_as_none = empty_as_none;
+        self
+    }
+
+    /// Values that are parsed into `MawuValue::None`, like `NULL`, `NA` or `-`, before their type is inferred.
+    /// Empty values are `MawuValue::None` unless `empty_as_none` is `false`, even then the empty string can be one of the tokens.
+    ///
+    /// Default: no tokens, only empty values are `MawuValue::None`
+    pub fn null_tokens(mut self, tokens: Vec<String>) -> Self {
+        self.null_tokens = tokens;
+        self
+    }
+
+    /// If `true`, values match the `null_tokens` ignoring ASCII case, so `NULL` also matches `null` and `Null`.
+    ///
+    /// Default: `false`
+    pub fn null_tokens_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.null_tokens_ignore_case = ignore_case;
+        self
+    }
+
+    /// Values that are parsed into `MawuValue::Bool(true)`, like `yes`, `Y` or `1`, in addition to `true`.
+    /// They are checked before any other type is inferred, so `1` becomes a boolean instead of a number.
+    /// Only used if `infer_types` is `true`, and `null_tokens` take precedence.
+    ///
+    /// Default: no tokens, only `true` is `MawuValue::Bool(true)`
+    pub fn true_tokens(mut self, tokens: Vec<String>) -> Self {
+        self.true_tokens = tokens;
+        self
+    }
+
+    /// Values that are parsed into `MawuValue::Bool(false)`, like `no`, `N` or `0`, in addition to `false`.
+    /// Works exactly like `true_tokens`.
+    ///
+    /// Default: no tokens, only `false` is `MawuValue::Bool(false)`
+    pub fn false_tokens(mut self, tokens: Vec<String>) -> Self {
+        self.false_tokens = tokens;
+        self
+    }
+
+    /// If `true`, `true`, `false` and the `true_tokens` and `false_tokens` are matched ignoring ASCII case,
+    /// so `TRUE` and `True` are booleans as well.
+    ///
+    /// Default: `false`
+    pub fn bool_tokens_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.bool_tokens_ignore_case = ignore_case;
+        self
+    }
+
+    /// If `true`, leading and trailing ASCII whitespace is removed from unquoted values before their type is inferred,
+    /// so ` 42 ` becomes a `MawuValue::Uint`. Quoted values are never changed, and header names are always trimmed.
+    ///
+    /// Default: `false`, every character of a value is kept. The reading functions not taking `CsvParseOptions`,
+    /// like `read::csv_headed`, always trim.
+    pub fn trim_fields(mut self, trim: bool) -> Self {
+        self.trim_fields = trim;
+        self
+    }
+
+    /// What to do with header names that are used more than once.
+    /// Headless files have no header, and keep every row as it is.
+    ///
+    /// Default: `DuplicateHeaders::Suffix`
+    pub fn duplicate_headers(mut self, duplicate_headers: DuplicateHeaders) -> Self {
+        self.duplicate_headers = duplicate_headers;
+        self
+    }
+
+    /// Returns `true` if the value is one of the `null_tokens`
+    pub(crate) fn is_null_token(&self, value: &str) -> bool {
+        self.null_tokens.iter().any(|token| matches_token(token, value, self.null_tokens_ignore_case))
+    }
+
+    /// Returns `true` if the value should be kept as a string because of its leading zero
+    pub(crate) fn keeps_leading_zero(&self, value: &str) -> bool {
+        let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value).as_bytes();
+        self.preserve_leading_zeros && matches!(unsigned, [b'0', b'0'..=b'9', ..])
+    }
+
+    /// Returns the boolean the value stands for, if it is `true`, `false` or one of the `true_tokens` or `false_tokens`
+    pub(crate) fn bool_token(&self, value: &str) -> Option<bool> {
+        let ignore_case = self.bool_tokens_ignore_case;
+        if matches_token("true", value, ignore_case) || self.true_tokens.iter().any(|token| matches_token(token, value, ignore_case)) {
+            Some(true)
+        } else if matches_token("false", value, ignore_case) || self.false_tokens.iter().any(|token| matches_token(token, value, ignore_case)) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+fn matches_token(token: &str, value: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        token.eq_ignore_ascii_case(value)
+    } else {
+        token == value
+    }
+}
@@ -0,0 +1,154 @@
+use crate::prelude::*;
+use crate::mawu_value::MawuValue;
+
+/// The delimiters `detect_delimiter` chooses from
+const CANDIDATES: [char; 4] = [',', ';', '\t', '|'];
+/// How many records of the sample are looked at
+const SAMPLE_RECORDS: usize = 10;
+/// How many characters of a file are handed to `detect_delimiter`
+#[cfg(feature = "std")]
+pub const SAMPLE_SIZE: usize = 64 * 1024;
+
+/// Splits the sample into records, ignoring newlines inside of quotes.
+/// Whitespace-only records are skipped, and the last record is dropped if the sample was cut
+/// off in the middle of it.
+fn sample_records(sample: &str) -> Vec<String> {
+    let mut records: Vec<String> = Default::default();
+    let mut record = String::new();
+    let mut in_quotes = false;
+    for c in sample.chars() {
+        if c == '\"' {
+            in_quotes = !in_quotes;
+        }
+        if !in_quotes && (c == '\n' || c == '\r') {
+            if record.trim().is_empty() {
+                record.clear();
+            } else {
+                records.push(core::mem::take(&mut record));
+            }
+        } else {
+            record.push(c);
+        }
+        if records.len() == SAMPLE_RECORDS {
+            return records;
+        }
+    }
+    if !record.trim().is_empty() {
+        records.push(record);
+    }
+    if records.len() > 1 && (in_quotes || !sample.ends_with(['\n', '\r'])) {
+        let _ = records.pop();
+    }
+    records
+}
+
+/// Counts how often `delimiter` appears outside of quotes
+fn count_outside_quotes(record: &str, delimiter: char) -> usize {
+    let mut in_quotes = false;
+    let mut count = 0;
+    for c in record.chars() {
+        if c == '\"' {
+            in_quotes = !in_quotes;
+        } else if c == delimiter && !in_quotes {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Returns the only candidate appearing the same, non-zero, number of times in every record of
+/// the sample, or `None` if no candidate or more than one candidate does.
+pub fn detect_delimiter(sample: &str) -> Option<char> {
+    let records = sample_records(sample);
+    if records.is_empty() {
+        return None;
+    }
+    let mut consistent = CANDIDATES.iter().filter(|delimiter| {
+        let first = count_outside_quotes(&records[0], **delimiter);
+        first > 0 && records.iter().all(|record| count_outside_quotes(record, **delimiter) == first)
+    });
+    match (consistent.next(), consistent.next()) {
+        (Some(delimiter), None) => Some(*delimiter),
+        _ => None,
+    }
+}
+
+/// Splits a record into its fields, removing quotes
+fn split_fields(record: &str, delimiter: char) -> Vec<String> {
+    let mut fields: Vec<String> = Default::default();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = record.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\"' {
+            if in_quotes && chars.peek() == Some(&'\"') {
+                field.push('\"');
+                let _ = chars.next();
+            } else {
+                in_quotes = !in_quotes;
+            }
+        } else if c == delimiter && !in_quotes {
+            fields.push(core::mem::take(&mut field).trim().to_string());
+        } else {
+            field.push(c);
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Guesses if the first record of the sample is a header.
+///
+/// The first record has to be made of non-empty, unique values that are not numbers, while
+/// at least one of the following records has to contain a number.
+/// If there are no numbers at all, `false` is returned, as reading a headed file as headless
+/// loses no data.
+pub fn has_header(sample: &str, delimiter: char) -> bool {
+    let records = sample_records(sample);
+    if records.len() < 2 {
+        return false;
+    }
+    let head = split_fields(&records[0], delimiter);
+    let plain_text = |field: &String| !field.is_empty() && !MawuValue::from(field.as_str()).is_number();
+    let unique = head.iter().enumerate().all(|(i, field)| !head[..i].contains(field));
+    if !unique || !head.iter().all(plain_text) {
+        return false;
+    }
+    records[1..].iter().any(|record| {
+        split_fields(record, delimiter)
+            .iter()
+            .any(|field| MawuValue::from(field.as_str()).is_number())
+    })
+}
+
+#[test]
+fn detect_delimiters() {
+    assert_eq!(detect_delimiter("a,b,c\n1,2,3\n"), Some(','));
+    assert_eq!(detect_delimiter("a;b;c\n1;2,5;3\n"), Some(';'));
+    assert_eq!(detect_delimiter("a\tb\n\"1\t2\"\t3\n"), Some('\t'));
+    assert_eq!(detect_delimiter("a|b\r\n1|2\r\n"), Some('|'));
+    assert_eq!(detect_delimiter("\"a,b\";c\n\"multi\nline, value\";d\n"), Some(';'));
+    // cut off in the middle of the last record
+    assert_eq!(detect_delimiter("a,b,c\n1,2,3\n4,5"), Some(','));
+    // both are consistent
+    assert_eq!(detect_delimiter("a,b;c\n1,2;3\n"), None);
+    // neither is consistent
+    assert_eq!(detect_delimiter("a,b\n1,2,3\n"), None);
+    assert_eq!(detect_delimiter("single\ncolumn\n"), None);
+    assert_eq!(detect_delimiter(""), None);
+}
+
+#[test]
+fn detect_headers() {
+    assert!(has_header("name,age\nAda,36\nBob,42\n", ','));
+    assert!(has_header("\"last, first\";score\n\"Lovelace, Ada\";15\n", ';'));
+    assert!(!has_header("1,2,3\n4,5,6\n", ','));
+    assert!(!has_header("Ada,36\nBob,42\n", ','));
+    // no numbers to tell the header apart
+    assert!(!has_header("name,city\nAda,London\n", ','));
+    // duplicate or empty names
+    assert!(!has_header("name,name\nAda,36\n", ','));
+    assert!(!has_header("name,\nAda,36\n", ','));
+    assert!(!has_header("name,age\n", ','));
+    assert_eq!(split_fields("a, \"b,\"\"c\"\"\" ,", ','), vec!["a", "b,\"c\"", ""]);
+}
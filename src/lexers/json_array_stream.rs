@@ -0,0 +1,337 @@
+use std::{
+    collections::VecDeque,
+    io::{BufReader, Bytes, ErrorKind, Read},
+};
+
+use crate::{
+    errors::{
+        json_error::{JsonError, JsonParseError},
+        MawuError,
+    },
+    lexers::{json_lexer::json_lexer_with, json_parse_options::JsonParseOptions},
+    mawu_value::MawuValue,
+    utils::is_whitespace,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum StreamState {
+    Start,
+    FirstElement,
+    NextElement,
+    Done,
+}
+
+/// Lazily parses the elements of a top-level JSON array, one element per call to `next()`.
+///
+/// Only the characters of the current element are held in memory.
+/// Errors inside an element are returned for that element only and the stream continues with
+/// the next one, while structural errors (no opening `[`, unexpected end of file) end the stream.
+///
+/// The elements are parsed according to the same `JsonParseOptions` as `read::json` would use for the
+/// whole array, so anything but whitespace between an element and the next comma is an error, and
+/// extra commas are skipped or returned as the error of an element.
+pub struct JsonArrayStream<R: Read> {
+    bytes: Bytes<BufReader<R>>,
+    peeked: Option<char>,
+    state: StreamState,
+    options: JsonParseOptions,
+}
+
+impl<R: Read> JsonArrayStream<R> {
+    pub fn new(reader: R) -> Self {
+        JsonArrayStream::new_with(reader, &JsonParseOptions::default())
+    }
+
+    pub fn new_with(reader: R, options: &JsonParseOptions) -> Self {
+        JsonArrayStream {
+            bytes: BufReader::new(reader).bytes(),
+            peeked: None,
+            state: StreamState::Start,
+            options: options.clone(),
+        }
+    }
+
+    fn read_byte(&mut self) -> Result<Option<u8>, MawuError> {
+        match self.bytes.next() {
+            Some(Ok(b)) => Ok(Some(b)),
+            Some(Err(e)) => Err(MawuError::IoError(e)),
+            None => Ok(None),
+        }
+    }
+
+    /// Decodes the next UTF-8 character from the reader
+    fn read_char(&mut self) -> Result<Option<char>, MawuError> {
+        let first = match self.read_byte()? {
+            Some(b) => b,
+            None => return Ok(None),
+        };
+        let width = if first < 0x80 {
+            1
+        } else if first >> 5 == 0b110 {
+            2
+        } else if first >> 4 == 0b1110 {
+            3
+        } else if first >> 3 == 0b11110 {
+            4
+        } else {
+            0
+        };
+        let mut buffer = vec![first];
+        for _ in 1..width {
+            match self.read_byte()? {
+                Some(b) => buffer.push(b),
+                None => break,
+            }
+        }
+        match std::str::from_utf8(&buffer) {
+            Ok(s) => Ok(s.chars().next()),
+            Err(_) => Err(MawuError::IoError(std::io::Error::new(
+                ErrorKind::InvalidData,
+                "stream did not contain valid UTF-8",
+            ))),
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<char>, MawuError> {
+        if self.peeked.is_none() {
+            self.peeked = self.read_char()?;
+        }
+        Ok(self.peeked)
+    }
+
+    fn skip_whitespace(&mut self) -> Result<(), MawuError> {
+        while let Some(c) = self.peek()? {
+            if !is_whitespace(&c) {
+                break;
+            }
+            self.peeked = None;
+        }
+        Ok(())
+    }
+
+    /// Collects the characters of the next element, up to but excluding the `,` or `]` ending it
+    fn collect_element(&mut self) -> Result<VecDeque<char>, MawuError> {
+        let mut element: VecDeque<char> = Default::default();
+        let mut depth: usize = 0;
+        // the quote the current string was opened with
+        let mut quote = None;
+        let mut escaped = false;
+        loop {
+            let c = match self.peek()? {
+                Some(c) => c,
+                None => {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedEndOfFile,
+                    )))
+                }
+            };
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
+                }
+            } else if depth == 0 && (c == ',' || c == ']') {
+                break;
+            } else if c == '{' || c == '[' {
+                depth += 1;
+            } else if c == '}' || c == ']' {
+                depth = depth.saturating_sub(1);
+            } else if c == '\"' || c == '\'' && self.options.allow_single_quotes {
+                quote = Some(c);
+            }
+            element.push_back(c);
+            self.peeked = None;
+        }
+        while element.back().is_some_and(is_whitespace) {
+            let _ = element.pop_back();
+        }
+        Ok(element)
+    }
+
+    /// The outer `Result` holds errors ending the stream, the inner one errors of a single element
+    fn next_element(&mut self) -> Result<Option<Result<MawuValue, MawuError>>, MawuError> {
+        self.skip_whitespace()?;
+        if self.state == StreamState::Start {
+            // a byte order mark is only allowed at the very start, see `JsonParseError::UnexpectedBom`
+            if self.peek()? == Some('\u{FEFF}') {
+                self.peeked = None;
+                self.skip_whitespace()?;
+            }
+            match self.peek()? {
+                Some('[') if self.options.max_depth == 0 => {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::MaxDepthExceeded(self.options.max_depth),
+                    )))
+                }
+                Some('[') => {
+                    self.peeked = None;
+                    self.state = StreamState::FirstElement;
+                    self.skip_whitespace()?;
+                }
+                Some(c) => {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedCharacter(c.to_string()),
+                    )))
+                }
+                None => {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedEndOfFile,
+                    )))
+                }
+            }
+        }
+        loop {
+            match self.peek()? {
+                Some(']') => {
+                    self.peeked = None;
+                    return Ok(None);
+                }
+                Some(',') if self.state == StreamState::NextElement => {
+                    self.peeked = None;
+                    self.skip_whitespace()?;
+                }
+                Some(c) if self.state == StreamState::NextElement => {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedCharacter(c.to_string()),
+                    )))
+                }
+                Some(_) => {}
+                None => {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedEndOfFile,
+                    )))
+                }
+            }
+            let element = self.collect_element()?;
+            let first = self.state == StreamState::FirstElement;
+            self.state = StreamState::NextElement;
+            if !element.is_empty() {
+                // the element is parsed on its own, so it is one level less deep than inside of the array,
+                // and nothing may follow it but the `,` or `]` ending it
+                let options = self.options.clone()
+                    .allow_trailing_characters(false)
+                    .max_depth(self.options.max_depth - 1);
+                return Ok(Some(json_lexer_with(element, &options)));
+            } else if !self.options.allow_extra_commas {
+                // a comma right before the closing bracket is a trailing one, any other is leading or doubled
+                let token = match self.peek()? {
+                    Some(']') if !first => "]",
+                    _ => ",",
+                };
+                return Ok(Some(Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidStructuralToken(token.to_string()),
+                )))));
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for JsonArrayStream<R> {
+    type Item = Result<MawuValue, MawuError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == StreamState::Done {
+            return None;
+        }
+        match self.next_element() {
+            Ok(Some(element)) => Some(element),
+            Ok(None) => {
+                self.state = StreamState::Done;
+                None
+            }
+            Err(e) => {
+                self.state = StreamState::Done;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[test]
+fn stream_array_elements() {
+    let input = "[ {\"a\": [1, 2]}, \"x,]\" ,\n -3, [], null ]".as_bytes();
+    let elements = JsonArrayStream::new(input)
+        .collect::<Result<Vec<MawuValue>, MawuError>>()
+        .unwrap();
+    assert_eq!(elements.len(), 5);
+    assert_eq!(elements[0].get("a").unwrap().len(), 2);
+    assert_eq!(elements[1], MawuValue::String("x,]".to_string()));
+    assert_eq!(elements[2], MawuValue::Int(-3));
+    assert_eq!(elements[3], MawuValue::Array(vec![]));
+    assert_eq!(elements[4], MawuValue::None);
+
+    assert_eq!(JsonArrayStream::new("[]".as_bytes()).count(), 0);
+    let umlaut = JsonArrayStream::new("[\"\u{e4}\u{2603}\"]".as_bytes()).next();
+    assert_eq!(umlaut.unwrap().unwrap(), MawuValue::String("\u{e4}\u{2603}".to_string()));
+}
+
+#[test]
+fn stream_array_errors() {
+    // a broken element only fails itself
+    let elements = JsonArrayStream::new("[1, tru, 3]".as_bytes()).collect::<Vec<_>>();
+    assert_eq!(elements.len(), 3);
+    assert!(elements[0].is_ok());
+    assert!(elements[1].is_err());
+    assert_eq!(elements[2].as_ref().unwrap(), &MawuValue::Uint(3));
+
+    let not_an_array = JsonArrayStream::new("{}".as_bytes()).collect::<Vec<_>>();
+    assert_eq!(not_an_array.len(), 1);
+    assert!(not_an_array[0].is_err());
+
+    let truncated = JsonArrayStream::new("[1, {\"a\": ".as_bytes()).collect::<Vec<_>>();
+    assert_eq!(truncated.len(), 2);
+    assert!(truncated[1].is_err());
+
+    let empty_element = JsonArrayStream::new_with("[1,,2]".as_bytes(), &JsonParseOptions::strict()).collect::<Vec<_>>();
+    assert_eq!(empty_element.len(), 3);
+    assert!(empty_element[1].is_err());
+
+    // nothing but whitespace may follow an element before its comma
+    for input in ["[1 2, 3]", "[{\"a\":1} {\"b\":2}]", "[\"a\" x]"] {
+        let elements = JsonArrayStream::new(input.as_bytes()).collect::<Vec<_>>();
+        assert!(matches!(
+            elements[0],
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::TrailingCharacters)))
+        ), "{}", input);
+    }
+    let elements = JsonArrayStream::new("[1 2, 3]".as_bytes()).collect::<Vec<_>>();
+    assert_eq!(elements.len(), 2);
+    assert_eq!(elements[1].as_ref().unwrap(), &MawuValue::Uint(3));
+}
+
+#[test]
+fn stream_array_options() {
+    let stream = |input: &str, options: &JsonParseOptions| JsonArrayStream::new_with(input.as_bytes(), options).collect::<Vec<_>>();
+    let structural = |res: &Result<MawuValue, MawuError>, token: &str| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidStructuralToken(ref t)))) if t == token)
+    };
+    // extra commas are skipped by default, like `read::json` does
+    for input in ["[1,]", "[1,,2]", "[,1]", "[,]", "[ , 1 , , ]"] {
+        let elements = stream(input, &JsonParseOptions::default());
+        let expected = crate::lexers::json_lexer::json_lexer(input.chars().collect()).unwrap();
+        assert_eq!(MawuValue::from(elements.into_iter().collect::<Result<Vec<MawuValue>, MawuError>>().unwrap()), expected, "{}", input);
+    }
+    let strict = JsonParseOptions::strict();
+    let trailing = stream("[1,]", &strict);
+    assert_eq!(trailing.len(), 2);
+    assert!(structural(&trailing[1], "]"));
+    assert!(structural(&stream("[,1]", &strict)[0], ","));
+    assert!(structural(&stream("[1,,2]", &strict)[1], ","));
+
+    // single quoted strings may hold the tokens ending an element
+    let quoted = stream("['a,]', \"b'\", {'c': '}'}]", &JsonParseOptions::lenient());
+    assert_eq!(quoted.len(), 3);
+    assert_eq!(quoted[0].as_ref().unwrap(), &MawuValue::String("a,]".to_string()));
+    assert_eq!(quoted[1].as_ref().unwrap(), &MawuValue::String("b'".to_string()));
+    assert_eq!(quoted[2].as_ref().unwrap().get("c").unwrap(), &MawuValue::String("}".to_string()));
+    assert!(stream("['a']", &JsonParseOptions::default())[0].is_err());
+
+    // the array itself counts towards the depth
+    assert!(stream("[[1]]", &JsonParseOptions::new().max_depth(2))[0].is_ok());
+    assert!(stream("[[1]]", &JsonParseOptions::new().max_depth(1))[0].is_err());
+    assert!(stream("[1]", &JsonParseOptions::new().max_depth(0))[0].is_err());
+    assert_eq!(stream("\u{FEFF}[1]", &JsonParseOptions::default()).len(), 1);
+}
@@ -0,0 +1,503 @@
+use crate::prelude::*;
+use alloc::borrow::Cow;
+
+use crate::{
+    errors::{
+        json_error::{JsonError, JsonParseError},
+        MawuError,
+    },
+    lexers::{
+        json_lexer::{check_number_range, is_identifier_char, is_json_number, parse_radix_number, radix_of},
+        json_parse_options::JsonParseOptions,
+    },
+    mawu_value::MawuValue,
+    mawu_value_ref::MawuValueRef,
+    utils::unescape_unicode,
+};
+
+// Works on the bytes of the input instead of a `VecDeque<char>`, so that strings can be sliced
+// out of it. All structural tokens are ASCII, so every position checked against them is also a
+// char boundary.
+
+fn parse_error(error: JsonParseError) -> MawuError {
+    MawuError::JsonError(JsonError::ParseError(error))
+}
+
+/// Returns the char starting at `pos`, for error messages
+fn char_at(input: &str, pos: usize) -> String {
+    input[pos..].chars().next().map(|c| c.to_string()).unwrap_or_default()
+}
+
+fn skip_whitespace(input: &str, pos: &mut usize) {
+    while let Some(b' ' | b'\t' | b'\n' | b'\r') = input.as_bytes().get(*pos) {
+        *pos += 1;
+    }
+}
+
+/// A byte order mark anywhere but at the very start gets its own error, whatever was expected in its place
+fn unexpected_bom(input: &str, pos: usize, error: MawuError) -> MawuError {
+    if input.get(pos..).is_some_and(|rest| rest.starts_with('\u{FEFF}')) {
+        parse_error(JsonParseError::UnexpectedBom)
+    } else {
+        error
+    }
+}
+
+pub fn json_borrowed_lexer<'a>(input: &'a str, options: &JsonParseOptions) -> Result<MawuValueRef<'a>, MawuError> {
+    let (value, mut pos) = json_borrowed_lexer_partial(input, options)?;
+    skip_whitespace(input, &mut pos);
+    if pos < input.len() && !options.allow_trailing_characters {
+        return Err(unexpected_bom(input, pos, parse_error(JsonParseError::TrailingCharacters)));
+    }
+    Ok(value)
+}
+
+/// Parses the first value of `input`, returning it together with the byte offset right after it.
+/// Anything after the value is left for the caller.
+pub fn json_borrowed_lexer_partial<'a>(
+    input: &'a str,
+    options: &JsonParseOptions,
+) -> Result<(MawuValueRef<'a>, usize), MawuError> {
+    let mut pos = if input.starts_with('\u{FEFF}') { '\u{FEFF}'.len_utf8() } else { 0 };
+    skip_whitespace(input, &mut pos);
+    if pos == input.len() {
+        if !options.allow_empty_documents {
+            return Err(parse_error(JsonParseError::UnexpectedEndOfFile));
+        }
+        return Ok((MawuValueRef::None, pos));
+    }
+    let value = json_borrowed_value_lexer(input, &mut pos, options, 0).map_err(|err| unexpected_bom(input, pos, err))?;
+    Ok((value, pos))
+}
+
+/// `depth` is the number of arrays and objects the value is nested in
+fn json_borrowed_value_lexer<'a>(
+    input: &'a str,
+    pos: &mut usize,
+    options: &JsonParseOptions,
+    depth: usize,
+) -> Result<MawuValueRef<'a>, MawuError> {
+    skip_whitespace(input, pos);
+    let rest = &input[*pos..];
+    let this_byte = match rest.as_bytes().first() {
+        Some(b) => *b,
+        None => return Err(parse_error(JsonParseError::UnexpectedEndOfFile)),
+    };
+    if (this_byte == b'{' || this_byte == b'[') && depth >= options.max_depth {
+        return Err(parse_error(JsonParseError::MaxDepthExceeded(options.max_depth)));
+    }
+    match this_byte {
+        b'{' => json_borrowed_object_lexer(input, pos, options, depth + 1),
+        b'[' => json_borrowed_array_lexer(input, pos, options, depth + 1),
+        b'"' => Ok(MawuValueRef::String(json_borrowed_string_lexer(input, pos, options)?)),
+        b'\'' if options.allow_single_quotes => {
+            Ok(MawuValueRef::String(json_borrowed_string_lexer(input, pos, options)?))
+        }
+        b'-' | b'0'..=b'9' => json_borrowed_number_lexer(input, pos, options),
+        b'+' if options.allow_leading_plus && rest.as_bytes().get(1).is_some_and(u8::is_ascii_digit) => {
+            // the plus itself is dropped
+            *pos += 1;
+            json_borrowed_number_lexer(input, pos, options)
+        }
+        b'+' => {
+            let end = rest.find(|c: char| c == ',' || c == ':' || c == ']' || c == '}' || c.is_whitespace());
+            Err(parse_error(JsonParseError::InvalidNumber(rest[..end.unwrap_or(rest.len())].to_string())))
+        }
+        b'}' | b']' | b',' | b':' => Err(parse_error(JsonParseError::InvalidStructuralToken(
+            (this_byte as char).to_string(),
+        ))),
+        _ if rest.starts_with("NaN") || rest.starts_with("nan") => {
+            Err(parse_error(JsonParseError::InvalidNumber("NaN".to_string())))
+        }
+        _ if rest.starts_with("Inf") || rest.starts_with("inf") => {
+            Err(parse_error(JsonParseError::InvalidNumber("Infinity".to_string())))
+        }
+        _ => {
+            for (literal, value) in [
+                ("true", MawuValueRef::Bool(true)),
+                ("false", MawuValueRef::Bool(false)),
+                ("null", MawuValueRef::None),
+            ] {
+                if rest.starts_with(literal) {
+                    *pos += literal.len();
+                    return Ok(value);
+                }
+            }
+            Err(parse_error(JsonParseError::InvalidCharacter(char_at(input, *pos))))
+        }
+    }
+}
+
+fn json_borrowed_object_lexer<'a>(
+    input: &'a str,
+    pos: &mut usize,
+    options: &JsonParseOptions,
+    depth: usize,
+) -> Result<MawuValueRef<'a>, MawuError> {
+    // skip the `{`
+    *pos += 1;
+    let mut pairs: Vec<(Cow<'a, str>, MawuValueRef<'a>)> = Default::default();
+    // a member is expected right after the opening brace and after every comma
+    let mut expect_member = true;
+    loop {
+        skip_whitespace(input, pos);
+        match input.as_bytes().get(*pos) {
+            None => return Err(parse_error(JsonParseError::UnexpectedEndOfFile)),
+            Some(b'}') => {
+                // a comma right before the closing brace is a trailing one
+                if expect_member && !pairs.is_empty() && !options.allow_extra_commas {
+                    return Err(parse_error(JsonParseError::InvalidStructuralToken("}".to_string())));
+                }
+                *pos += 1;
+                return Ok(MawuValueRef::Object(pairs));
+            }
+            Some(b',') => {
+                if expect_member && !options.allow_extra_commas {
+                    return Err(parse_error(JsonParseError::InvalidStructuralToken(",".to_string())));
+                }
+                *pos += 1;
+                expect_member = true;
+            }
+            Some(_) if !expect_member => return Err(parse_error(JsonParseError::ExpectedEndOfObject)),
+            Some(&this_byte) => {
+                let key = match this_byte {
+                    b'"' => json_borrowed_string_lexer(input, pos, options)?,
+                    b'\'' if options.allow_single_quotes => json_borrowed_string_lexer(input, pos, options)?,
+                    b if options.allow_unquoted_keys && is_identifier_char(b as char, true) => {
+                        let start = *pos;
+                        *pos += input[start..].find(|c| !is_identifier_char(c, false)).unwrap_or(input.len() - start);
+                        Cow::Borrowed(&input[start..*pos])
+                    }
+                    _ => return Err(parse_error(JsonParseError::ExpectedKey)),
+                };
+                skip_whitespace(input, pos);
+                match input.as_bytes().get(*pos) {
+                    Some(b':') => {}
+                    None => return Err(parse_error(JsonParseError::UnexpectedEndOfFile)),
+                    _ => return Err(parse_error(JsonParseError::ExpectedColon)),
+                }
+                *pos += 1;
+                let value = json_borrowed_value_lexer(input, pos, options, depth)?;
+                pairs.push((key, value));
+                expect_member = false;
+            }
+        }
+    }
+}
+
+fn json_borrowed_array_lexer<'a>(
+    input: &'a str,
+    pos: &mut usize,
+    options: &JsonParseOptions,
+    depth: usize,
+) -> Result<MawuValueRef<'a>, MawuError> {
+    // skip the `[`
+    *pos += 1;
+    let mut values: Vec<MawuValueRef<'a>> = Default::default();
+    // a value is expected right after the opening bracket and after every comma
+    let mut expect_value = true;
+    loop {
+        skip_whitespace(input, pos);
+        match input.as_bytes().get(*pos) {
+            None => return Err(parse_error(JsonParseError::UnexpectedEndOfFile)),
+            Some(b']') => {
+                // a comma right before the closing bracket is a trailing one
+                if expect_value && !values.is_empty() && !options.allow_extra_commas {
+                    return Err(parse_error(JsonParseError::InvalidStructuralToken("]".to_string())));
+                }
+                *pos += 1;
+                return Ok(MawuValueRef::Array(values));
+            }
+            Some(b',') => {
+                if expect_value && !options.allow_extra_commas {
+                    return Err(parse_error(JsonParseError::InvalidStructuralToken(",".to_string())));
+                }
+                *pos += 1;
+                expect_value = true;
+            }
+            Some(_) if !expect_value => {
+                return Err(parse_error(JsonParseError::UnexpectedCharacter(char_at(input, *pos))));
+            }
+            Some(_) => {
+                values.push(json_borrowed_value_lexer(input, pos, options, depth)?);
+                expect_value = false;
+            }
+        }
+    }
+}
+
+/// Borrows the string from the input, and only allocates once the first escape sequence is found.
+/// The string ends at the same quote it was opened with, `"` or, if allowed, `'`
+fn json_borrowed_string_lexer<'a>(
+    input: &'a str,
+    pos: &mut usize,
+    options: &JsonParseOptions,
+) -> Result<Cow<'a, str>, MawuError> {
+    let bytes = input.as_bytes();
+    let quote = bytes[*pos];
+    // skip the opening quote
+    *pos += 1;
+    let start = *pos;
+    let mut string: Option<String> = None;
+    // start of the characters not yet copied into `string`
+    let mut run_start = start;
+    while let Some(&this_byte) = bytes.get(*pos) {
+        if this_byte == quote {
+            let out = match string {
+                None => Cow::Borrowed(&input[start..*pos]),
+                Some(mut string) => {
+                    string.push_str(&input[run_start..*pos]);
+                    Cow::Owned(string)
+                }
+            };
+            *pos += 1;
+            return Ok(out);
+        } else if this_byte == b'\\' {
+            let string = string.get_or_insert_with(Default::default);
+            string.push_str(&input[run_start..*pos]);
+            *pos += json_borrowed_escape_lexer(input, *pos, string, options)?;
+            run_start = *pos;
+        } else if options.reject_control_characters && this_byte < b' ' {
+            return Err(parse_error(JsonParseError::UnescapedCharacter(this_byte as char)));
+        } else {
+            *pos += 1;
+        }
+    }
+    Err(parse_error(JsonParseError::UnexpectedEndOfFile))
+}
+
+/// Unescapes the escape sequence starting with the `\` at `pos` into `string`, returning its length
+fn json_borrowed_escape_lexer(
+    input: &str,
+    pos: usize,
+    string: &mut String,
+    options: &JsonParseOptions,
+) -> Result<usize, MawuError> {
+    let invalid = || {
+        parse_error(JsonParseError::InvalidEscapeSequence(
+            input[pos..].chars().take(2).collect(),
+        ))
+    };
+    let unescaped = match input.as_bytes().get(pos + 1) {
+        Some(b'"') => '"',
+        Some(b'\'') if options.allow_single_quotes => '\'',
+        Some(b'\\') => '\\',
+        Some(b'/') => '/',
+        Some(b'b') => '\u{0008}',
+        Some(b'f') => '\u{000C}',
+        Some(b'n') => '\n',
+        Some(b'r') => '\r',
+        Some(b't') => '\t',
+        Some(b'u') => {
+            let codepoint = input.get(pos + 2..pos + 6).ok_or_else(invalid)?;
+            if !codepoint.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            let next_codepoint = match input.get(pos + 6..pos + 12) {
+                Some(next) if next.starts_with("\\u") => &next[2..],
+                _ => "",
+            };
+            return match unescape_unicode(codepoint, next_codepoint) {
+                Ok((out, codepoint_used)) => {
+                    string.push_str(&out);
+                    Ok(if codepoint_used { 12 } else { 6 })
+                }
+                Err(err @ MawuError::JsonError(_)) => Err(err),
+                Err(_) => Err(invalid()),
+            };
+        }
+        Some(_) => return Err(invalid()),
+        None => return Err(parse_error(JsonParseError::UnexpectedEndOfFile)),
+    };
+    string.push(unescaped);
+    Ok(2)
+}
+
+/// Numbers never borrow, the characters are converted exactly like `json_lexer` does
+fn json_borrowed_number_lexer<'a>(
+    input: &'a str,
+    pos: &mut usize,
+    options: &JsonParseOptions,
+) -> Result<MawuValueRef<'a>, MawuError> {
+    let start = *pos;
+    let sign = usize::from(input.as_bytes().get(start) == Some(&b'-'));
+    let unsigned = &input[start + sign..];
+    if unsigned.starts_with('0') && unsigned[1..].chars().next().and_then(radix_of).is_some() {
+        // `0x`, `0o` and `0b` prefixed integers
+        *pos += sign + unsigned.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(unsigned.len());
+        let number = &input[start..*pos];
+        if !options.allow_radix_prefixes {
+            return Err(parse_error(JsonParseError::InvalidNumber(number.to_string())));
+        }
+        return match check_number_range(parse_radix_number(number)?, number, options)? {
+            MawuValue::Uint(u) => Ok(MawuValueRef::Uint(u)),
+            MawuValue::Int(i) => Ok(MawuValueRef::Int(i)),
+            _ => Ok(MawuValueRef::None),
+        };
+    }
+    while let Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E') = input.as_bytes().get(*pos) {
+        *pos += 1;
+    }
+    let number = &input[start..*pos];
+    if options.validate_numbers && !is_json_number(number) {
+        return Err(parse_error(JsonParseError::InvalidNumber(number.to_string())));
+    }
+    match check_number_range(MawuValue::from(number), number, options)? {
+        MawuValue::Uint(u) => Ok(MawuValueRef::Uint(u)),
+        MawuValue::Int(i) => Ok(MawuValueRef::Int(i)),
+        MawuValue::Float(f) => Ok(MawuValueRef::Float(f)),
+        // too large to be represented
+        MawuValue::None => Ok(MawuValueRef::None),
+        _ => Err(parse_error(JsonParseError::InvalidNumber(number.to_string()))),
+    }
+}
+
+#[test]
+fn borrowed_strings() {
+    let input = r#"{"plain": "value", "escaped": "a\"b\u00e9\uD834\uDD1E", "wide": "ünïcödé"}"#;
+    let value = json_borrowed_lexer(input, &JsonParseOptions::default()).unwrap();
+    assert!(value.get("plain").unwrap().is_borrowed());
+    assert!(value.get("wide").unwrap().is_borrowed());
+    let escaped = value.get("escaped").unwrap();
+    assert!(!escaped.is_borrowed());
+    assert_eq!(escaped.as_str(), Some("a\"bé𝄞"));
+    assert!(matches!(value.as_object().unwrap()[0].0, Cow::Borrowed("plain")));
+}
+
+#[test]
+fn borrowed_multi_codepoint() {
+    let input = "{\"crlf\": \"a\r\nb\", \"family\": \"\u{1F469}\u{200D}\u{1F467}\"}\r\n";
+    let value = json_borrowed_lexer(input, &JsonParseOptions::default()).unwrap();
+    assert_eq!(value.get("crlf").unwrap().as_str(), Some("a\r\nb"));
+    assert_eq!(value.get("family").unwrap().as_str(), Some("\u{1F469}\u{200D}\u{1F467}"));
+    assert!(value.get("family").unwrap().is_borrowed());
+}
+
+#[test]
+fn borrowed_leading_plus() {
+    assert_eq!(json_borrowed_lexer("[+5]", &JsonParseOptions::lenient()).unwrap().to_owned(), MawuValue::from(vec![MawuValue::Uint(5)]));
+    assert!(matches!(
+        json_borrowed_lexer("[+5]", &JsonParseOptions::strict()),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidNumber(ref n)))) if n == "+5"
+    ));
+    assert!(json_borrowed_lexer("+-5", &JsonParseOptions::lenient()).is_err());
+}
+
+#[test]
+fn borrowed_radix_prefixes() {
+    let value = json_borrowed_lexer("[0xFF, -0o10, 0b11]", &JsonParseOptions::lenient()).unwrap();
+    assert_eq!(value.to_owned(), MawuValue::from(vec![MawuValue::Uint(255), MawuValue::Int(-8), MawuValue::Uint(3)]));
+    assert!(matches!(
+        json_borrowed_lexer("[0xFF]", &JsonParseOptions::strict()),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidNumber(ref n)))) if n == "0xFF"
+    ));
+    assert!(json_borrowed_lexer("0xG", &JsonParseOptions::lenient()).is_err());
+}
+
+#[test]
+fn borrowed_single_quotes() {
+    let value = json_borrowed_lexer(r#"{'a': 'say "hi"', 'b': 'it\'s'}"#, &JsonParseOptions::lenient()).unwrap();
+    assert_eq!(value.get("a").unwrap().as_str(), Some("say \"hi\""));
+    assert!(value.get("a").unwrap().is_borrowed());
+    assert_eq!(value.get("b").unwrap().as_str(), Some("it's"));
+    assert!(json_borrowed_lexer("{'a': 'b'}", &JsonParseOptions::strict()).is_err());
+}
+
+#[test]
+fn borrowed_unquoted_keys() {
+    let value = json_borrowed_lexer("{a: 1, b_2: {$c: 2}}", &JsonParseOptions::lenient()).unwrap();
+    assert_eq!(value.get("a"), Some(&MawuValueRef::Uint(1)));
+    assert_eq!(value.get("b_2").unwrap().get("$c"), Some(&MawuValueRef::Uint(2)));
+    assert!(matches!(value.as_object().unwrap()[0].0, Cow::Borrowed("a")));
+    assert!(json_borrowed_lexer("{a: 1}", &JsonParseOptions::strict()).is_err());
+    assert!(json_borrowed_lexer("{2a: 1}", &JsonParseOptions::lenient()).is_err());
+}
+
+#[test]
+fn borrowed_unexpected_bom() {
+    let lex = |s: &'static str| json_borrowed_lexer(s, &JsonParseOptions::default());
+    assert_eq!(lex("\u{FEFF}[1]").unwrap().to_owned(), MawuValue::from(vec![MawuValue::Uint(1)]));
+    for input in ["{\"a\": 1,\u{FEFF} \"b\": 2}", "{\"a\"\u{FEFF}: 1}", "{\"a\": 1\u{FEFF}}"] {
+        assert!(matches!(
+            lex(input),
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedBom)))
+        ), "{:?}", input);
+    }
+    // after the top-level value it is one of the trailing characters, which are ignored by default
+    assert_eq!(lex("[1]\u{FEFF}").unwrap().to_owned(), MawuValue::from(vec![MawuValue::Uint(1)]));
+}
+
+#[test]
+fn borrowed_extra_commas() {
+    let structural = |res: Result<MawuValueRef, MawuError>, token: &str| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidStructuralToken(ref t)))) if t == token)
+    };
+    let lenient = JsonParseOptions::lenient();
+    let one_two = MawuValue::from(vec![MawuValue::Uint(1), MawuValue::Uint(2)]);
+    for input in ["[1,,2]", "[,1,2]", "[1,2,]", "[1, 2 , , ]"] {
+        assert_eq!(json_borrowed_lexer(input, &lenient).unwrap().to_owned(), one_two, "{}", input);
+    }
+    assert_eq!(json_borrowed_lexer("{,\"a\": 1,,}", &lenient).unwrap().get("a"), Some(&MawuValueRef::Uint(1)));
+    assert_eq!(json_borrowed_lexer("[,]", &lenient).unwrap(), MawuValueRef::Array(Vec::new()));
+
+    let strict = JsonParseOptions::strict();
+    assert!(structural(json_borrowed_lexer("[1,,2]", &strict), ","));
+    assert!(structural(json_borrowed_lexer("[,1]", &strict), ","));
+    assert!(structural(json_borrowed_lexer("[1,]", &strict), "]"));
+    assert!(structural(json_borrowed_lexer("{\"a\": 1,,\"b\": 2}", &strict), ","));
+    assert!(structural(json_borrowed_lexer("{\"a\": 1,}", &strict), "}"));
+    assert!(structural(json_borrowed_lexer("[1,]", &JsonParseOptions::new().allow_extra_commas(false)), "]"));
+
+    // a missing comma is an error either way
+    for options in [lenient, strict] {
+        for input in ["[1 2]", "[\"a\" \"b\"]", "{\"a\": 1 \"b\": 2}"] {
+            assert!(json_borrowed_lexer(input, &options).is_err(), "{}", input);
+        }
+    }
+}
+
+#[test]
+fn borrowed_errors() {
+    let lex = |s: &'static str| json_borrowed_lexer(s, &JsonParseOptions::strict());
+    for invalid in [
+        "[1,]", "[1 2]", "{\"a\" 1}", "{\"a\": 1,}", "{1: 2}", "[\"a]", "\"\\x\"", "\"\\u12\"",
+        "[NaN]", "[01]", "tru", "[1] 2", "[\"a\tb\"]", "\"\\uDC00\"",
+    ] {
+        assert!(lex(invalid).is_err(), "{}", invalid);
+    }
+    assert_eq!(json_borrowed_lexer("{} x", &JsonParseOptions::lenient()).unwrap(), MawuValueRef::Object(Vec::new()));
+    assert!(matches!(
+        lex("{} x"),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::TrailingCharacters)))
+    ));
+    assert!(matches!(
+        lex("[1]\u{FEFF}"),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedBom)))
+    ));
+    assert!(matches!(
+        json_borrowed_lexer("[[]]", &JsonParseOptions::new().max_depth(1)),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::MaxDepthExceeded(1))))
+    ));
+    for truncated in ["\"ab\\", "{\"a\"", "{\"a\": 1", "{", "[1, 2"] {
+        assert!(matches!(
+            lex(truncated),
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedEndOfFile)))
+        ), "{}", truncated);
+    }
+    for empty in ["", " \n", "\u{FEFF}"] {
+        assert!(matches!(
+            lex(empty),
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedEndOfFile)))
+        ), "{:?}", empty);
+        assert_eq!(json_borrowed_lexer(empty, &JsonParseOptions::lenient()).unwrap(), MawuValueRef::None);
+    }
+    assert_eq!(json_borrowed_lexer("[-1, 1.5e1, 1e400]", &JsonParseOptions::lenient()).unwrap().to_owned(), MawuValue::from(vec![
+        MawuValue::Int(-1),
+        MawuValue::Float(15.0),
+        MawuValue::None,
+    ]));
+    assert!(matches!(
+        lex("[-1, 1.5e1, 1e400]"),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::NumberOutOfRange(ref s)))) if s == "1e400"
+    ));
+}
@@ -1,38 +1,159 @@
 // the 'unused_imports' warning is a false positive, they are needed for the tests
 #![allow(unused_imports)]
-use std::{
-    char, collections::{HashMap, VecDeque}, rc::Rc, sync::{Mutex, MutexGuard}
-};
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+use core::{char, num::IntErrorKind};
 
 use crate::{
     errors::{
         json_error::{JsonError, JsonParseError},
         MawuError, MawuInternalError,
     },
-    mawu_value::MawuValue,
+    lexers::json_parse_options::JsonParseOptions,
+    mawu_value::{MawuObject, MawuValue},
     utils::{
-        file_handling::read_file, is_digit, is_end_of_primitive_value, is_json_string_terminator_token, is_whitespace, unescape_unicode
+        is_digit, is_end_of_primitive_value, is_json_string_terminator_token, is_whitespace, unescape_unicode
     },
 };
+#[cfg(test)]
+use crate::utils::file_handling::read_file;
 
 pub fn json_lexer(file_contents: VecDeque<char>) -> Result<MawuValue, MawuError> {
-    if file_contents.len() > 0 {
-        let contents_store: Rc<Mutex<VecDeque<char>>> = Rc::new(Mutex::new(file_contents));
-        let contents = contents_store.try_lock();
-        if contents.is_err() {
-            return Err(MawuError::InternalError(
-                MawuInternalError::UnableToLockMasterMutex,
-            ));
-        } else {
-            json_value_lexer(&mut contents.unwrap())
+    json_lexer_with(file_contents, &JsonParseOptions::default())
+}
+
+pub fn json_lexer_with(mut file_contents: VecDeque<char>, options: &JsonParseOptions) -> Result<MawuValue, MawuError> {
+    // a byte order mark is only allowed at the very start, see `JsonParseError::UnexpectedBom`
+    if file_contents.front() == Some(&'\u{FEFF}') {
+        let _ = file_contents.pop_front();
+    }
+    if file_contents.iter().any(|c| !is_whitespace(c)) {
+        let mut contents = file_contents;
+        let value = json_value_lexer(&mut contents, options, 0)?;
+        match contents.iter().find(|c| !is_whitespace(c)) {
+            Some(_) if options.allow_trailing_characters => Ok(value),
+            Some('\u{FEFF}') => Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnexpectedBom,
+            ))),
+            Some(_) => Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::TrailingCharacters,
+            ))),
+            None => Ok(value),
         }
-    } else {
+    } else if options.allow_empty_documents {
         Ok(MawuValue::default())
+    } else {
+        Err(MawuError::JsonError(JsonError::ParseError(
+            JsonParseError::UnexpectedEndOfFile,
+        )))
+    }
+}
+
+/// How many bytes a chunk of `json_parallel_lexer_with` holds at least, smaller documents are not split up
+#[cfg(feature = "rayon")]
+const MIN_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Works like `json_lexer_with`, but if the document is a top-level array, it is split into chunks of
+/// whole elements that are parsed across the threads of rayon, and concatenated in order afterwards.
+#[cfg(feature = "rayon")]
+pub fn json_parallel_lexer_with(input: &str, options: &JsonParseOptions) -> Result<MawuValue, MawuError> {
+    let chunk_size = (input.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK_SIZE);
+    json_chunked_lexer_with(input, options, chunk_size)
+}
+
+#[cfg(feature = "rayon")]
+fn json_chunked_lexer_with(input: &str, options: &JsonParseOptions, chunk_size: usize) -> Result<MawuValue, MawuError> {
+    use rayon::prelude::*;
+
+    let document = input.strip_prefix('\u{FEFF}').unwrap_or(input);
+    let start = document.len() - document.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    // anything but an array, or an array that does not end, is parsed in one piece, returning the same value or error
+    let (boundaries, end) = match document[start..].starts_with('[').then(|| element_boundaries(document, start, chunk_size, options)).flatten() {
+        Some(split) if !split.0.is_empty() => split,
+        _ => return json_lexer_with(input.chars().collect(), options),
+    };
+    match document[end + 1..].chars().find(|c| !is_whitespace(c)) {
+        Some(_) if options.allow_trailing_characters => {}
+        Some('\u{FEFF}') => return Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedBom))),
+        Some(_) => return Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::TrailingCharacters))),
+        None => {}
+    }
+    let mut starts = vec![start + 1];
+    starts.extend(boundaries.iter().map(|comma| comma + 1));
+    let mut ends = boundaries;
+    ends.push(end);
+    let chunks = starts.into_iter().zip(ends).map(|(start, end)| &document[start..end]).collect::<Vec<&str>>();
+    // an empty chunk lies between two commas, or after the last one, which its brackets would hide
+    if !options.allow_extra_commas {
+        if let Some(index) = chunks.iter().position(|chunk| chunk.chars().all(|c| is_whitespace(&c))) {
+            let token = if index + 1 == chunks.len() { "]" } else { "," };
+            return Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidStructuralToken(token.to_string()))));
+        }
+    }
+    let elements = chunks
+        .into_par_iter()
+        .map(|chunk| {
+            // each chunk wrapped in brackets holds exactly its elements, the commas between the chunks are left out
+            let chunk = core::iter::once('[').chain(chunk.chars()).chain(core::iter::once(']')).collect();
+            match json_lexer_with(chunk, options)? {
+                MawuValue::Array(elements) => Ok(elements),
+                _ => unreachable!("the chunk is wrapped in brackets"),
+            }
+        })
+        .collect::<Result<Vec<Vec<MawuValue>>, MawuError>>()?;
+    Ok(MawuValue::Array(elements.into_iter().flatten().collect()))
+}
+
+/// Returns the indices of the commas between the elements of the array opening at `start` that the
+/// chunks after the first start behind, every `chunk_size` bytes or later, and the index of the closing bracket.
+///
+/// Only commas outside of strings and nested arrays and objects separate elements. Returns `None`
+/// if the array is never closed.
+#[cfg(feature = "rayon")]
+fn element_boundaries(document: &str, start: usize, chunk_size: usize, options: &JsonParseOptions) -> Option<(Vec<usize>, usize)> {
+    let bytes = document.as_bytes();
+    let mut boundaries = Vec::new();
+    let mut next_boundary = start + chunk_size;
+    let mut depth = 0usize;
+    let mut quote = None;
+    let mut index = start;
+    while index < bytes.len() {
+        let b = bytes[index];
+        match quote {
+            Some(q) => {
+                if b == b'\\' {
+                    index += 1;
+                } else if b == q {
+                    quote = None;
+                }
+            }
+            None => match b {
+                b'"' => quote = Some(b),
+                b'\'' if options.allow_single_quotes => quote = Some(b),
+                b'[' | b'{' => depth += 1,
+                b']' | b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some((boundaries, index));
+                    }
+                }
+                b',' if depth == 1 && index >= next_boundary => {
+                    boundaries.push(index);
+                    next_boundary = index + chunk_size;
+                }
+                _ => {}
+            },
+        }
+        index += 1;
     }
+    None
 }
 
-fn json_value_lexer(
-    file_contents: &mut MutexGuard<VecDeque<char>>,
+/// `depth` is the number of arrays and objects the value is nested in
+pub fn json_value_lexer(
+    file_contents: &mut VecDeque<char>,
+    options: &JsonParseOptions,
+    depth: usize,
 ) -> Result<MawuValue, MawuError> {
     while file_contents.front().is_some() {
         let this_char = file_contents.pop_front().unwrap();
@@ -43,12 +164,17 @@ fn json_value_lexer(
             continue;
         }
         // Actual parsing
+        if (this_char == '{' || this_char == '[') && depth >= options.max_depth {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::MaxDepthExceeded(options.max_depth),
+            )));
+        }
         if this_char == '{' {
             // object
-            return json_object_lexer(file_contents);
+            return json_object_lexer(file_contents, options, depth + 1);
         } else if this_char == '[' {
             // array
-            return json_array_lexer(file_contents);
+            return json_array_lexer(file_contents, options, depth + 1);
         } else if this_char == 'N' && file_contents.front() == Some(&'a') && file_contents.get(1) == Some(&'N') || this_char == 'n' && file_contents.front() == Some(&'a') && file_contents.get(1) == Some(&'n') {
             // NaN
             return Err(MawuError::JsonError(JsonError::ParseError(
@@ -96,9 +222,23 @@ fn json_value_lexer(
             return Err(MawuError::JsonError(JsonError::ParseError(
                 JsonParseError::InvalidStructuralToken(this_char.to_string()),
             )));
-        } else if this_char == '\"' {
+        } else if this_char == '\"' || this_char == '\'' && options.allow_single_quotes {
             // string
-            return json_string_lexer(file_contents);
+            return json_string_lexer(file_contents, options, this_char, depth);
+        } else if this_char == '+' {
+            // number with a leading plus, the plus itself is dropped
+            let number_start = file_contents.front().copied();
+            if !options.allow_leading_plus || !number_start.is_some_and(|c| c.is_ascii_digit()) {
+                let number = file_contents
+                    .iter()
+                    .take_while(|c| !is_end_of_primitive_value(**c) && !is_whitespace(c))
+                    .collect::<String>();
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidNumber(format!("+{}", number)),
+                )));
+            }
+            let _ = file_contents.pop_front();
+            return json_number_lexer(file_contents, number_start, options);
         } else if this_char == '-' || is_digit(&this_char)? {
             // number
             return json_number_lexer(
@@ -108,7 +248,12 @@ fn json_value_lexer(
                 } else {
                     None
                 },
+                options,
             );
+        } else if this_char == '\u{FEFF}' {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnexpectedBom,
+            )));
         } else {
             // Invalid json grammar
             return Err(MawuError::JsonError(JsonError::ParseError(
@@ -122,67 +267,196 @@ fn json_value_lexer(
 }
 
 fn json_object_lexer(
-    file_contents: &mut MutexGuard<VecDeque<char>>,
+    file_contents: &mut VecDeque<char>,
+    options: &JsonParseOptions,
+    depth: usize,
 ) -> Result<MawuValue, MawuError> {
-    let mut binding_object: HashMap<String, MawuValue> = Default::default();
-    while file_contents.front() != Some(&'}') && file_contents.front().is_some() {
-        if is_whitespace(file_contents.front().unwrap()) {
-            let _ = file_contents.pop_front();
-            continue;
-        }
-        if file_contents.front() == Some(&',') {
-            let _ = file_contents.pop_front();
-            continue;
-        }
-        if file_contents.front() == Some(&'\n') && file_contents.len() <= 1 {
-            let _ = file_contents.pop_front();
-            return Ok(MawuValue::from(binding_object));
-        }
-        let key = json_value_lexer(file_contents)?.to_string();
-        if file_contents.front() == Some(&':') {
-            let _ = file_contents.pop_front();
-            let value = json_value_lexer(file_contents)?;
-            binding_object.insert(key, value);
-        } else {
-            return Err(MawuError::JsonError(JsonError::ParseError(
-                JsonParseError::ExpectedColon,
-            )));
+    let mut binding_object: MawuObject = Default::default();
+    // a member is expected right after the opening brace and after every comma
+    let mut expect_member = true;
+    loop {
+        match file_contents.front().copied() {
+            None => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedEndOfFile,
+                )));
+            }
+            Some(c) if is_whitespace(&c) => {
+                let _ = file_contents.pop_front();
+            }
+            Some('\u{FEFF}') => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedBom,
+                )));
+            }
+            Some('}') => {
+                // a comma right before the closing brace is a trailing one
+                if expect_member && !binding_object.is_empty() && !options.allow_extra_commas {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::InvalidStructuralToken("}".to_string()),
+                    )));
+                }
+                let _ = file_contents.pop_front();
+                return Ok(MawuValue::Object(binding_object));
+            }
+            Some(',') => {
+                if expect_member && !options.allow_extra_commas {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::InvalidStructuralToken(",".to_string()),
+                    )));
+                }
+                let _ = file_contents.pop_front();
+                expect_member = true;
+            }
+            Some(_) if !expect_member => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::ExpectedEndOfObject,
+                )));
+            }
+            Some(c) => {
+                let key = if c == '\"' || c == '\'' && options.allow_single_quotes {
+                    let _ = file_contents.pop_front();
+                    // keys are moved out of the parsed string instead of formatted into a new one
+                    match json_string_lexer(file_contents, options, c, depth)? {
+                        MawuValue::String(key) => key,
+                        other => other.to_string(),
+                    }
+                } else if options.allow_unquoted_keys && is_identifier_char(c, true) {
+                    json_identifier_lexer(file_contents)
+                } else if c == '\'' {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::InvalidCharacter(c.to_string()),
+                    )));
+                } else {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::ExpectedKey,
+                    )));
+                };
+                while file_contents.front().is_some_and(is_whitespace) {
+                    let _ = file_contents.pop_front();
+                }
+                if file_contents.front() == Some(&':') {
+                    let _ = file_contents.pop_front();
+                    let value = json_value_lexer(file_contents, options, depth)?;
+                    binding_object.insert(key, value);
+                    expect_member = false;
+                } else if file_contents.is_empty() {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedEndOfFile,
+                    )));
+                } else if file_contents.front() == Some(&'\u{FEFF}') {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedBom,
+                    )));
+                } else {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::ExpectedColon,
+                    )));
+                }
+            }
         }
     }
-    if file_contents.front() == Some(&'}') {
+}
+
+/// Checks if `c` can be part of an unquoted key, `first` being `true` for its first char
+pub(crate) fn is_identifier_char(c: char, first: bool) -> bool {
+    c.is_ascii_alphabetic() || c == '_' || c == '$' || !first && c.is_ascii_digit()
+}
+
+/// Reads an unquoted key, and the whitespace between it and the colon
+pub(crate) fn json_identifier_lexer(file_contents: &mut VecDeque<char>) -> String {
+    let mut key = String::new();
+    while let Some(c) = file_contents.front().copied().filter(|c| is_identifier_char(*c, key.is_empty())) {
+        key.push(c);
         let _ = file_contents.pop_front();
-        Ok(MawuValue::from(binding_object))
-    } else {
-        Err(MawuError::JsonError(JsonError::ParseError(
-            JsonParseError::ExpectedEndOfObject,
-        )))
     }
+    while file_contents.front().is_some_and(is_whitespace) {
+        let _ = file_contents.pop_front();
+    }
+    key
 }
 
 fn json_array_lexer(
-    file_contents: &mut MutexGuard<VecDeque<char>>,
+    file_contents: &mut VecDeque<char>,
+    options: &JsonParseOptions,
+    depth: usize,
 ) -> Result<MawuValue, MawuError> {
     let mut binding_array: Vec<MawuValue> = Default::default();
-    while file_contents.front() != Some(&']') && file_contents.front().is_some() {
-        if is_whitespace(file_contents.front().unwrap()) {
-            let _ = file_contents.pop_front();
-            continue;
-        }
-        if file_contents.front() == Some(&',') {
-            let _ = file_contents.pop_front();
-            continue;
-        }
-        if file_contents.front() == Some(&'\n') && file_contents.len() <= 1 {
-            let _ = file_contents.pop_front();
-            return Ok(MawuValue::from(binding_array));
+    // a value is expected right after the opening bracket and after every comma
+    let mut expect_value = true;
+    loop {
+        match file_contents.front().copied() {
+            None => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedEndOfFile,
+                )));
+            }
+            Some(c) if is_whitespace(&c) => {
+                let _ = file_contents.pop_front();
+            }
+            Some('\u{FEFF}') => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedBom,
+                )));
+            }
+            Some(']') => {
+                // a comma right before the closing bracket is a trailing one
+                if expect_value && !binding_array.is_empty() && !options.allow_extra_commas {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::InvalidStructuralToken("]".to_string()),
+                    )));
+                }
+                let _ = file_contents.pop_front();
+                return Ok(MawuValue::from(binding_array));
+            }
+            Some(',') => {
+                if expect_value && !options.allow_extra_commas {
+                    return Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::InvalidStructuralToken(",".to_string()),
+                    )));
+                }
+                let _ = file_contents.pop_front();
+                expect_value = true;
+            }
+            Some(c) if !expect_value => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedCharacter(c.to_string()),
+                )));
+            }
+            Some(_) => {
+                binding_array.push(json_value_lexer(file_contents, options, depth)?);
+                expect_value = false;
+            }
         }
-        let value = json_value_lexer(file_contents)?;
-        binding_array.push(value);
     }
-    if file_contents.front() == Some(&']') {
-        let _ = file_contents.pop_front();
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn chunked_matches_serial() {
+    let contents = "\u{FEFF} [1, \"a,]\\\"[\", {\"b\": [2, {}], 'c': '],'}, [[3]],, -4.5e1 ,null, [], {\"\\\\\": \",\"}] \n";
+    let options = JsonParseOptions::lenient();
+    let serial = json_lexer_with(contents.chars().collect(), &options).unwrap();
+    assert_eq!(serial.len(), 8);
+    // every possible chunk size, down to one element per chunk
+    for chunk_size in 1..contents.len() {
+        assert_eq!(json_chunked_lexer_with(contents, &options, chunk_size).unwrap(), serial, "chunk size {}", chunk_size);
+    }
+    assert_eq!(element_boundaries(contents, 4, 1, &options).unwrap().0.len(), 8);
+
+    for invalid in ["[1, 2] 3", "[1, [2, 3]", "[1, \"2]", "[1, {\"a\" 2}]", "[1, 2]\u{FEFF}"] {
+        assert_eq!(json_chunked_lexer_with(invalid, &options, 1).is_err(), json_lexer_with(invalid.chars().collect(), &options).is_err(), "{}", invalid);
+    }
+    assert_eq!(json_chunked_lexer_with("{\"a\": [1, 2]}", &options, 1).unwrap(), json_lexer_with("{\"a\": [1, 2]}".chars().collect(), &options).unwrap());
+    for input in ["[1,]", "[1,,2]", "[,1]", "[1, 2 , ]", "[1 2]"] {
+        for options in [JsonParseOptions::lenient(), JsonParseOptions::strict()] {
+            let serial = json_lexer_with(input.chars().collect(), &options);
+            for chunk_size in 1..input.len() {
+                let chunked = json_chunked_lexer_with(input, &options, chunk_size);
+                assert_eq!(chunked.as_ref().ok(), serial.as_ref().ok(), "{} {}", input, chunk_size);
+            }
+        }
     }
-    Ok(MawuValue::from(binding_array))
 }
 
 #[test]
@@ -193,6 +467,56 @@ fn object_lexer() {
     assert!(input.is_ok());
 }
 
+#[test]
+fn object_lexer_unquoted_keys() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    let object = lex("{a: 1, b_2: 2}", &JsonParseOptions::lenient()).unwrap();
+    assert_eq!(object.get("a").unwrap(), &MawuValue::Uint(1));
+    assert_eq!(object.get("b_2").unwrap(), &MawuValue::Uint(2));
+    let object = lex("{ $id : 'x', _: {true: null}, \"quoted\": 3 }", &JsonParseOptions::lenient()).unwrap();
+    assert_eq!(object.get("$id").unwrap(), &MawuValue::String("x".to_string()));
+    assert_eq!(object.get("_").unwrap().get("true").unwrap(), &MawuValue::None);
+    assert_eq!(object.get("quoted").unwrap(), &MawuValue::Uint(3));
+    for invalid in ["{a b: 1}", "{a-b: 1}", "{a}", "{a"] {
+        assert!(lex(invalid, &JsonParseOptions::lenient()).is_err(), "{}", invalid);
+    }
+    assert!(lex("{a: 1, b_2: 2}", &JsonParseOptions::strict()).is_err());
+}
+
+#[test]
+fn extra_commas() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    let structural = |res: Result<MawuValue, MawuError>, token: &str| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidStructuralToken(ref t)))) if t == token)
+    };
+    let one_two = MawuValue::from(vec![MawuValue::Uint(1), MawuValue::Uint(2)]);
+    for input in ["[1,,2]", "[,1,2]", "[1,2,]", "[1, 2 , , ]"] {
+        assert_eq!(lex(input, &JsonParseOptions::lenient()).unwrap(), one_two, "{}", input);
+    }
+    assert_eq!(lex("{,\"a\": 1,,}", &JsonParseOptions::lenient()).unwrap().get("a").unwrap(), &MawuValue::Uint(1));
+    assert_eq!(lex("[,]", &JsonParseOptions::lenient()).unwrap(), MawuValue::from(Vec::<MawuValue>::new()));
+
+    let strict = JsonParseOptions::strict();
+    assert!(structural(lex("[1,,2]", &strict), ","));
+    assert!(structural(lex("[,1]", &strict), ","));
+    assert!(structural(lex("[,]", &strict), ","));
+    assert!(structural(lex("[1,]", &strict), "]"));
+    assert!(structural(lex("{\"a\": 1,,\"b\": 2}", &strict), ","));
+    assert!(structural(lex("{\"a\": 1,}", &strict), "}"));
+    assert!(structural(lex("[1,]", &JsonParseOptions::new().allow_extra_commas(false)), "]"));
+    assert_eq!(lex("[1, 2]", &strict).unwrap(), one_two);
+
+    // a missing comma is an error either way, and keys have to be strings or identifiers
+    for options in [JsonParseOptions::lenient(), strict] {
+        assert!(lex("[1 2]", &options).is_err());
+        assert!(lex("[true false]", &options).is_err());
+        assert!(lex("[\"a\" \"b\"]", &options).is_err());
+        assert!(lex("{\"a\": 1 \"b\": 2}", &options).is_err());
+        assert!(lex("{1: 1}", &options).is_err());
+        assert!(lex("{[]: 1}", &options).is_err());
+    }
+}
+
 #[test]
 fn array_lexer() {
     let input = json_lexer(
@@ -201,42 +525,40 @@ fn array_lexer() {
     assert!(input.is_ok());
 }
 
+/// `quote` is the char the string was opened with, `"` or, if allowed, `'`
+///
+/// `depth` is the number of arrays and objects the string is nested in. At the top level whatever
+/// follows the string is left to the caller, see `JsonParseOptions::allow_trailing_characters`.
 fn json_string_lexer(
-    file_contents: &mut MutexGuard<VecDeque<char>>,
+    file_contents: &mut VecDeque<char>,
+    options: &JsonParseOptions,
+    quote: char,
+    depth: usize,
 ) -> Result<MawuValue, MawuError> {
-    let mut string: String = Default::default();
+    // the string ends at the next quote, unless it contains escaped ones
+    let mut string = String::with_capacity(file_contents.iter().position(|c| *c == quote).unwrap_or(0));
     loop {
         let this_char = file_contents.pop_front();
         if this_char.is_some() {
             let character = this_char.unwrap();
             let next_char = file_contents.front();
-            // End of string
-            // Or part checks for end of file
-            if character == '\"' && is_json_string_terminator_token(next_char)
-                || file_contents.len() == 0
-                || file_contents.front() == Some(&'\n') && file_contents.len() <= 1
-            {
-                return Ok(MawuValue::String(string));
-            }
-            // the two nested if statements are joined, meaning that only if `\"` is encountered
-            // AND the next char is whitespace the logic is executed
-            if character == '\"' && next_char.is_some() {
-                let next_char = file_contents.pop_front().unwrap();
-                if is_whitespace(&next_char) {
-                    while is_whitespace(file_contents.front().unwrap()) {
-                        let _ = file_contents.pop_front().unwrap();
-                    }
-
-                    if is_json_string_terminator_token(file_contents.front()) {
-                        return Ok(MawuValue::String(string));
-                    } else {
-                        return Err(MawuError::JsonError(JsonError::ParseError(
-                            JsonParseError::UnexpectedCharacter(
-                                file_contents.front().unwrap().to_string(),
-                            ),
-                        )));
-                    }
+            // End of string, only ever at a quote, running out of input first is an error
+            if character == quote {
+                if depth == 0 {
+                    return Ok(MawuValue::String(string));
                 }
+                // inside of an array or object only a structural token may follow, `\r\n` or any
+                // other whitespace in between is skipped by the caller
+                return match file_contents.iter().find(|c| !is_whitespace(c)) {
+                    None => Ok(MawuValue::String(string)),
+                    Some(c) if is_json_string_terminator_token(Some(c)) => Ok(MawuValue::String(string)),
+                    Some('\u{FEFF}') => Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedBom,
+                    ))),
+                    Some(c) => Err(MawuError::JsonError(JsonError::ParseError(
+                        JsonParseError::UnexpectedCharacter(c.to_string()),
+                    ))),
+                };
             }
             // Escape character
             // remember, some characters have two chars of escape sequence (`\"` being represented
@@ -252,7 +574,10 @@ fn json_string_lexer(
                             let hex3 = file_contents.pop_front().unwrap();
                             let hex4 = file_contents.pop_front().unwrap();
                             let next_codepoint = {
-                                if file_contents.len() >= 6 {
+                                if file_contents.len() >= 6
+                                    && file_contents.front() == Some(&'\\')
+                                    && file_contents.get(1) == Some(&'u')
+                                {
                                     let mut out: String = Default::default();
                                     out.push(*file_contents.get(2).unwrap());
                                     out.push(*file_contents.get(3).unwrap());
@@ -267,7 +592,9 @@ fn json_string_lexer(
                                 &format!("{}{}{}{}", hex1, hex2, hex3, hex4),
                                 &next_codepoint,
                             );
-                            if tmp.is_err() {
+                            if let Err(MawuError::JsonError(err)) = tmp {
+                                return Err(MawuError::JsonError(err));
+                            } else if tmp.is_err() {
                                 Err(MawuError::JsonError(JsonError::ParseError(
                                     JsonParseError::InvalidEscapeSequence(format!(
                                         "{}{}",
@@ -289,6 +616,14 @@ fn json_string_lexer(
                                 string.push_str(&out);
                             }
                             continue;
+                        } else {
+                            // not even room for the 4 hex-digits left
+                            Err(MawuError::JsonError(JsonError::ParseError(
+                                JsonParseError::InvalidEscapeSequence(format!(
+                                    "{}{}",
+                                    character, next_char
+                                )),
+                            )))?;
                         }
                     } else if next_char == '/' {
                         string.push('/');
@@ -306,6 +641,8 @@ fn json_string_lexer(
                         string.push('\\');
                     } else if next_char == '\"' {
                         string.push('"');
+                    } else if next_char == '\'' && options.allow_single_quotes {
+                        string.push('\'');
                     } else {
                         Err(MawuError::JsonError(JsonError::ParseError(
                             JsonParseError::InvalidEscapeSequence(format!(
@@ -322,13 +659,17 @@ fn json_string_lexer(
             // Only space is accepted as whitespace in json, the rest has to be escaped
             } else if character == ' ' {
                 string.push(' ');
-            } else if character == '\"' {
+            } else if options.reject_control_characters && character < ' ' {
                 return Err(MawuError::JsonError(JsonError::ParseError(
-                    JsonParseError::InvalidEscapeSequence(format!("{}", character)),
+                    JsonParseError::UnescapedCharacter(character),
                 )));
             } else {
                 string.push(character);
             }
+        } else {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnexpectedEndOfFile,
+            )));
         }
     }
 }
@@ -390,9 +731,203 @@ fn string_lexer() {
     assert!(parsed_tab.unwrap() == MawuValue::String("\t test".to_string()));
 }
 
+#[test]
+fn string_lexer_control_characters() {
+    let raw_tab = "[\"a\tb\"]".chars().collect::<VecDeque<char>>();
+    let lenient = json_lexer_with(raw_tab.clone(), &JsonParseOptions::lenient()).unwrap();
+    assert_eq!(lenient, MawuValue::from(vec![MawuValue::String("a\tb".to_string())]));
+    let strict = json_lexer_with(raw_tab, &JsonParseOptions::strict());
+    assert!(matches!(
+        strict,
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnescapedCharacter('\t'))))
+    ));
+
+    let escaped_tab = "[\"a\\tb\"]".chars().collect::<VecDeque<char>>();
+    let strict = json_lexer_with(escaped_tab, &JsonParseOptions::strict()).unwrap();
+    assert_eq!(strict, MawuValue::from(vec![MawuValue::String("a\tb".to_string())]));
+    let raw_null = "[\"a\u{0}b\"]".chars().collect::<VecDeque<char>>();
+    assert!(json_lexer_with(raw_null, &JsonParseOptions::strict()).is_err());
+}
+
+#[test]
+fn string_lexer_surrogates() {
+    let lex = |s: &str| json_lexer(s.chars().collect::<VecDeque<char>>());
+    let unpaired = |res: Result<MawuValue, MawuError>| {
+        matches!(
+            res,
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnpairedSurrogate(_))))
+        )
+    };
+    assert_eq!(lex(r#""𝄞""#).unwrap(), MawuValue::String("𝄞".to_string()));
+    // lone high surrogate
+    assert!(unpaired(lex(r#""\uD800""#)));
+    assert!(unpaired(lex(r#""\uD800abcdef""#)));
+    assert!(unpaired(lex(r#""\uD800A""#)));
+    assert!(unpaired(lex(r#""\uD800\uD800""#)));
+    // lone low surrogate
+    assert!(unpaired(lex(r#""\uDC00""#)));
+    assert!(unpaired(lex(r#""\uDD1E\uD834""#)));
+}
+
+#[test]
+fn truncated_input() {
+    let lex = |s: &str| json_lexer(s.chars().collect::<VecDeque<char>>());
+    let end_of_file = |res: Result<MawuValue, MawuError>| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedEndOfFile))))
+    };
+    // cut mid-escape, or anywhere else inside of a string
+    assert!(end_of_file(lex(r#""ab\"#)));
+    assert!(end_of_file(lex(r#"["ab\"#)));
+    assert!(end_of_file(lex(r#""ab"#)));
+    assert!(end_of_file(lex("\"ab\n")));
+    assert!(matches!(
+        lex(r#""\u12"#),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidEscapeSequence(_))))
+    ));
+    // cut after a key, a colon, a value or an opening bracket
+    for input in [r#"{"a""#, r#"{"a" "#, r#"{"a":"#, r#"{"a": 1"#, "{\"a\": 1\n", "{", "[", "[1, 2", "[1, 2,\n", r#"[{"a": [1]"#] {
+        assert!(end_of_file(lex(input)), "{}", input);
+    }
+}
+
+#[test]
+fn string_lexer_single_quotes() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    let lenient = JsonParseOptions::lenient();
+    let object = lex("{'a':'b'}", &lenient).unwrap();
+    assert_eq!(object.get("a").unwrap(), &MawuValue::String("b".to_string()));
+    assert_eq!(
+        lex(r#"['it\'s', 'say "hi"', "it\'s", "it's"]"#, &lenient).unwrap(),
+        MawuValue::from(vec![
+            MawuValue::String("it's".to_string()),
+            MawuValue::String("say \"hi\"".to_string()),
+            MawuValue::String("it's".to_string()),
+            MawuValue::String("it's".to_string()),
+        ])
+    );
+    assert!(matches!(
+        lex("{'a':'b'}", &JsonParseOptions::strict()),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidCharacter(_))))
+    ));
+    assert!(matches!(
+        lex(r#""it\'s""#, &JsonParseOptions::strict()),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidEscapeSequence(_))))
+    ));
+}
+
+#[test]
+fn trailing_characters() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    let trailing = |res: Result<MawuValue, MawuError>| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::TrailingCharacters))))
+    };
+    assert!(trailing(lex("{} x", &JsonParseOptions::strict())));
+    assert!(trailing(lex("{\"a\":1} garbage", &JsonParseOptions::strict())));
+    assert!(trailing(lex("[1]]", &JsonParseOptions::strict())));
+    assert!(trailing(lex("[][]", &JsonParseOptions::new().allow_trailing_characters(false))));
+    assert_eq!(lex("{} x", &JsonParseOptions::lenient()).unwrap(), MawuValue::new_object());
+    assert_eq!(lex("{} \r\n\t", &JsonParseOptions::strict()).unwrap(), MawuValue::new_object());
+    // nothing but a structural token may follow a string inside of an array or object
+    let unexpected = |res: Result<MawuValue, MawuError>| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedCharacter(ref c)))) if c == "x")
+    };
+    assert!(trailing(lex("\"ab\"x\"", &JsonParseOptions::strict())));
+    assert!(unexpected(lex("[\"ab\"x\"]", &JsonParseOptions::strict())));
+    assert!(unexpected(lex("{\"a\"x\": 1}", &JsonParseOptions::strict())));
+    assert!(unexpected(lex("[\"ab\" x]", &JsonParseOptions::lenient())));
+    assert_eq!(lex("\"ab\"x\"", &JsonParseOptions::lenient()).unwrap(), MawuValue::String("ab".to_string()));
+}
+
+#[test]
+fn out_of_range_numbers() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    let out_of_range = |res: Result<MawuValue, MawuError>, number: &str| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::NumberOutOfRange(ref s)))) if s == number)
+    };
+    let reject = JsonParseOptions::new().allow_out_of_range_numbers(false);
+    assert_eq!(lex("1e400", &JsonParseOptions::lenient()).unwrap(), MawuValue::None);
+    assert_eq!(lex("[-1e400]", &JsonParseOptions::lenient()).unwrap(), MawuValue::from(vec![MawuValue::None]));
+    // the error holds the number the way it was read, with the sign of the exponent added
+    assert!(out_of_range(lex("1e400", &reject), "1e+400"));
+    assert!(out_of_range(lex("[1, -1e400]", &reject), "-1e+400"));
+    assert!(out_of_range(lex("{\"a\": 0x1FFFFFFFFFFFFFFFF}", &reject.clone().allow_radix_prefixes(true)), "0x1FFFFFFFFFFFFFFFF"));
+    assert!(out_of_range(lex("1e400", &JsonParseOptions::strict()), "1e+400"));
+    // large integers still fit into a float, tiny numbers are rounded to zero
+    assert_eq!(lex("123456789012345678901234567890", &reject).unwrap(), MawuValue::Float(1.2345678901234568e29));
+    assert_eq!(lex("1e-400", &reject).unwrap(), MawuValue::Float(0.0));
+}
+
+#[test]
+fn unexpected_bom() {
+    let lex = |s: &str| json_lexer(s.chars().collect::<VecDeque<char>>());
+    let bom = |res: Result<MawuValue, MawuError>| {
+        matches!(res, Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedBom))))
+    };
+    assert_eq!(lex("\u{FEFF}{\"a\": 1}").unwrap().get("a").unwrap(), &MawuValue::Uint(1));
+    assert_eq!(lex("\u{FEFF}").unwrap(), MawuValue::None);
+    // anywhere in the middle of an object
+    for input in [
+        "{\"a\": 1,\u{FEFF} \"b\": 2}",
+        "{\u{FEFF}\"a\": 1}",
+        "{\"a\"\u{FEFF}: 1}",
+        "{\"a\": \u{FEFF}1}",
+        "{\"a\": 1\u{FEFF}}",
+        "[\"a\"\u{FEFF}]",
+        "\u{FEFF}\u{FEFF}{}",
+    ] {
+        assert!(bom(lex(input)), "{:?}", input);
+    }
+    assert!(bom(json_lexer_with("{a\u{FEFF}: 1}".chars().collect(), &JsonParseOptions::lenient())));
+    // inside of a string it is just another char
+    assert_eq!(lex("{\"a\u{FEFF}\": 1}").unwrap().get("a\u{FEFF}").unwrap(), &MawuValue::Uint(1));
+}
+
+#[test]
+fn string_lexer_multi_codepoint() {
+    // `\r\n` and emoji joined by zero width joiners or skin tone modifiers are single graphemes,
+    // but every char of them has to end up in the string, and nothing else
+    let lex = |s: &str| json_lexer(s.chars().collect::<VecDeque<char>>());
+    let family = "\u{1F469}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    let thumbs_up = "\u{1F44D}\u{1F3FD}";
+    assert_eq!(lex("[\"a\r\nb\"]").unwrap(), MawuValue::from(vec![MawuValue::String("a\r\nb".to_string())]));
+    assert_eq!(lex("\"\\r\\n\"\r\n").unwrap(), MawuValue::String("\r\n".to_string()));
+    // a CRLF after the closing quote of a top level string used to run past the end of the input
+    assert_eq!(lex("\"a\r\nb\"\r\n").unwrap(), MawuValue::String("a\r\nb".to_string()));
+    assert!(json_lexer_with("\"a\"\r\nb".chars().collect(), &JsonParseOptions::strict()).is_err());
+    assert!(lex("[\"a\"\r\nb]").is_err());
+
+    let parsed = lex(&format!("{{\"{}\": \"{}\"\r\n}}\r\n", family, thumbs_up)).unwrap();
+    let string = parsed.get(family).unwrap().as_str().unwrap();
+    assert_eq!(string.chars().collect::<Vec<char>>(), vec!['\u{1F44D}', '\u{1F3FD}']);
+    assert_eq!(lex(&format!("\"{}\"", family)).unwrap().as_str().unwrap().chars().count(), 5);
+    assert_eq!(lex("[1\r\n,2\r\n]\r\n").unwrap(), MawuValue::from(vec![MawuValue::Uint(1), MawuValue::Uint(2)]));
+}
+
+#[test]
+fn nesting_depth() {
+    let lex = |s: &str, max_depth: usize| {
+        json_lexer_with(s.chars().collect::<VecDeque<char>>(), &JsonParseOptions::new().max_depth(max_depth))
+    };
+    let too_deep = |res: Result<MawuValue, MawuError>| {
+        matches!(
+            res,
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::MaxDepthExceeded(_))))
+        )
+    };
+    assert!(lex("[[]]", 2).is_ok());
+    assert!(too_deep(lex("[[]]", 1)));
+    assert!(too_deep(lex(r#"{"a": {"b": []}}"#, 2)));
+    assert!(lex("1", 0).is_ok());
+    let deep = "[".repeat(100_000);
+    assert!(too_deep(json_lexer(deep.chars().collect::<VecDeque<char>>())));
+    // unterminated string at the end of the file
+    assert!(json_lexer("\"".chars().collect::<VecDeque<char>>()).is_err());
+}
+
 fn json_number_lexer(
-    file_contents: &mut MutexGuard<VecDeque<char>>,
+    file_contents: &mut VecDeque<char>,
     first_digit: Option<char>,
+    options: &JsonParseOptions,
 ) -> Result<MawuValue, MawuError> {
     let mut out: String = Default::default();
     if first_digit.is_some() {
@@ -400,34 +935,240 @@ fn json_number_lexer(
     } else {
         out.push('-');
     }
-    while file_contents.len() > 0 {
-        let this_char = file_contents.pop_front().unwrap();
-        if is_whitespace(&this_char) {
-            continue;
+    // `0x`, `0o` and `0b` prefixed integers, the prefix follows right after the zero
+    let zero_at = if first_digit == Some('0') {
+        Some(0)
+    } else if first_digit.is_none() && file_contents.front() == Some(&'0') {
+        Some(1)
+    } else {
+        None
+    };
+    if zero_at.is_some_and(|i| file_contents.get(i).copied().and_then(radix_of).is_some()) {
+        while let Some(&this_char) = file_contents.front() {
+            if is_end_of_primitive_value(this_char) || is_whitespace(&this_char) {
+                break;
+            }
+            out.push(this_char);
+            let _ = file_contents.pop_front();
         }
+        if !options.allow_radix_prefixes {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::InvalidNumber(out),
+            )));
+        }
+        return check_number_range(parse_radix_number(&out)?, &out, options);
+    }
+    // peeks at the next char first, so the one ending the number is left in place for the caller
+    while let Some(&this_char) = file_contents.front() {
+        if is_end_of_primitive_value(this_char) || is_whitespace(&this_char) {
+            break;
+        }
+        let _ = file_contents.pop_front();
         if this_char == '.' || is_digit(&this_char)? {
             out.push(this_char);
         } else if this_char == 'e' || this_char == 'E' {
             out.push(this_char);
             if file_contents.front() == Some(&'+') || file_contents.front() == Some(&'-') {
                 out.push(file_contents.pop_front().unwrap());
-            } else if is_digit(file_contents.front().unwrap())? {
+            } else if file_contents.front().is_some_and(|c| c.is_ascii_digit()) {
                 out.push('+');
             } else {
                 return Err(MawuError::JsonError(JsonError::ParseError(
                     JsonParseError::InvalidCharacter(this_char.to_string()),
                 )));
             }
-        } else if is_end_of_primitive_value(this_char) {
-            file_contents.push_front(this_char);
-            break;
+        } else if this_char == '\u{FEFF}' {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnexpectedBom,
+            )));
         } else {
             return Err(MawuError::JsonError(JsonError::ParseError(
                 JsonParseError::InvalidCharacter(this_char.to_string()),
             )));
         }
     }
-    Ok(MawuValue::from(out))
+    if options.validate_numbers && !is_json_number(&out) {
+        return Err(MawuError::JsonError(JsonError::ParseError(
+            JsonParseError::InvalidNumber(out),
+        )));
+    }
+    check_number_range(MawuValue::from(out.as_str()), &out, options)
+}
+
+/// Applies `JsonParseOptions::allow_out_of_range_numbers` to a parsed number,
+/// which is `MawuValue::None` if it is too large to be represented
+pub(crate) fn check_number_range(value: MawuValue, number: &str, options: &JsonParseOptions) -> Result<MawuValue, MawuError> {
+    if value.is_none() && !options.allow_out_of_range_numbers {
+        Err(MawuError::JsonError(JsonError::ParseError(
+            JsonParseError::NumberOutOfRange(number.to_string()),
+        )))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Returns the radix of an integer by the char following its leading zero, `x`, `o` or `b`
+pub(crate) fn radix_of(prefix: char) -> Option<u32> {
+    match prefix {
+        'x' | 'X' => Some(16),
+        'o' | 'O' => Some(8),
+        'b' | 'B' => Some(2),
+        _ => None,
+    }
+}
+
+/// Parses an integer prefixed with `0x`, `0o` or `0b`, like `0xFF` or `-0o755`.
+/// Just like decimal numbers, integers not representable in 64 bits are returned as `MawuValue::None`.
+pub(crate) fn parse_radix_number(number: &str) -> Result<MawuValue, MawuError> {
+    let invalid = || {
+        MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidNumber(
+            number.to_string(),
+        )))
+    };
+    let (negative, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, number),
+    };
+    let mut chars = unsigned.chars();
+    let radix = match (chars.next(), chars.next().and_then(radix_of)) {
+        (Some('0'), Some(radix)) => radix,
+        _ => return Err(invalid()),
+    };
+    let digits = chars.as_str();
+    // `from_str_radix` would accept a sign of its own
+    if digits.starts_with(['+', '-']) {
+        return Err(invalid());
+    }
+    match u64::from_str_radix(digits, radix) {
+        Ok(n) if !negative => Ok(MawuValue::Uint(n)),
+        Ok(n) => Ok(i64::try_from(-(n as i128)).map_or(MawuValue::None, MawuValue::Int)),
+        Err(err) if *err.kind() == IntErrorKind::PosOverflow => Ok(MawuValue::None),
+        Err(_) => Err(invalid()),
+    }
+}
+
+/// Checks `number` against the number grammar of RFC 8259:
+/// `[ minus ] int [ frac ] [ exp ]`, where `int` is `0` or has no leading zero,
+/// and `frac` and `exp` need at least one digit.
+pub(crate) fn is_json_number(number: &str) -> bool {
+    let mut chars = number.chars().peekable();
+    if chars.peek() == Some(&'-') {
+        chars.next();
+    }
+    match chars.next() {
+        Some('0') => {
+            if chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return false;
+            }
+        }
+        Some('1'..='9') => {
+            while chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                chars.next();
+            }
+        }
+        _ => return false,
+    }
+    if chars.peek() == Some(&'.') {
+        chars.next();
+        if !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        while chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+            chars.next();
+        }
+    }
+    if chars.peek() == Some(&'e') || chars.peek() == Some(&'E') {
+        chars.next();
+        if chars.peek() == Some(&'+') || chars.peek() == Some(&'-') {
+            chars.next();
+        }
+        if !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return false;
+        }
+        while chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+            chars.next();
+        }
+    }
+    chars.next().is_none()
+}
+
+#[test]
+fn number_lexer_grammar() {
+    for invalid in ["01", "-01", "1.", "-", "1e", "1e+", "1.e5", "1..2", "1.2.3", "--1"] {
+        let contents = invalid.chars().collect::<VecDeque<char>>();
+        assert!(
+            matches!(
+                json_lexer_with(contents, &JsonParseOptions::strict()),
+                Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidNumber(_) | JsonParseError::InvalidCharacter(_)
+                )))
+            ),
+            "{}",
+            invalid
+        );
+    }
+    let leading_dot = ".5".chars().collect::<VecDeque<char>>();
+    assert!(json_lexer_with(leading_dot, &JsonParseOptions::strict()).is_err());
+    for valid in ["0", "-0", "10", "0.5", "-1.25", "1e5", "1E-5", "0e+1", "-12.5e10"] {
+        let contents = valid.chars().collect::<VecDeque<char>>();
+        assert!(json_lexer_with(contents, &JsonParseOptions::strict()).is_ok(), "{}", valid);
+    }
+    let lenient = "01".chars().collect::<VecDeque<char>>();
+    assert!(json_lexer_with(lenient, &JsonParseOptions::lenient()).is_ok());
+    // whitespace ends a number, what follows it is not part of it
+    for invalid in ["[1 2]", "{\"a\": 1 2}", "[1 000]", "[- 1]", "1 2"] {
+        let contents = invalid.chars().collect::<VecDeque<char>>();
+        assert!(json_lexer_with(contents, &JsonParseOptions::strict()).is_err(), "{}", invalid);
+    }
+    let spaced = "[ 1 , -2\t]".chars().collect::<VecDeque<char>>();
+    assert_eq!(
+        json_lexer_with(spaced, &JsonParseOptions::strict()).unwrap(),
+        MawuValue::from(vec![MawuValue::Uint(1), MawuValue::Int(-2)])
+    );
+}
+
+#[test]
+fn number_lexer_leading_plus() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    assert_eq!(lex("+5", &JsonParseOptions::lenient()).unwrap(), MawuValue::Uint(5));
+    assert_eq!(lex("[+1.5e2, -1]", &JsonParseOptions::lenient()).unwrap(), MawuValue::from(vec![MawuValue::Float(150.0), MawuValue::Int(-1)]));
+    // the plus is dropped before the number is validated
+    assert_eq!(lex("+5", &JsonParseOptions::strict().allow_leading_plus(true)).unwrap(), MawuValue::Uint(5));
+    assert!(matches!(
+        lex("[+5]", &JsonParseOptions::strict()),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidNumber(ref n)))) if n == "+5"
+    ));
+    for invalid in ["+", "+-5", "++5", "+.5"] {
+        assert!(lex(invalid, &JsonParseOptions::lenient()).is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn number_lexer_radix_prefixes() {
+    let lex = |s: &str, options: &JsonParseOptions| json_lexer_with(s.chars().collect::<VecDeque<char>>(), options);
+    let lenient = JsonParseOptions::lenient();
+    assert_eq!(lex("0xFF", &lenient).unwrap(), MawuValue::Uint(255));
+    assert_eq!(
+        lex("[0o755, 0b101, -0x1f, +0X10, 0xffffffffffffffff]", &lenient).unwrap(),
+        MawuValue::from(vec![
+            MawuValue::Uint(493),
+            MawuValue::Uint(5),
+            MawuValue::Int(-31),
+            MawuValue::Uint(16),
+            MawuValue::Uint(u64::MAX),
+        ])
+    );
+    // too large for 64 bits, just like decimal numbers
+    assert_eq!(lex("0x10000000000000000", &lenient).unwrap(), MawuValue::None);
+    assert_eq!(lex("-0x8000000000000000", &lenient).unwrap(), MawuValue::Int(i64::MIN));
+    assert_eq!(lex("-0x8000000000000001", &lenient).unwrap(), MawuValue::None);
+    for invalid in ["0x", "0xG", "0b102", "0o8", "0x-1", "0x1.5", "00x1"] {
+        assert!(lex(invalid, &lenient).is_err(), "{}", invalid);
+    }
+    assert!(matches!(
+        lex("[0xFF]", &JsonParseOptions::strict()),
+        Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidNumber(ref n)))) if n == "0xFF"
+    ));
 }
 
 // Actual test with 100% coverage (I think)
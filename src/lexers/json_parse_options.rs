@@ -0,0 +1,205 @@
+/// Options changing how strictly JSON is parsed.
+///
+/// `JsonParseOptions::default()` and `new()` return the options `read::json` uses, accepting some input
+/// that is not strictly valid JSON. `lenient()` additionally turns on the JSON5-style extensions: a leading `+`,
+/// radix prefixes, single quotes and unquoted keys.
+/// `strict()` turns on every check: unescaped control characters in strings, numbers outside of the
+/// JSON grammar, leading `+`, radix prefixes, single quotes, unquoted keys, extra commas, empty documents,
+/// trailing characters and out of range numbers are all rejected. Every `y_` file of the JSONTestSuite
+/// parses with it and every `n_` file errors.
+///
+/// ## Example
+/// ```rust
+/// use mawu::json::JsonParseOptions;
+///
+/// let options = JsonParseOptions::new()
+///     .reject_control_characters(true)
+///     .validate_numbers(true)
+///     .allow_leading_plus(false)
+///     .allow_radix_prefixes(false)
+///     .allow_single_quotes(false)
+///     .allow_unquoted_keys(false)
+///     .allow_trailing_characters(false)
+///     .allow_extra_commas(false)
+///     .allow_empty_documents(false)
+///     .allow_out_of_range_numbers(false);
+/// assert_eq!(options, JsonParseOptions::strict());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonParseOptions {
+    pub(crate) reject_control_characters: bool,
+    pub(crate) validate_numbers: bool,
+    pub(crate) allow_leading_plus: bool,
+    pub(crate) allow_radix_prefixes: bool,
+    pub(crate) allow_single_quotes: bool,
+    pub(crate) allow_unquoted_keys: bool,
+    pub(crate) allow_trailing_characters: bool,
+    pub(crate) allow_extra_commas: bool,
+    pub(crate) allow_empty_documents: bool,
+    pub(crate) allow_out_of_range_numbers: bool,
+    pub(crate) max_depth: usize,
+}
+
+/// How deep arrays and objects may be nested by default
+const DEFAULT_MAX_DEPTH: usize = 512;
+
+impl Default for JsonParseOptions {
+    fn default() -> Self {
+        JsonParseOptions {
+            reject_control_characters: false,
+            validate_numbers: false,
+            allow_leading_plus: false,
+            allow_radix_prefixes: false,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            allow_trailing_characters: true,
+            allow_extra_commas: true,
+            allow_empty_documents: true,
+            allow_out_of_range_numbers: true,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl JsonParseOptions {
+    /// Creates new `JsonParseOptions`, identical to `JsonParseOptions::default()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns options with every check turned on, see the setters below for what each of them rejects
+    pub fn strict() -> Self {
+        JsonParseOptions {
+            reject_control_characters: true,
+            validate_numbers: true,
+            allow_leading_plus: false,
+            allow_radix_prefixes: false,
+            allow_single_quotes: false,
+            allow_unquoted_keys: false,
+            allow_trailing_characters: false,
+            allow_extra_commas: false,
+            allow_empty_documents: false,
+            allow_out_of_range_numbers: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Returns the default options with the JSON5-style extensions turned on as well
+    pub fn lenient() -> Self {
+        Self::default()
+            .allow_leading_plus(true)
+            .allow_radix_prefixes(true)
+            .allow_single_quotes(true)
+            .allow_unquoted_keys(true)
+    }
+
+    /// If `true`, unescaped control characters (`U+0000` to `U+001F`, e.g. a literal tab or newline)
+    /// inside of strings return a `JsonParseError::UnescapedCharacter`.
+    /// If `false`, they are kept in the string as is.
+    ///
+    /// Default: `false`
+    pub fn reject_control_characters(mut self, reject: bool) -> Self {
+        self.reject_control_characters = reject;
+        self
+    }
+
+    /// If `true`, numbers not following the JSON number grammar (e.g. `01`, `1.`, `-` or `1e`)
+    /// return a `JsonParseError::InvalidNumber`.
+    /// If `false`, they are parsed as well as possible.
+    ///
+    /// Default: `false`
+    pub fn validate_numbers(mut self, validate: bool) -> Self {
+        self.validate_numbers = validate;
+        self
+    }
+
+    /// If `true`, numbers may start with a `+` (e.g. `+5`), which is dropped while parsing.
+    /// If `false`, they return a `JsonParseError::InvalidNumber`.
+    ///
+    /// Default: `false`, `JsonParseOptions::lenient()` turns it on
+    pub fn allow_leading_plus(mut self, allow: bool) -> Self {
+        self.allow_leading_plus = allow;
+        self
+    }
+
+    /// If `true`, integers may be written in hexadecimal, octal or binary with a `0x`, `0o` or `0b`
+    /// prefix (e.g. `0xFF` or `-0o755`), and are parsed like any other integer.
+    /// If `false`, they return a `JsonParseError::InvalidNumber`.
+    ///
+    /// Default: `false`, `JsonParseOptions::lenient()` turns it on
+    pub fn allow_radix_prefixes(mut self, allow: bool) -> Self {
+        self.allow_radix_prefixes = allow;
+        self
+    }
+
+    /// If `true`, strings and keys may be enclosed in single quotes (e.g. `{'a': 'b'}`) as well,
+    /// ending at the next unescaped `'`. `\'` can then be used as an escape sequence in any string.
+    /// If `false`, a `'` outside of a string returns a `JsonParseError::InvalidCharacter`.
+    ///
+    /// Default: `false`, `JsonParseOptions::lenient()` turns it on
+    pub fn allow_single_quotes(mut self, allow: bool) -> Self {
+        self.allow_single_quotes = allow;
+        self
+    }
+
+    /// If `true`, object keys may be unquoted identifiers made of ASCII letters, digits, `_` and `$`,
+    /// not starting with a digit (e.g. `{a: 1, b_2: 2}`).
+    /// If `false`, every key has to be a string.
+    ///
+    /// Default: `false`, `JsonParseOptions::lenient()` turns it on
+    pub fn allow_unquoted_keys(mut self, allow: bool) -> Self {
+        self.allow_unquoted_keys = allow;
+        self
+    }
+
+    /// If `true`, anything after the top-level value is ignored (e.g. the `x` in `{} x`).
+    /// If `false`, anything but whitespace after it returns a `JsonParseError::TrailingCharacters`.
+    ///
+    /// Default: `true`
+    pub fn allow_trailing_characters(mut self, allow: bool) -> Self {
+        self.allow_trailing_characters = allow;
+        self
+    }
+
+    /// If `true`, commas in arrays and objects may be repeated, or come before the first or after the last
+    /// element (e.g. `[1,,2,]` or `{"a": 1,}`), and are skipped.
+    /// If `false`, they return a `JsonParseError::InvalidStructuralToken`.
+    /// A missing comma between two elements is always an error.
+    ///
+    /// Default: `true`
+    pub fn allow_extra_commas(mut self, allow: bool) -> Self {
+        self.allow_extra_commas = allow;
+        self
+    }
+
+    /// If `true`, an empty or whitespace-only document is parsed into `MawuValue::None`.
+    /// If `false`, it returns a `JsonParseError::UnexpectedEndOfFile`.
+    ///
+    /// Default: `true`
+    pub fn allow_empty_documents(mut self, allow: bool) -> Self {
+        self.allow_empty_documents = allow;
+        self
+    }
+
+    /// If `true`, numbers too large to be represented in 64 bits, like `1e400` or `0x1FFFFFFFFFFFFFFFF`,
+    /// are parsed into `MawuValue::None`.
+    /// If `false`, they return a `JsonParseError::NumberOutOfRange` instead of being silently dropped.
+    /// Numbers too small to be represented, like `1e-400`, are rounded to `0` either way.
+    ///
+    /// Default: `true`
+    pub fn allow_out_of_range_numbers(mut self, allow: bool) -> Self {
+        self.allow_out_of_range_numbers = allow;
+        self
+    }
+
+    /// How deep arrays and objects may be nested, deeper nesting returns a
+    /// `JsonParseError::MaxDepthExceeded` instead of overflowing the stack.
+    /// `[[]]` has a depth of 2. The parser is recursive, so raising the limit far above the
+    /// default may need a larger stack.
+    ///
+    /// Default: `512`
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+}
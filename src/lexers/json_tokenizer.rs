@@ -0,0 +1,454 @@
+use crate::prelude::*;
+use alloc::collections::VecDeque;
+
+use crate::{
+    errors::{
+        json_error::{JsonError, JsonParseError},
+        MawuError,
+    },
+    lexers::{
+        json_lexer::{is_identifier_char, json_identifier_lexer, json_value_lexer},
+        json_parse_options::JsonParseOptions,
+    },
+    mawu_value::MawuValue,
+    utils::is_whitespace,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+/// A single event emitted by the `JsonTokenizer`
+pub enum JsonEvent {
+    /// An object was opened with `{`
+    BeginObject,
+    /// An object was closed with `}`
+    EndObject,
+    /// An array was opened with `[`
+    BeginArray,
+    /// An array was closed with `]`
+    EndArray,
+    /// The key of the next key-value-pair inside an object
+    Key(String),
+    /// A primitive value, so a string, number, boolean or `null`
+    Value(MawuValue),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Container {
+    Object,
+    Array,
+}
+
+/// What an open object or array expects next
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Expect {
+    /// The first element, or the end of the container
+    FirstElement,
+    /// The next element, after a comma
+    Element,
+    /// The value of a key-value-pair, after its key and colon
+    Value,
+    /// A comma or the end of the container, after an element
+    CommaOrEnd,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Frame {
+    container: Container,
+    expect: Expect,
+}
+
+/// A pull based tokenizer over JSON data, yielding one `JsonEvent` per call to `next()`.
+///
+/// Primitive values are parsed by the same lexer `read::json` uses, and the `JsonParseOptions` are
+/// applied the same way, so the events of a document make up exactly the `MawuValue` `read::json`
+/// would return, and a document `read::json` rejects returns an error.
+/// `new()` uses `JsonParseOptions::default()`, `new_with()` takes any other options.
+///
+/// After an error was returned, the tokenizer is exhausted and only returns `None`.
+pub struct JsonTokenizer {
+    contents: VecDeque<char>,
+    options: JsonParseOptions,
+    stack: Vec<Frame>,
+    started: bool,
+    finished: bool,
+}
+
+impl JsonTokenizer {
+    /// Creates a new `JsonTokenizer` over the given characters
+    pub fn new(contents: VecDeque<char>) -> Self {
+        JsonTokenizer::new_with(contents, &JsonParseOptions::default())
+    }
+
+    /// Creates a new `JsonTokenizer` over the given characters, tokenizing according to the given `JsonParseOptions`
+    pub fn new_with(mut contents: VecDeque<char>, options: &JsonParseOptions) -> Self {
+        // a byte order mark is only allowed at the very start, see `JsonParseError::UnexpectedBom`
+        if contents.front() == Some(&'\u{FEFF}') {
+            let _ = contents.pop_front();
+        }
+        JsonTokenizer {
+            contents,
+            options: options.clone(),
+            stack: Default::default(),
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// Returns how deep the tokenizer currently is nested inside objects and arrays
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.contents.front().is_some_and(is_whitespace) {
+            let _ = self.contents.pop_front();
+        }
+    }
+
+    fn set_expect(&mut self, expect: Expect) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.expect = expect;
+        }
+    }
+
+    fn open_container(&mut self, container: Container) -> Result<(), MawuError> {
+        if self.stack.len() >= self.options.max_depth {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::MaxDepthExceeded(self.options.max_depth),
+            )));
+        }
+        let _ = self.contents.pop_front();
+        // once the new container is closed, the one around it expects a comma
+        self.set_expect(Expect::CommaOrEnd);
+        self.stack.push(Frame { container, expect: Expect::FirstElement });
+        Ok(())
+    }
+
+    fn close_container(&mut self, container: Container, token: char) -> Result<(), MawuError> {
+        let frame = match self.stack.last() {
+            Some(frame) if frame.container == container => *frame,
+            _ => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidStructuralToken(token.to_string()),
+                )));
+            }
+        };
+        match frame.expect {
+            Expect::Value => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::ExpectedValue,
+                )));
+            }
+            // a comma right before the closing token is a trailing one
+            Expect::Element if !self.options.allow_extra_commas => {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidStructuralToken(token.to_string()),
+                )));
+            }
+            Expect::FirstElement | Expect::Element | Expect::CommaOrEnd => {}
+        }
+        let _ = self.contents.pop_front();
+        let _ = self.stack.pop();
+        Ok(())
+    }
+
+    /// Checks what follows the top-level value, like `read::json` does
+    fn end_of_document(&mut self) -> Result<Option<JsonEvent>, MawuError> {
+        self.skip_whitespace();
+        match self.contents.front() {
+            Some(_) if self.options.allow_trailing_characters => Ok(None),
+            Some('\u{FEFF}') => Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnexpectedBom,
+            ))),
+            Some(_) => Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::TrailingCharacters,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<JsonEvent>, MawuError> {
+        // only the first top-level value is tokenized
+        if self.started && self.stack.is_empty() {
+            return self.end_of_document();
+        }
+        self.skip_whitespace();
+        let next_char = match self.contents.front() {
+            Some(c) => *c,
+            None => {
+                if self.stack.is_empty() && self.options.allow_empty_documents {
+                    return Ok(None);
+                }
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedEndOfFile,
+                )));
+            }
+        };
+        self.started = true;
+        let frame = self.stack.last().copied();
+        if next_char == '\u{FEFF}' {
+            Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnexpectedBom,
+            )))
+        } else if next_char == '}' {
+            self.close_container(Container::Object, next_char)?;
+            Ok(Some(JsonEvent::EndObject))
+        } else if next_char == ']' {
+            self.close_container(Container::Array, next_char)?;
+            Ok(Some(JsonEvent::EndArray))
+        } else if next_char == ',' && frame.is_some_and(|frame| frame.expect != Expect::Value) {
+            // a leading or doubled comma
+            if frame.is_some_and(|frame| frame.expect != Expect::CommaOrEnd) && !self.options.allow_extra_commas {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidStructuralToken(next_char.to_string()),
+                )));
+            }
+            let _ = self.contents.pop_front();
+            if frame.is_some_and(|frame| frame.expect == Expect::CommaOrEnd) {
+                self.set_expect(Expect::Element);
+            }
+            self.next_event()
+        } else if let Some(Frame { expect: Expect::CommaOrEnd, container }) = frame {
+            // a missing comma, reported like `read::json` does
+            Err(MawuError::JsonError(JsonError::ParseError(match container {
+                Container::Object => JsonParseError::ExpectedEndOfObject,
+                Container::Array => JsonParseError::UnexpectedCharacter(next_char.to_string()),
+            })))
+        } else if let Some(Frame { container: Container::Object, expect: Expect::FirstElement | Expect::Element }) = frame {
+            let key = if next_char == '\"' || next_char == '\'' && self.options.allow_single_quotes {
+                match json_value_lexer(&mut self.contents, &self.options, self.stack.len())? {
+                    MawuValue::String(s) => s,
+                    other => other.to_string(),
+                }
+            } else if self.options.allow_unquoted_keys && is_identifier_char(next_char, true) {
+                json_identifier_lexer(&mut self.contents)
+            } else if next_char == '\'' {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::InvalidCharacter(next_char.to_string()),
+                )));
+            } else {
+                return Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::ExpectedKey,
+                )));
+            };
+            self.skip_whitespace();
+            match self.contents.front() {
+                Some(':') => {
+                    let _ = self.contents.pop_front();
+                    self.set_expect(Expect::Value);
+                    Ok(Some(JsonEvent::Key(key)))
+                }
+                None => Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedEndOfFile,
+                ))),
+                Some('\u{FEFF}') => Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnexpectedBom,
+                ))),
+                Some(_) => Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::ExpectedColon,
+                ))),
+            }
+        } else if next_char == '{' {
+            self.open_container(Container::Object)?;
+            Ok(Some(JsonEvent::BeginObject))
+        } else if next_char == '[' {
+            self.open_container(Container::Array)?;
+            Ok(Some(JsonEvent::BeginArray))
+        } else {
+            let value = json_value_lexer(&mut self.contents, &self.options, self.stack.len())?;
+            self.set_expect(Expect::CommaOrEnd);
+            Ok(Some(JsonEvent::Value(value)))
+        }
+    }
+}
+
+impl From<&str> for JsonTokenizer {
+    fn from(value: &str) -> Self {
+        JsonTokenizer::new(value.chars().collect())
+    }
+}
+
+impl Iterator for JsonTokenizer {
+    type Item = Result<JsonEvent, MawuError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.next_event() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[test]
+fn tokenize_nested() {
+    let events = JsonTokenizer::from("{\"a\": [1, -2, \"x\"], \"b\": {\"c\": null}, \"d\": true}")
+        .collect::<Result<Vec<JsonEvent>, MawuError>>()
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            JsonEvent::BeginObject,
+            JsonEvent::Key("a".to_string()),
+            JsonEvent::BeginArray,
+            JsonEvent::Value(MawuValue::Uint(1)),
+            JsonEvent::Value(MawuValue::Int(-2)),
+            JsonEvent::Value(MawuValue::String("x".to_string())),
+            JsonEvent::EndArray,
+            JsonEvent::Key("b".to_string()),
+            JsonEvent::BeginObject,
+            JsonEvent::Key("c".to_string()),
+            JsonEvent::Value(MawuValue::None),
+            JsonEvent::EndObject,
+            JsonEvent::Key("d".to_string()),
+            JsonEvent::Value(MawuValue::Bool(true)),
+            JsonEvent::EndObject,
+        ]
+    );
+
+    let scalar = JsonTokenizer::from(" 42 ").collect::<Vec<_>>();
+    assert_eq!(scalar.len(), 1);
+    assert_eq!(scalar[0].as_ref().unwrap(), &JsonEvent::Value(MawuValue::Uint(42)));
+
+    assert_eq!(JsonTokenizer::from("").count(), 0);
+}
+
+#[test]
+fn tokenize_errors() {
+    let mismatched = JsonTokenizer::from("[1}").collect::<Vec<_>>();
+    assert!(mismatched.last().unwrap().is_err());
+
+    let missing_colon = JsonTokenizer::from("{\"a\" 1}").collect::<Vec<_>>();
+    assert!(missing_colon.last().unwrap().is_err());
+
+    let missing_value = JsonTokenizer::from("{\"a\":}").collect::<Vec<_>>();
+    assert!(missing_value.last().unwrap().is_err());
+
+    let mut truncated = JsonTokenizer::from("[1, 2");
+    assert_eq!(truncated.next().unwrap().unwrap(), JsonEvent::BeginArray);
+    assert!(truncated.next().unwrap().is_ok());
+    assert!(truncated.next().unwrap().is_ok());
+    assert!(truncated.next().unwrap().is_err());
+    assert!(truncated.next().is_none());
+}
+
+#[test]
+fn tokenize_commas() {
+    let error = |input: &str| JsonTokenizer::new_with(input.chars().collect(), &JsonParseOptions::strict()).find_map(Result::err);
+    let structural = |input: &str, token: &str| {
+        matches!(error(input), Some(MawuError::JsonError(JsonError::ParseError(JsonParseError::InvalidStructuralToken(ref t)))) if t == token)
+    };
+    // missing commas
+    assert!(matches!(
+        error("[1 2]"),
+        Some(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnexpectedCharacter(ref c)))) if c == "2"
+    ));
+    assert!(matches!(
+        error("{\"a\":1 \"b\":2}"),
+        Some(MawuError::JsonError(JsonError::ParseError(JsonParseError::ExpectedEndOfObject)))
+    ));
+    assert!(error("[\"a\" \"b\"]").is_some());
+    assert!(error("[[] {}]").is_some());
+    // leading, doubled and trailing commas
+    assert!(structural("[,,1]", ","));
+    assert!(structural("[,1]", ","));
+    assert!(structural("[1,,2]", ","));
+    assert!(structural("{,\"a\": 1}", ","));
+    assert!(structural("{\"a\": 1,,\"b\": 2}", ","));
+    assert!(structural("[1,]", "]"));
+    assert!(structural("{\"a\": 1,}", "}"));
+    assert!(structural("[,]", ","));
+    // keys have to be strings
+    assert!(matches!(
+        error("{{}: 1}"),
+        Some(MawuError::JsonError(JsonError::ParseError(JsonParseError::ExpectedKey)))
+    ));
+
+    for valid in ["[1, [2, 3], {\"a\": [4]}, \"b\"]", "{\"a\": {}, \"b\": [], \"c\": 1}", "[]", "{}"] {
+        assert!(error(valid).is_none(), "{}", valid);
+    }
+    // the default options skip extra commas, like `read::json`
+    let events = JsonTokenizer::from("[,1,,2,]").collect::<Result<Vec<JsonEvent>, MawuError>>().unwrap();
+    assert_eq!(events, vec![JsonEvent::BeginArray, JsonEvent::Value(MawuValue::Uint(1)), JsonEvent::Value(MawuValue::Uint(2)), JsonEvent::EndArray]);
+    assert!(JsonTokenizer::from("{,\"a\": 1,,}").all(|event| event.is_ok()));
+    assert!(JsonTokenizer::from("[1 2]").any(|event| event.is_err()));
+}
+
+#[cfg(test)]
+/// Builds the value the events of `tokenizer` make up, or returns the first error
+fn rebuild(tokenizer: &mut JsonTokenizer) -> Result<MawuValue, MawuError> {
+    let value = match tokenizer.next() {
+        None => return Ok(MawuValue::None),
+        Some(event) => rebuild_from(event?, tokenizer)?,
+    };
+    match tokenizer.next() {
+        None => Ok(value),
+        Some(Err(e)) => Err(e),
+        Some(Ok(event)) => panic!("unexpected event after the top-level value: {:?}", event),
+    }
+}
+
+#[cfg(test)]
+fn rebuild_from(event: JsonEvent, tokenizer: &mut JsonTokenizer) -> Result<MawuValue, MawuError> {
+    let next = |tokenizer: &mut JsonTokenizer| tokenizer.next().expect("the events end inside of a container");
+    match event {
+        JsonEvent::Value(value) => Ok(value),
+        JsonEvent::BeginArray => {
+            let mut array = Vec::new();
+            loop {
+                match next(tokenizer)? {
+                    JsonEvent::EndArray => return Ok(MawuValue::from(array)),
+                    event => array.push(rebuild_from(event, tokenizer)?),
+                }
+            }
+        }
+        JsonEvent::BeginObject => {
+            let mut object = crate::mawu_value::MawuObject::default();
+            loop {
+                match next(tokenizer)? {
+                    JsonEvent::EndObject => return Ok(MawuValue::Object(object)),
+                    JsonEvent::Key(key) => {
+                        let event = next(tokenizer)?;
+                        object.insert(key, rebuild_from(event, tokenizer)?);
+                    }
+                    event => panic!("unexpected event inside of an object: {:?}", event),
+                }
+            }
+        }
+        event => panic!("unexpected event: {:?}", event),
+    }
+}
+
+#[test]
+fn tokenize_matches_lexer() {
+    use crate::lexers::json_lexer::json_lexer_with;
+
+    let inputs = [
+        "", " \n ", "\u{FEFF}[1]", "[1]\u{FEFF}", "[\u{FEFF}1]", "42", " \"x\" ", "true", "null", "-0.5e2", "+5", "0xFF", "-0o10", "1e400",
+        "[]", "{}", "[1, [2, 3], {\"a\": [4]}, \"b\"]", "{\"a\": {\"b\": {}}, \"c\": [[], [null]]}",
+        "[,]", "[,1]", "[1,]", "[1,,2]", "{,}", "{\"a\": 1,}", "{,\"a\": 1,,\"b\": 2}",
+        "[1 2]", "[\"a\" \"b\"]", "[[] {}]", "{\"a\": 1 \"b\": 2}", "{\"a\" 1}", "{\"a\":}", "{\"a\": ,}", "[1}", "{]", "[1, 2", "{\"a\"",
+        "{a: 1, b_2: [true]}", "{ $id : 'x' }", "{'a': 'b'}", "['a', \"b\"]", "{a b: 1}", "{1: 1}", "{{}: 1}", "{'a\": 1}",
+        "[1] x", "{} {}", "1 2", "\"a\" \"b\"", "[\"\t\"]", "[01]", "[1.]", "[\"\\u00e9\\ud83d\\ude00\"]", "[\"\\x\"]",
+        "[[[[1]]]]", "{\"a\": [{\"b\": [{}]}]}",
+    ];
+    for options in [JsonParseOptions::default(), JsonParseOptions::lenient(), JsonParseOptions::strict(), JsonParseOptions::default().max_depth(3)] {
+        for input in inputs {
+            let expected = json_lexer_with(input.chars().collect(), &options);
+            let tokenized = rebuild(&mut JsonTokenizer::new_with(input.chars().collect(), &options));
+            match (&expected, &tokenized) {
+                (Ok(expected), Ok(tokenized)) => assert_eq!(expected, tokenized, "{:?} {:?}", input, options),
+                (Err(_), Err(_)) => {}
+                _ => panic!("{:?} {:?}: read::json returned {:?}, the tokenizer {:?}", input, options, expected, tokenized),
+            }
+        }
+    }
+}
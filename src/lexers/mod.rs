@@ -1,2 +1,9 @@
 pub mod csv_lexer;
+pub mod csv_parse_options;
+pub mod csv_sniffer;
+pub mod json_borrowed_lexer;
+#[cfg(feature = "std")]
+pub mod json_array_stream;
 pub mod json_lexer;
+pub mod json_parse_options;
+pub mod json_tokenizer;
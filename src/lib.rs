@@ -24,6 +24,7 @@
 //! - Write pretty with custom spacing
 //! - Supports CSV files with or without header
 //! - Supports missing or not provided values
+//! - Usable in `no_std` environments with `alloc`
 //! - Fully documented
 //! - Handling of edge cases is explained in the documentation
 //! - Tries to stay as close to the rfc4180, rfc8259 and ECMA-404 standard as possible for maximum interoperability
@@ -40,6 +41,8 @@
 //!     - [Naming the Creation: A Legacy of the Divine](#naming-the-creation-a-legacy-of-the-divine)
 //!     - [Contents](#contents)
 //!     - [Using Mawu](#using-mawu)
+//!         - [Optional features](#optional-features)
+//!             - [Without `std`](#without-std)
 //!     - [`MawuValue`](#mawuvalue)
 //!        - [Convenience functions](#convenience-functions)
 //!        - [An exhaustive list of all `MawuValue` types and functions](#an-exhaustive-list-of-all-mawuvalue-types-and-functions)
@@ -54,6 +57,8 @@
 //!             - [Without header](#without-header)
 //!         - [CSV Return value](#csv-return-value)
 //!         - [CSV Usage](#csv-usage)
+//!         - [Other delimiters](#other-delimiters)
+//!         - [CSV parse options](#csv-parse-options)
 //!     - [Reading JSON](#reading-json)
 //!         - [Edge cases](#edge-cases)
 //!             - [Objects](#objects)
@@ -62,11 +67,17 @@
 //!             - [Strings](#strings)
 //!             - [Structure](#structure)
 //!         - [JSON Usage](#json-usage)
+//!         - [Tokenizing JSON](#tokenizing-json)
+//!         - [Borrowed parsing](#borrowed-parsing)
 //!     - [Writing](#writing)
 //!         - [Writing data to disk](#writing-data-to-disk)
 //!             - [Writing examples](#writing-examples)
 //!         - [Writing pretty data to disk](#writing-pretty-data-to-disk)
 //!             - [Writing pretty examples](#writing-pretty-examples)
+//!     - [JSON utilities](#json-utilities)
+//!         - [Canonical JSON](#canonical-json)
+//!         - [JSON Pointer and Patch](#json-pointer-and-patch)
+//!         - [JSON Schema validation](#json-schema-validation)
 //!
 //! ## Using Mawu
 //! Start by adding this repository to your `Cargo.toml`.
@@ -135,6 +146,45 @@
 //! In the first printed line in the code example above, is a perfect example of the `MawuValue` enum.
 //! Because Mawu only returns `MawuValue`'s, and you will be interacting with them a lot, I really recommend reading the chapter on `MawuValue`'s.
 //!
+//! ### Optional features
+//! Mawu has no dependencies by default. Some functionality can be enabled with cargo features:
+//! - `std` - Enabled by default. Everything touching files or readers, so the `read` module, `write`, `write_pretty` and all path based functions of the `json` and `csv` modules, needs it. See [Without `std`](#without-std).
+//! - `flate2` - Gzip compressed files are recognised by their magic bytes and decompressed before parsing, so all reading functions accept `.gz` files as is.
+//! - `rayon` - Adds `csv::read_csv_headed_parallel`, parsing the records of large headed CSV files across all cores. The file is split at record boundaries, never inside of quoted values, and the result is the same as `read::csv_headed` returns. On a single core it is slightly slower because of the splitting, and files smaller than about 64k characters are not split at all.
+//!   It also adds `json::from_str_parallel` and `json::from_str_parallel_with`, parsing the elements of a large top-level JSON array across all cores in the same way. The array is only split between its elements, and the result is the same as `json::from_str` returns. Like for CSV, it is slightly slower on a single core.
+//! - `serde` - Adds `csv::from_path_typed`, reading a headed CSV file straight into a `Vec` of any type implementing serde's `Deserialize`, with the header names as field names, and `json::from_str_typed`, parsing JSON straight into such a type. CSV values are only parsed into numbers or bools for fields of that type, so `String` fields keep values like `02139` as they are. `json::to_string_typed` writes any type implementing `Serialize` as JSON, formatted exactly like `json::to_string` formats a `MawuValue`. `json::from_str_typed` and `json::to_string_typed` work without `std` as well.
+//! - `btreemap` - `MawuValue::Object` wraps a `BTreeMap` instead of a `HashMap`, so objects iterate and are written sorted by key, giving stable output without calling `canonicalize`. Lookups and inserts are `O(log n)` instead of `O(1)`, making parsing large objects somewhat slower.
+//! ```toml
+//! [dependencies]
+//! mawu = { git = "https://github.com/Xqhare/mawu", features = ["flate2"] }
+//! ```
+//!
+//! #### Without `std`
+//! With `default-features = false`, Mawu is `no_std` and only needs `alloc`.
+//! The parsers and serializers work on strings instead of files:
+//! - `json::from_str(input)` and `json::from_str_with(input, &options)` parse JSON
+//! - `json::from_str_borrowed(input)` and `json::from_str_borrowed_with(input, &options)` parse JSON into a `MawuValueRef`, see [Borrowed parsing](#borrowed-parsing)
+//! - `json::from_str_interned(input)` and `json::from_str_interned_with(input, &options)` parse JSON into an `InternedMawuValue`, see [Borrowed parsing](#borrowed-parsing)
+//! - `json::from_str_partial(input)` parses the first JSON value of `input` and returns it with the byte offset right after it
+//! - `json::from_str_many(input)` parses every JSON value of `input`, for documents concatenated with or without whitespace between them
+//! - `json::to_string(&value, spaces)` serializes JSON like `write_pretty` would, `0` spaces writes everything on one line
+//! - `json::to_string_with(&value, spaces, &options)` does the same, writing `NaN` and infinite floats according to its `JsonWriteOptions`
+//! - `csv::from_str_headed(input)` and `csv::from_str_headless(input)` parse CSV like `read::csv_headed` and `read::csv_headless`
+//! - `csv::from_str_with(input, &options)` parses CSV according to its `CsvParseOptions`
+//! - `csv::from_str_table_with(input, &options)` parses headed CSV into a `CsvTable`, see [Tables](#tables)
+//! - `csv::split_record(line, delimiter, quote)` splits a single record into its unquoted fields
+//! - `csv::to_string(&value, spaces)` serializes a `MawuValue::CSVObject` or `MawuValue::CSVArray`
+//! - `csv::to_string_with(&value, spaces, &options)` does the same, quoting values according to its `CsvWriteOptions`
+//!
+//! As there is no `HashMap` without `std`, both objects and the rows of headed CSV files are backed by a `BTreeMap`, as with the `btreemap` feature, and `MawuError::IoError` does not exist.
+//! The `mawu!` macro works in `no_std` crates as well.
+//!
+//! The crate in `tests/no_std` is a `no_std` library using Mawu like this, and only builds as long as all of the above stays true:
+//! ```sh
+//! cargo build --manifest-path tests/no_std/Cargo.toml
+//! cargo build --manifest-path tests/no_std/Cargo.toml --features btreemap
+//! ```
+//!
 //! ## `MawuValue`
 //! Mawu uses the `MawuValue` enum to represent the different types of values that can be found in JSON and CSV files.
 //!
@@ -146,11 +196,25 @@
 //! Mawu supports only 64-bit systems, and all numbers parsed by Mawu are stored in a `_64` type, e.g. `u64`, `f64` or `i64`.
 //! This also means that Mawu does not support `u128` or `i128`.
 //!
+//! `MawuValue` and `MawuError` are both `Send` and `Sync`, so parsed data can be moved or shared between threads freely.
+//! Cloning a `MawuValue` copies the entire tree. To hand the same document to many threads or tasks, `into_shared` wraps it in an `Arc` as a `SharedMawuValue`, which is cloned by incrementing a reference count and gives read-only access to every reading method of `MawuValue`.
+//!
+//! `MawuValue` also implements `Eq` and `Hash`, so it can be used as a key in a `HashMap` or inside a `HashSet`. Floats are hashed by their bit pattern, and because `NaN` is never equal to itself, a `MawuValue::Float(f64::NAN)` can not be found again once inserted.
+//!
 //! ### Convenience Functions
 //! Mawu provides convenience functions for all types in the form of `is_{MawuValue}`, `as_{MawuValue}` and `to_{MawuValue}` functions.
 //!
 //! Calling `is_` will return `true` if the value is the type requested, and `false` otherwise. This can be useful if you have different data-types in the same array.
 //! `is_true`, `is_false`, `is_number` and `is_null` are convenience functions to check if the value is a boolean and `true`, if the value is a boolean and `false`, if the value is a number (either a float, integer or signed integer) or if the value is `None`, respectively and can be used in logic without any further processing or allocating needed.
+//! For generic traversal code, `is_container` is `true` for arrays, objects and both CSV types, `is_scalar` for everything else including `None`, and `is_csv` for both CSV types.
+//! `is_empty_container` is `true` only for containers without elements, unlike `is_empty`, which is also `true` for `0`, `""` and `None`.
+//! `as_number` returns any number wrapped in a `MawuNumber`, so unsigned integers, signed integers and floats can be handled in one `match`. `MawuNumber::as_f64` converts it to a float.
+//! To convert between scalar types, `coerce_to` takes a `MawuScalarType` and returns the converted value, e.g. `MawuValue::String("42".to_string()).coerce_to(MawuScalarType::Uint)` returns `Some(MawuValue::Uint(42))`. Strings are parsed ignoring surrounding whitespace, `true` and `false` coerce to and from `1` and `0`, and values that would lose information, like `4.5` into an integer or `9007199254740993` into a float, return `None`. `None` and containers are never coerced.
+//! As floats are compared exactly, `approx_eq` is provided to compare numbers of any numeric type within a given epsilon. `NaN` is never approximately equal to anything.
+//! `eq_ignoring_nulls` compares like `==`, but treats keys mapped to `MawuValue::None` as absent, so `{"a": null}` equals `{}` when comparing configs.
+//! To read a field of an object, `get_str`, `get_u64`, `get_i64`, `get_f64`, `get_bool`, `get_array` and `get_object` combine `get` with the matching conversion, e.g. `config.get_u64("port")` instead of `config.get("port").and_then(|v| v.to_uint())`.
+//! If a default value should be used instead of `None`, `as_str_or`, `to_u64_or`, `to_i64_or`, `to_f64_or` and `to_bool_or` return the converted value or the supplied default.
+//! For quick scripts and tests, `expect_str`, `expect_u64`, `expect_i64`, `expect_f64`, `expect_bool`, `expect_array` and `expect_object` return the value directly and panic with the given message and the actual type if the type does not match, just like `Option::expect`.
 //!
 //! When you call any `as_` or `to_` function on a `MawuValue` you are returned a `Option()` wrapping the desired value, or `None` if the value is not the type requested.
 //! Calling `as_null` or `to_null` will return `None` instead when the value is none, and `Some()` wrapping nothing otherwise.
@@ -166,6 +230,9 @@
 //!
 //! The `new_` functions are not in a place where I would call them actually useful. Instead, please construct a `MawuValue` using the `MawuValue::from()` function.
 //!
+//! `depth` returns the deepest level of nesting and `node_count` the total number of values inside any `MawuValue`, useful to reject overly large or deep documents after parsing.
+//! `heap_size` estimates the bytes a `MawuValue` has allocated, counting the capacity of every string and array and the entries of every object, to bound in-memory caches of parsed documents.
+//!
 //! For a better overview, take a look at the table below.
 //!
 //! ### An exhaustive list of all `MawuValue` types and functions
@@ -177,52 +244,78 @@
 //!         - is returned by `MawuValue::default()` and `MawuValue::new()`
 //!         - `is_empty` always returns `true`
 //!         - `len` always returns 0
+//!         - `is_scalar` returns `true`
 //!     - `MawuValue::Bool`
 //!         - wrapping a `bool`
 //!         - `as_bool` and `to_bool` return `Option<bool>`
+//!         - `expect_bool` returns the value or panics with the given message
+//!         - `to_bool_or` returns the value or a supplied default
 //!         - `is_true` and `is_false` return `true` or `false` respectively
 //!         - `is_bool` returns `true`
 //!         - `is_empty` always returns `false`
 //!         - `len` always returns 0
+//!         - `is_scalar` returns `true`
 //!     - `MawuValue::Uint`
 //!         - wrapping a `u64`
 //!         - `as_uint` and `to_uint` return `Option<u64>`
+//!         - `expect_u64` returns the value or panics with the given message
+//!         - `to_u64_or` returns the value or a supplied default
+//!         - `as_number` returns `Option<MawuNumber>`
+//!         - `approx_eq` compares it to any other number within an epsilon
 //!         - `to_usize` returns `Option<usize>`
 //!         - `is_number` and `is_uint` return `true`
 //!         - `is_empty` returns `true` if the unsigned integer is 0
 //!         - `is_negative` always returns `false` and `is_positive` returns `true`
 //!         - `len` always returns 0
+//!         - `is_scalar` returns `true`
 //!     - `MawuValue::Int`
 //!         - wrapping a `i64`
 //!         - `as_int` and `to_int` return `Option<i64>`
+//!         - `expect_i64` returns the value or panics with the given message
+//!         - `to_i64_or` returns the value or a supplied default
+//!         - `as_number` returns `Option<MawuNumber>`
+//!         - `approx_eq` compares it to any other number within an epsilon
 //!         - `to_isize` returns `Option<isize>`
 //!         - `is_number` and `is_int` return `true`
 //!         - `is_empty` returns `true` if the int is 0
 //!         - `is_negative` and `is_positive` return `true` if the int is negative or positive
 //!         - `len` always returns 0
+//!         - `is_scalar` returns `true`
 //!     - `MawuValue::Float`
 //!         - wrapping a `f64`
 //!         - `as_float` and `to_float` return `Option<f64>`
+//!         - `expect_f64` returns the value or panics with the given message
+//!         - `to_f64_or` returns the value or a supplied default
+//!         - `as_number` returns `Option<MawuNumber>`
+//!         - `approx_eq` compares it to any other number within an epsilon
 //!         - `is_number` and `is_float` return `true`
 //!         - `is_empty` returns `true` if the float is 0.0
 //!         - `is_negative` and `is_positive` return `true` if the float is negative or positive
 //!         - `len` always returns 0
+//!         - `is_scalar` returns `true`
 //!     - `MawuValue::String`
 //!         - wrapping a `String`
 //!         - `as_string` and `to_string` return `Option<String>`
 //!         - `as_str` returns `Option<&str>`
+//!         - `as_bytes` returns `Option<&[u8]>`, the UTF-8 bytes of the string
+//!         - `expect_str` returns the value or panics with the given message
+//!         - `as_str_or` returns the value or a supplied default
 //!         - `is_string` returns `true`
 //!         - `is_empty` returns `true` if the string has a length of 0
 //!         - `len` returns the length of the string
+//!         - `is_scalar` returns `true`
 //! - JSON exclusive types
 //!     - `MawuValue::Array`
 //!         - wrapping a `Vec<MawuValue>`
 //!         - `as_array` and `to_array` return `Option<Vec<MawuValue>>`
-//!         - `is_array` returns `true`
+//!         - `expect_array` returns the value or panics with the given message
+//!         - `is_array` and `is_container` return `true`
+//!         - `is_empty_container` returns `true` if the array is empty
 //!         - can be constructed by using `MawuValue::new_array`
+//!         - `get_or_insert_array` returns the array mutably, turning a `MawuValue::None` into an empty array first
 //!         - `is_empty` returns `true` if the array is empty
 //!         - `clear` removes all elements from the array
-//!         - `iter_array` returns an iterator over the array
+//!         - `iter_array` and `iter_array_mut` return an iterator over the array, `None` for any other type
 //!         - `array_insert` inserts an element into the array at the given index
 //!         - `array_remove` removes an element from the array at the given index
 //!         - `pop` removes and returns the last element of the array
@@ -230,56 +323,76 @@
 //!         - `contains` returns `true` if the array contains the element
 //!         - `len` returns the number of elements in the array
 //!         - `clear` removes all elements from the array
-//!         - `iter_array` returns an iterator over the array
+//!         - `iter_array` and `iter_array_mut` return an iterator over the array, `None` for any other type
 //!         - `array_insert` inserts an element into the array at the given index
 //!         - `array_remove` removes an element from the array at the given index
 //!         - `array_peek` returns a reference to the element from the array at the given index
 //!         - `pop` removes and returns the last element of the array
 //!         - `push` appends an element to the end of the array
 //!         - `contains` returns `true` if the array contains the element
+//!         - `retain_array` keeps only the elements for which the given closure returns `true`
+//!         - `to_csv_rows` converts an array of objects into a `MawuValue::CSVObject`, with the union of all keys as columns and `MawuValue::None` for missing keys
 //!     - `MawuValue::Object`
-//!         - wrapping a `HashMap<String, MawuValue>`
-//!         - `as_object` and `to_object` return `Option<HashMap<String, MawuValue>>`
-//!         - `is_object` returns `true`
+//!         - wrapping a `MawuObject`, a `HashMap<String, MawuValue>` or with the `btreemap` feature a `BTreeMap<String, MawuValue>`
+//!         - `as_object` and `to_object` return `Option<MawuObject>`
+//!         - `expect_object` returns the value or panics with the given message
+//!         - `is_object` and `is_container` return `true`
+//!         - `is_empty_container` returns `true` if the object is empty
 //!         - can be constructed by using `MawuValue::new_object`
+//!         - `get_or_insert_object` returns the object mutably, turning a `MawuValue::None` into an empty object first
 //!         - `is_empty` returns `true` if the object is empty
 //!         - `clear` removes all elements from the object
-//!         - `iter_object` returns an iterator over the object
+//!         - `iter_object` and `iter_object_mut` return an iterator over the key-value-pairs of the object, `None` for any other type
+//!         - `keys` and `values` return an iterator over the keys or the values of the object, `None` for any other type
 //!         - `get` returns a `Option<MawuValue>` if the object contains the key
 //!         - `object_insert` inserts an element into the object at the given key
 //!         - `object_remove` removes an element from the object at the given key
 //!         - `has_key` returns `true` if the object contains the key
+//!         - `contains_key` does the same for a `&str` key, without allocating
 //!         - `len` returns the number of elements in the object
 //!         - `clear` removes all elements from the object
-//!         - `iter_object` returns an iterator over the object
+//!         - `iter_object` and `iter_object_mut` return an iterator over the key-value-pairs of the object, `None` for any other type
 //!         - `get` returns a `Option<MawuValue>` if the object contains the key
 //!         - `object_insert` inserts an element into the object at the given key
 //!         - `object_remove` removes an element from the object at the given key
 //!         - `has_key` returns `true` if the object contains the key
+//!         - `retain_object` keeps only the key-value-pairs for which the given closure returns `true`
+//!         - `find_all` returns every value stored under the given key, no matter how deeply nested
+//!         - `redact` replaces every value stored under a key matching the given predicate, no matter how deeply nested
+//!         - `get_str`, `get_u64`, `get_i64`, `get_f64`, `get_bool`, `get_array` and `get_object` return the value at the given key if it has the requested type, `None` otherwise
 //! - CSV exclusive types
 //!     - `MawuValue::CsvArray`
 //!         - wrapping a `Vec<Vec<MawuValue>>`
 //!         - `as_csv_array` and `to_csv_array` return `Option<Vec<Vec<MawuValue>>>`
-//!         - `is_csv_array` returns `true`
+//!         - `is_csv_array`, `is_csv` and `is_container` return `true`
+//!         - `is_empty_container` returns `true` if the array is empty
 //!         - can be constructed by using `MawuValue::new_csv_array`
 //!         - `is_empty` returns `true` if the array is empty
 //!         - `clear` removes all elements from the array
 //!         - `len` returns the number of elements in the array
 //!         - `clear` removes all elements from the array
+//!         - `csv_column_index` returns the values of one column, rows without it contribute `MawuValue::None`
+//!         - `to_pretty_table` renders the rows as a text table with aligned columns
 //!     - `MawuValue::CsvObject`
 //!         - wrapping a `Vec<HashMap<String, MawuValue>>`
 //!         - `as_csv_object` and `to_csv_object` return `Option<Vec<HashMap<String, MawuValue>>>`
-//!         - `is_csv_object` returns `true`
+//!         - `is_csv_object`, `is_csv` and `is_container` return `true`
+//!         - `is_empty_container` returns `true` if the object is empty
 //!         - can be constructed by using `MawuValue::new_csv_object`
 //!         - `is_empty` returns `true` if the object is empty
 //!         - `clear` removes all elements from the object
 //!         - `len` returns the number of elements in the object
 //!         - `clear` removes all elements from the object
+//!         - `csv_column` returns the values of one column, rows without it contribute `MawuValue::None`
+//!         - `csv_column_stats` returns the count, sum, mean, min and max of the numeric values of one column as `ColumnStats`
+//!         - `csv_column_f64` returns the values of one column as `Vec<f64>`, either failing on or replacing non-numeric values with `NaN`
+//!         - `csv_group_by` groups the rows by the value of one column, rows without it are grouped under `""`
+//!         - `to_pretty_table` renders the rows as a text table with aligned columns, a header and a separator row
 //!
 //! #### Example of getting a `MawuValue` if its type is not known or different in the same field
 //! This example shows the usage of `is_`, `as_` and `to_` functions.
 //! ```rust
-//! use mawu::mawu_value::MawuValue;
+//! use mawu::mawu_value::{MawuObject, MawuValue};
 //! use mawu::read::json;
 //!
 //! use std::collections::HashMap;
@@ -335,8 +448,8 @@
 //!     assert_eq!(array.len(), 1);
 //!     assert_eq!(owned_array.len(), 1);
 //! } else if mawu_value.is_object() {
-//!     let object: &HashMap<String, MawuValue> = mawu_value.as_object().unwrap();
-//!     let owned_object: HashMap<String, MawuValue> = mawu_value.to_object().unwrap();
+//!     let object: &MawuObject = mawu_value.as_object().unwrap();
+//!     let owned_object: MawuObject = mawu_value.to_object().unwrap();
 //!     // Do something with `object`
 //!     assert_eq!(object.len(), 1);
 //!     assert_eq!(owned_object.len(), 1);
@@ -487,6 +600,41 @@
 //! assert_eq!(another_mawu_value.get("key10").unwrap(), &MawuValue::Int(10));
 //! ```
 //!
+//! Arrays and objects can also be collected from an iterator of `MawuValue`'s or `(String, MawuValue)` pairs, and extended the same way:
+//! ```rust
+//! use mawu::mawu_value::MawuValue;
+//!
+//! let mut array: MawuValue = (1..=3).map(MawuValue::from).collect();
+//! array.extend(vec![MawuValue::from("four")]);
+//! assert_eq!(array.len(), 4);
+//!
+//! let object: MawuValue = vec!["a", "b"].into_iter().map(|key| (key.to_string(), MawuValue::from(key))).collect();
+//! assert_eq!(object.get("b").unwrap(), &MawuValue::from("b"));
+//! ```
+//!
+//! Objects and arrays can also be built fluently with `MawuObjectBuilder` and `MawuArrayBuilder`, which can be nested without calling `build` on the inner builders:
+//! ```rust
+//! use mawu::mawu_value::{MawuArrayBuilder, MawuObjectBuilder, MawuValue};
+//!
+//! let value = MawuObjectBuilder::new()
+//!     .set("name", "mawu")
+//!     .set("nums", MawuArrayBuilder::new().add(1).add(2))
+//!     .set("nested", MawuObjectBuilder::new().set("ok", true))
+//!     .build();
+//! assert_eq!(value.get("nested").unwrap().get("ok").unwrap(), &MawuValue::Bool(true));
+//! ```
+//!
+//! For literal data, most often in tests or for default values, the `mawu!` macro builds a `MawuValue` from JSON like syntax, with any rust expression allowed as a value.
+//! Literals become the value parsing them as JSON would return, so string literals are always a `MawuValue::String`, and non-negative integers a `MawuValue::Uint`:
+//! ```rust
+//! use mawu::{mawu, mawu_value::MawuValue};
+//!
+//! let name = "x";
+//! let value = mawu!({"name": name, "nums": [1, 2, 3], "nested": {"ok": true, "nothing": null}});
+//! assert_eq!(value.get("nums").unwrap(), &MawuValue::from(vec![1u64, 2, 3]));
+//! assert_eq!(value.get("nested").unwrap().get("ok").unwrap(), &MawuValue::Bool(true));
+//! ```
+//!
 //! There are more examples in the function docs.
 //! 
 //! #### Creating a `MawuValue` for CSV data
@@ -555,7 +703,10 @@
 //!          - `Vec<T>` where T can be any type that can be converted to `MawuValue`
 //!     - objects
 //!          - `Vec<(K, V)>` where K is a string and V is any type that can be converted to `MawuValue`
+//!          - `[(&str, V); N]` and `&[(&str, V)]` where V is any type that can be converted to `MawuValue`, e.g. `MawuValue::from([("a", 1), ("b", 2)])`
+//!            - a single pair is written as a one element array, e.g. `MawuValue::from([("k", 5)])`, as a `From<(K, V)>` would conflict with `Vec<T>`
 //!          - `HashMap<K, V>` where K is a string and V is any type that can be converted to `MawuValue`
+//!          - `BTreeMap<K, V>` where K is a string and V is any type that can be converted to `MawuValue`
 //!
 //! ## `MawuError`
 //! `MawuError`'s are the errors that can be returned by `MawuValue`'s methods.
@@ -576,6 +727,7 @@
 //! - `MawuError`
 //!     - `IoError`
 //!         - all possible `std::io::Error`s
+//!         - only with the `std` feature
 //!     - `CsvError`
 //!         - `ParseError(CsvParseError)`
 //!             - should you encounter this, your special CSV is not compatible with Mawu
@@ -586,9 +738,18 @@
 //!                 - `ExtraValue(String)`
 //!                 - `UnrecognizedHeader(String)`
 //!                 - `UnexpectedNewline`
+//!                 - `UndetectableDelimiter`
+//!                 - `DuplicateHeader(String)`
 //!         - `WriteError(CsvWriteError)`
 //!             - `NotCSV`
 //!             - `UnallowedType(String)`
+//!             - `NeedsQuoting(String)`
+//!             - `NotCsvType(String)`
+//!             - `InconsistentColumns(String)`
+//!             - `IoError(std::io::Error)`
+//!                 - only with the `std` feature
+//!         - `DeserializeError(String)`
+//!             - only with the `serde` feature
 //!     - `JsonError`
 //!         - `ParseError(JsonParseError)`
 //!             - should you encounter this, I am certain that your file is not valid JSON
@@ -607,9 +768,33 @@
 //!                 - `ExpectedValue`
 //!                 - `ExpectedEndOfObject`
 //!                 - `InvalidNumber(String)`
+//!                 - `UnpairedSurrogate(String)`
+//!                 - `MaxDepthExceeded(usize)`
+//!                 - `ExpectedTopLevelObject(String)`
+//!                 - `ExpectedTopLevelArray(String)`
+//!                 - `UnexpectedBom`
+//!                 - `TrailingCharacters`
+//!                 - `NumberOutOfRange(String)`
 //!         - `WriteError(JsonWriteError)`
 //!             - `NotJSON`
 //!             - `NotJSONType(String)`
+//!             - `InvalidNumber(String)`
+//!             - `IoError(std::io::Error)`
+//!                 - only with the `std` feature
+//!         - `PointerError(JsonPointerError)`
+//!             - `InvalidPointer(String)`
+//!             - `PathNotFound(String)`
+//!             - `InvalidIndex(String)`
+//!         - `PatchError(JsonPatchError)`
+//!             - `NotAPatch`
+//!             - `InvalidOperation(String)`
+//!             - `MissingMember(String)`
+//!             - `TestFailed(String)`
+//!             - `MoveIntoChild(String)`
+//!         - `DeserializeError(String)`
+//!             - only with the `serde` feature
+//!         - `SerializeError(String)`
+//!             - only with the `serde` feature
 //!     - `InternalError`
 //!     - should you encounter this, I am certain that there is a bug in Mawu, please report it
 //!          - `UnableToLockMasterMutex`
@@ -626,7 +811,8 @@
 //! This is not part of the rfc4180 standard and thus not implemented in Mawu, instead it would be treated as a single string, with the appropriate errors. Meaning that Mawu would produce a `MawuValue::String("[aaa, bbb, ccc]")` instead.
 //! `aaa / "bbb" / ccc` would produce an error for example, as Mawu treats the entire thing as one string, but it encounters unescaped double-quotes.
 //!
-//! Another example is the way encoding is implemented. Mawu uses `utf-8` encoding exclusively for CSV, and does not recognize or produce a `BOM` or similar at the beginning of the file.
+//! Another example is the way encoding is implemented. Mawu uses `utf-8` encoding exclusively for CSV, and does not produce a `BOM` or similar at the beginning of the file. A UTF-8 `BOM` at the very beginning of a file, as written by Excel and a lot of other Windows software, is silently removed before parsing.
+//! Files encoded in `utf-16` (little or big endian) or `latin-1` can be read with the `_with_encoding` variants of the reading functions, e.g. `read::csv_headed_with_encoding(path, Encoding::Utf16Le)`, any `BOM` of the chosen encoding is removed as well.
 //! There are CSV files encoded in `utf-16`, `utf-32` or even some `ASCII`-variants, and there are some more esoteric implementations like the IBM one where you can define new field names in the middle of a CSV file by using `#GROUP_OBJECT_PROFILE#` [learn more](https://www.ibm.com/docs/en/sig-and-i/10.0.2?topic=schedules-example-comma-separated-value-csv-file).
 //!
 //! Because of this, most if not all CSV files are only supported in the ecosystem or app they were created in, and there is no guarantee that Mawu will be able to parse them correctly.
@@ -646,6 +832,18 @@
 //! One last example is the handling of a value of `""` in the middle of a CSV file. This is also part of the rfc4180 standard only implicitly, and sometimes interpreted as an empty string, other times as a missing value.
 //! Mawu will treat it as an empty string and uses it as the default for any empty value itself.
 //!
+//! By default, every empty value is parsed into `MawuValue::None`, the same value a JSON `null` is parsed into, so "explicitly null" and "empty" cannot be told apart.
+//! Should the difference matter, `CsvParseOptions::empty_as_none(false)` keeps empty values as an empty `MawuValue::String`, and `null_tokens` can mark the values that really are null:
+//!
+//! ```rust
+//! use mawu::{csv::{from_str_with, CsvParseOptions}, mawu_value::MawuValue};
+//!
+//! let options = CsvParseOptions::new().empty_as_none(false).null_tokens(vec!["NULL".to_string()]);
+//! let value = from_str_with("a,b\n,NULL\n", &options).unwrap();
+//! assert_eq!(value.as_csv_object().unwrap()[0].get("a").unwrap(), &MawuValue::String("".to_string()));
+//! assert_eq!(value.as_csv_object().unwrap()[0].get("b").unwrap(), &MawuValue::None);
+//! ```
+//!
 //! This library implements missing or not provided values differently depending on if a header is present or not.
 //!
 //! #### With header
@@ -670,6 +868,8 @@
 //!
 //! To get to your data, you will need to iterate over the contents of the `MawuValue` returned. You can do this by calling `as_csv_object()` or `as_csv_array()` on the `MawuValue` returned as appropriate. If you are not sure what the returned value type is, you can check by using `is_csv_object()` or `is_csv_array()`, convenience functions for all types are provided by Mawu.  
 //!
+//! An empty or whitespace-only file holds no data, not even a header. Reading one with `csv_headed` returns an empty `MawuValue::CSVObject`, with `csv_headless` an empty `MawuValue::CSVArray`, instead of an error.
+//!
 //! ### CSV Usage
 //! Reading a CSV file and just printing out the values:
 //!
@@ -700,6 +900,90 @@
 //! }
 //! ```
 //!
+//! CSV that is already in memory, or arrives over the network, does not have to be written to a file first.
+//! `csv::from_str_headed` and `csv::from_str_headless` parse a string, `csv::from_reader_headed`, `csv::from_reader_headless` and `csv::from_reader_with` read anything implementing `std::io::Read`, exactly like the file based functions read a file.
+//! ```rust
+//! use mawu::{csv::{from_reader_headed, from_str_headed}, mawu_value::MawuValue};
+//!
+//! let csv = "name,age\nAda,36\n";
+//! let from_str = from_str_headed(csv).unwrap();
+//! assert_eq!(from_str.as_csv_object().unwrap()[0].get("age").unwrap(), &MawuValue::Uint(36));
+//! assert_eq!(from_reader_headed(csv.as_bytes()).unwrap(), from_str);
+//! ```
+//!
+//! To handle odd files record by record, `csv::split_record(line, delimiter, quote)` splits a single record into its fields, respecting quotes, without the rest of the parser.
+//!
+//! ### Other delimiters
+//! Files separated by something other than a comma can be read with `csv::read_headed_with_delimiter` and `csv::read_headless_with_delimiter`.
+//! For tab-separated files, `csv::read_tsv_headed` and `csv::read_tsv_headless` are provided as a shorthand.
+//! All quoting rules still apply, so a quoted value may contain the delimiter.
+//!
+//! ```rust
+//! use mawu::csv::{read_headed_with_delimiter, read_tsv_headed};
+//!
+//! let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv";
+//! let tsv = read_tsv_headed(path_to_file).unwrap();
+//! assert_eq!(tsv, read_headed_with_delimiter(path_to_file, '\t').unwrap());
+//! ```
+//!
+//! If the delimiter of a file is not known, `csv::read_csv_auto` reads a headed file after detecting the delimiter with `csv::detect_delimiter`.
+//! Out of `,`, `;`, tab and `|`, the one appearing the same number of times outside of quotes in each of the first 10 records is chosen.
+//! Should none or more than one of them qualify, a `CsvParseError::UndetectableDelimiter` is returned instead of a guess.
+//!
+//! If it is not even known if the file has a header, `csv::read_csv_auto_headed` also guesses that with `csv::has_header`, returning a `MawuValue::CSVObject` for headed and a `MawuValue::CSVArray` for headless files.
+//! This is only a heuristic and can be wrong: A header is assumed if the first record is made of unique, non-empty values that are not numbers, and at least one of the next records contains a number.
+//! Files without any numbers are always read as headless, as no data is lost that way.
+//!
+//! ### CSV parse options
+//! `csv::read_with` takes a `csv::CsvParseOptions`, allowing every reading function above to be configured the same way.
+//! `CsvParseOptions::new()` returns the defaults used by `read::csv_headed`, except for `trim_fields`, each option can then be changed by calling the method of the same name:
+//! - `delimiter` - The character separating values. Default: `,`
+//! - `quote` - The character quoting values, escaped inside of a quoted value by doubling it. Default: `"`
+//! - `comment` - If set, records starting with this character are skipped. Default: `None`
+//! - `infer_types` - If `false`, all values are kept as `MawuValue::String`, empty values still follow `empty_as_none`. Default: `true`
+//! - `preserve_leading_zeros` - If `true`, numbers with a leading zero like `007` are kept as `MawuValue::String` instead of being parsed into `MawuValue::Uint(7)`. Default: `false`
+//! - `ragged_rows` - `RaggedRows::Error` returns a `CsvParseError::ExtraValue` for rows longer than the header, `RaggedRows::Fit` cuts them down to size. Rows shorter than the header are always filled with `MawuValue::None`. Default: `RaggedRows::Error`
+//! - `has_headers` - If `false`, a `MawuValue::CSVArray` is returned, just like `read::csv_headless`. Default: `true`
+//! - `empty_as_none` - If `false`, empty values are kept as an empty `MawuValue::String` instead of `MawuValue::None`, so they can be told apart from explicit nulls. Default: `true`
+//! - `null_tokens` - Values like `NULL` or `NA` that are parsed into `MawuValue::None`, just like empty values unless `empty_as_none` is `false`. Default: none
+//! - `null_tokens_ignore_case` - If `true`, `null_tokens` are matched ignoring ASCII case. Default: `false`
+//! - `true_tokens` and `false_tokens` - Values like `yes`, `no`, `1` or `0` that are parsed into `MawuValue::Bool` in addition to `true` and `false`, before any other type is inferred. Default: none
+//! - `bool_tokens_ignore_case` - If `true`, `true`, `false` and the boolean tokens are matched ignoring ASCII case, so `TRUE` is a boolean as well. Default: `false`
+//! - `trim_fields` - If `true`, leading and trailing whitespace is removed from unquoted values before their type is inferred, header names are always trimmed. All reading functions not taking `CsvParseOptions`, like `read::csv_headed`, trim. Default: `false`
+//! - `duplicate_headers` - `DuplicateHeaders::Suffix` keeps every column of a header like `a,b,a` by renaming the repeated names to `a_2`, `a_3` and so on, `DuplicateHeaders::Error` returns a `CsvParseError::DuplicateHeader`. Default: `DuplicateHeaders::Suffix`
+//!
+//! ```rust
+//! use mawu::{csv::{read_with, CsvParseOptions, RaggedRows}, mawu_value::MawuValue};
+//!
+//! let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/options.csv";
+//! let options = CsvParseOptions::new()
+//!     .delimiter(';')
+//!     .quote('\'')
+//!     .comment(Some('#'))
+//!     .infer_types(false)
+//!     .ragged_rows(RaggedRows::Fit);
+//! let csv = read_with(path_to_file, &options).unwrap();
+//! assert_eq!(csv.as_csv_object().unwrap()[2].get("age").unwrap(), &MawuValue::String("007".to_string()));
+//! ```
+//!
+//! ### Tables
+//! Every record of a `MawuValue::CSVObject` is its own `HashMap`, repeating every column name and losing the order of the columns.
+//! `csv::read_table` and `csv::read_table_with` read a headed file into a `csv::CsvTable` instead, storing the header once as `headers` and every record as a `Vec` of its values in column order as `rows`.
+//! For files with many columns this needs about half the memory.
+//! `get(row, column)` looks up a single value, `column_index` finds the index of a column to read many rows directly.
+//! `MawuValue::from(table)` converts it into the `MawuValue::CSVObject` `read::csv_headed` would have returned.
+//!
+//! ```rust
+//! use mawu::{csv::{read_table_with, CsvParseOptions}, mawu_value::MawuValue};
+//!
+//! let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/auto-semicolon.csv";
+//! let table = read_table_with(path_to_file, &CsvParseOptions::new().delimiter(';')).unwrap();
+//! assert_eq!(table.headers, vec!["name", "age", "price"]);
+//! let age = table.column_index("age").unwrap();
+//! assert_eq!(table.rows[0][age], MawuValue::Uint(36));
+//! assert_eq!(table.get(1, "name"), Some(&MawuValue::from("Bob")));
+//! ```
+//!
 //! ## Reading JSON
 //! This library supports JSON files that conform to the rfc8259 and the ECMA-404 standard.
 //! JSON is one of the most used and common file formats used for data interchange. Defined in 2001 by Douglas Crockford, JSON has gone through several editions and has been used in production for over 20 years.
@@ -711,27 +995,39 @@
 //! ### Edge cases
 //!
 //! #### BOM
-//! Mawu does not recognize or produce a `BOM` or similar at the beginning of the file at all and will error out if it encounters one.
+//! Mawu does not produce a `BOM` at the beginning of the file. A UTF-8 `BOM` at the very beginning of a file or string is silently removed before parsing, any other `BOM` will error out.
+//! A `BOM` anywhere else outside of a string returns a `JsonParseError::UnexpectedBom`, inside of a string it is kept like any other character.
 //!
 //! #### Files
-//! If a file should be empty, Mawu will return a `None` value.
+//! If a file should be empty, Mawu will return a `None` value. `JsonParseOptions::strict()` or `JsonParseOptions::new().allow_empty_documents(false)` return a `JsonParseError::UnexpectedEndOfFile` instead.
 //!
 //! #### Objects
 //! In the rfc8259 standard, a JSON object is a set of key-value pairs where the keys should be unique. As this is not a hard requirement however, JSON parsers have handled this in a number of ways.
 //! Mawu will parse JSON objects as a `HashMap<String, MawuValue>` and uses the same behavior for duplicate keys, in that they are replaced with the last value.
 //! Because of the same behavior of `HashMap`, Mawu will return JSON objects not in the same order as the JSON file.
+//! With the `btreemap` feature, objects are parsed into a `BTreeMap<String, MawuValue>` instead, returning and writing them sorted by key.
+//! Every key has to be a string by default. With `JsonParseOptions::lenient()` or `JsonParseOptions::new().allow_unquoted_keys(true)`, keys do not need to be quoted if they are identifiers made of ASCII letters, digits, `_` and `$` not starting with a digit, like in `{a: 1, b_2: 2}`.
 //!
 //! #### Arrays
 //! Ordering of arrays is kept the same as in the JSON file.
 //!
+//! Commas in arrays and objects that are repeated or trail the last element, like in `[1,,2,]` or `{"a": 1,}`, are skipped by default. `JsonParseOptions::strict()` or `JsonParseOptions::new().allow_extra_commas(false)` return a `JsonParseError::InvalidStructuralToken` for them instead.
+//! A missing comma between two elements is always an error.
+//!
 //! #### Numbers
 //! `Infinity` and `NaN` are explicitly not part of the rfc8259 standard, but are implemented in some parsers. Mawu does not support them at all, and any `NaN` or `Infinity` encountered will error. Should you pass in a string into `MawuValue::from` like "1.0e500000" instead of a float with Infinity (or NaN), you will be returned a `MawuValue::None`.
 //! If you want or need to use `NaN` or `Infinity` in your code, you can always just cast them to strings.
 //!
+//! By default, numbers not following the rfc8259 number grammar, like `01` or `1.`, are parsed as well as possible.
+//! Using `json::read_with` with `JsonParseOptions::strict()` or `JsonParseOptions::new().validate_numbers(true)` rejects them with a `JsonParseError::InvalidNumber` instead.
+//!
+//! A leading `+`, like in `+5`, is not allowed by rfc8259 either. It returns a `JsonParseError::InvalidNumber` by default, while `JsonParseOptions::lenient()` or `JsonParseOptions::new().allow_leading_plus(true)` drop it.
+//! The same goes for integers in hexadecimal, octal or binary, like `0xFF`, `0o755` or `-0b101`. They are rejected by default, and parsed into the same `MawuValue::Uint` or `MawuValue::Int` as decimal ones with `JsonParseOptions::lenient()` or `JsonParseOptions::new().allow_radix_prefixes(true)`.
+//!
 //! The rfc8259 doesn't set any limits on the range and precision of numbers, but recommends the implementation of `IEEE 754 binary64`. Because of this recommendation, Mawu supports only 64-bit systems, and all numbers parsed by Mawu are returned in a `_64` type.
-//! Should Mawu encounter a number not representable in 64 bits, it will return an error.
+//! Should Mawu encounter a number not representable in 64 bits, it is parsed into a `MawuValue::None` by default. As this silently drops the number, `JsonParseOptions::strict()` or `JsonParseOptions::new().allow_out_of_range_numbers(false)` return a `JsonParseError::NumberOutOfRange` holding the number instead.
 //! As any implementor of the standards is free to set its own limits on the range and precision of numbers, Mawu chooses to use the same limits and behaviour of the rust standard library `String.parse()` function.
-//! This can be the case for large numbers expressed in exponent notation. For example, `123.456e+350` is not representable in 64-bits (and will return `MawuValue::None`) while `123.456e300` is representable.
+//! This can be the case for large numbers expressed in exponent notation. For example, `123.456e+350` is not representable in 64-bits (and will return `MawuValue::None`, or `JsonParseError::NumberOutOfRange` with `JsonParseOptions::strict()`) while `123.456e300` is representable.
 //! In the case of `123.456e-350`, the parser of the rust standard library will approximate to `0` and Mawu return `0`.
 //!
 //! Some numbers supplied as integers, e.g. `123456789e29`, can be converted into `f64` numbers should they be too large to be represented as `u64` but a `f64` can still hold them.
@@ -741,11 +1037,25 @@
 //! > Any underflow will result in a `0`.
 //!
 //! #### Strings
-//! Mawu accepts only UTF-8 encoded files.
+//! Mawu accepts only UTF-8 encoded files, unless `read::json_with_encoding` is used to read `utf-16` or `latin-1` encoded ones.
 //! Escaped UTF-16 surrogate pairs as permitted by the standards are parsed correctly.
+//! An escaped surrogate that is not part of a pair, like a lone `\uD800` or `\uDC00`, returns a `JsonParseError::UnpairedSurrogate`.
+//! Unescaped control characters (e.g. a literal tab or newline) inside of strings are kept as is.
+//! To reject them as RFC 8259 requires, read the file with `json::read_with` and `JsonParseOptions::strict()` or `JsonParseOptions::new().reject_control_characters(true)`.
+//! Strings and keys enclosed in single quotes, like `{'a': 'b'}`, return a `JsonParseError::InvalidCharacter` by default. `JsonParseOptions::lenient()` or `JsonParseOptions::new().allow_single_quotes(true)` accept them, with `\'` escaping a single quote.
 //!
 //! #### Structure
-//! Mawu accepts any amount of nested structures.
+//! Arrays and objects may be nested up to 512 levels deep, deeper nesting returns a `JsonParseError::MaxDepthExceeded` instead of overflowing the stack.
+//! The limit can be changed with `JsonParseOptions::max_depth` and `json::read_with`.
+//! An empty or whitespace-only file is parsed as `MawuValue::None`, unless `allow_empty_documents` is `false`.
+//! Anything after the top-level value, like the `x` in `{} x`, is ignored by default. `JsonParseOptions::strict()` or `JsonParseOptions::new().allow_trailing_characters(false)` return a `JsonParseError::TrailingCharacters` for anything but whitespace instead.
+//! Any value is allowed at the top level, so `42` is a valid JSON file. `json::read_as_object` and `json::read_as_array` return a `JsonParseError::ExpectedTopLevelObject` or `ExpectedTopLevelArray` for anything but the expected container.
+//!
+//! #### Conformance
+//! The files of the [JSONTestSuite](https://github.com/nst/JSONTestSuite) in `data/json/json-test-data/jsonTestSuite-data/test_parsing` are checked by the `json_test_suite_conformance` test, parsing with `JsonParseOptions::strict()`.
+//! Files prefixed with `y_` must parse, files prefixed with `n_` must error and files prefixed with `i_` may do either, but no file may panic.
+//! To add a case, drop a file following the same naming into the directory.
+//! The test fails on any file that does not follow its prefix, there is no list of exceptions.
 //!
 //! ### JSON Usage
 //! ```rust
@@ -820,6 +1130,69 @@
 //! }
 //! ```
 //!
+//! ### Tokenizing JSON
+//! If you only need a few values out of a large document, you can use the `JsonTokenizer` instead of building the entire `MawuValue`.
+//! It yields one `JsonEvent` at a time, these are `BeginObject`, `EndObject`, `BeginArray`, `EndArray`, `Key(String)` and `Value(MawuValue)`.
+//! Primitive values are parsed by the same lexer as `read::json` uses, so they are identical to the ones you would get out of a fully parsed document.
+//! It follows the same `JsonParseOptions` as `read::json`, so the events always make up exactly the value `read::json` returns, `json::tokenize_with` takes options other than the default ones.
+//! ```rust
+//! use mawu::json::{tokenize, JsonEvent};
+//!
+//! let path_to_file = "data/json/json-test-data/simple-object.json";
+//! for event in tokenize(path_to_file).unwrap() {
+//!     match event.unwrap() {
+//!         JsonEvent::Key(key) => println!("key: {}", key),
+//!         JsonEvent::Value(value) => println!("value: {}", value),
+//!         _ => {}
+//!     }
+//! }
+//! ```
+//!
+//! For huge files made up of one top-level array, `json::array_stream` takes any `std::io::Read` and returns one fully parsed element at a time, so only a single element is ever held in memory. Like the tokenizer, it follows the same `JsonParseOptions` as `read::json`, `json::array_stream_with` takes other ones.
+//! ```rust
+//! use std::fs::File;
+//! use mawu::json::array_stream;
+//!
+//! let file = File::open("data/json/json-test-data/rfc8259-test-data/array.json").unwrap();
+//! for element in array_stream(file) {
+//!     println!("{}", element.unwrap());
+//! }
+//! ```
+//!
+//! ### Borrowed parsing
+//! Every string of a `MawuValue` is its own `String`, which makes up most of the parsing time of documents made mostly of strings.
+//! If the document is already in memory, `json::from_str_borrowed` returns a `MawuValueRef<'a>` instead, whose strings and keys borrow from the input.
+//! Only strings containing escape sequences have to be unescaped into a new `String`, both kinds are held in a `Cow<'a, str>`.
+//! On the 26mb `test_data_160k.json`, where all 1.6 million strings and keys can be borrowed, this is about four times faster than `json::from_str`.
+//! This also holds for repeated object keys, `json::from_str` allocates a new `String` for every key of every object, as a `MawuObject` owns its keys. Parsing 100,000 objects with the same five keys borrowed instead makes 200,000 instead of 1,000,000 allocations and retains 49mb instead of 77mb.
+//! If the value has to outlive the input, `json::from_str_interned` returns an `InternedMawuValue` instead, which allocates every distinct key once as an `Arc<str>` shared by all objects using it, for the same 100,000 objects it retains 48mb as well. It has the same variants and accessors as `MawuValueRef` except `is_borrowed`, and `json::from_str_interned_with` takes `JsonParseOptions`.
+//!
+//! `MawuValueRef` has the variants `Object`, `Array`, `Uint`, `Int`, `Float`, `String`, `Bool` and `None`, and the accessors `get`, `as_object`, `as_array`, `as_str`, `as_uint`, `as_int`, `as_float`, `as_bool`, `is_none` and `is_borrowed`.
+//! Objects are a `Vec` of key-value-pairs in the order of the input, if a key is used more than once `get` returns the last value.
+//! `to_owned()` and `into_owned()` convert a `MawuValueRef` into the same `MawuValue` `json::from_str` would have returned.
+//!
+//! The borrowing parser handles commas like `json::from_str`: extra commas are skipped by default and a missing comma is always an error, see `JsonParseOptions::allow_extra_commas`.
+//! Anything after the top-level value is handled according to `JsonParseOptions::allow_trailing_characters`, like in `json::from_str`: `json::from_str_borrowed` ignores it, with `JsonParseOptions::strict()` anything but whitespace is an error.
+//! `json::from_str_borrowed_with` takes `JsonParseOptions` like `json::read_with`.
+//! ```rust
+//! use mawu::json::{from_str_borrowed, from_str_borrowed_with, JsonParseOptions};
+//!
+//! let input = String::from(r#"{"name": "mawu", "formats": ["json", "csv"]}"#);
+//! let value = from_str_borrowed(&input).unwrap();
+//! assert_eq!(value.get("name").unwrap().as_str(), Some("mawu"));
+//! assert_eq!(value.get("formats").unwrap().as_array().unwrap().len(), 2);
+//! let owned = value.to_owned();
+//! assert_eq!(owned.get("name").unwrap().as_str(), Some("mawu"));
+//!
+//! assert_eq!(from_str_borrowed(r#""a"  "b""#).unwrap().as_str(), Some("a"));
+//! assert!(from_str_borrowed_with(r#""a"  "b""#, &JsonParseOptions::strict()).is_err());
+//! ```
+//!
+//! To read several documents concatenated without a delimiter, like `{}{}`, `json::from_str_partial` parses only the first value with the borrowing parser.
+//! It returns the value as a `MawuValue` together with the byte offset right after it, the next document is parsed from the input sliced at that offset.
+//! `json::from_str_many` does exactly that until the input is used up, returning every document in a `Vec`, no matter if they are separated by whitespace, newlines or nothing at all.
+//! Only a document that is neither an object nor an array has to be followed by whitespace or the `{` or `[` of the next one, so `truefalse` is an error.
+//!
 //! ## Writing
 //! The writing functionality is unified, meaning that there are only two functions:
 //! - `write()`
@@ -835,6 +1208,13 @@
 //! Passing in either `MawuValue::CSVObject` or `MawuValue::CSVArray` will result in an CSV file
 //! being written. Passing any other `MawuValue` will result in a JSON file being written.
 //!
+//! JSON strings and keys are escaped as needed, so reading a written JSON file returns the same `MawuValue`.
+//! The one exception is `-0`, which is read as a `MawuValue::Int(0)` but written and read back as `0`.
+//! Floats are written with the shortest digits that read back as the same float, always with a fraction or an exponent, so `MawuValue::Float(1.0)` is written as `1.0` and `1e21` as `1e21`. A `NaN` or infinite float can not be written as JSON and returns a `JsonWriteError::InvalidNumber`, unless it is serialized with `json::to_string_with` and `NonFiniteFloats::Null` in its `JsonWriteOptions`, writing it as `null` instead.
+//!
+//! To write JSON anywhere else, like a socket or an in-memory buffer, `json::to_writer(writer, &value, spaces)` takes anything implementing `std::io::Write`, and returns a `JsonWriteError::IoError` if writing fails.
+//! `csv::write_csv_headed_to(writer, &value)` does the same for a `MawuValue::CSVObject`, writing one record at a time instead of building the whole file in memory first, for tables too large to hold twice.
+//!
 //! > Please supply the `.json` extension if you want to write a JSON file, or `.csv` if you want to write a CSV file.
 //!
 //! ### Writing data to disk
@@ -920,6 +1300,10 @@
 //! Depending on the type of `MawuValue` passed in, the correct encoding is decided. `CSVObject`
 //! and `CSVArray` are written as CSV files, while any other `MawuValue` is written as JSON.
 //!
+//! `read::csv_headed` trims unquoted values, so a pretty CSV file reads back in without its indentation. With `CsvParseOptions` the indentation is part of every value, unless `trim_fields` is set.
+//!
+//! Every string value of a CSV file is written inside of quotes. To quote only the values that need it, every value, or none at all, serialize with `csv::to_string_with` and a `QuoteStyle` in its `CsvWriteOptions` instead.
+//!
 //! #### Writing pretty examples
 //! ##### JSON
 //! ```rust
@@ -984,19 +1368,108 @@
 //! # // Cleaning up, as `cargo test` actually creates the file on disc during testing
 //! # std::fs::remove_file(path_to_file).unwrap();
 //! ```
+//!
+//! ## JSON utilities
+//! Besides reading and writing, Mawu provides a few tools that only make sense for JSON data.
+//!
+//! ### Canonical JSON
+//! `json::canonicalize` serializes a `MawuValue` following the JSON Canonicalization Scheme (RFC 8785), so equal values always result in the exact same string, ready for hashing or signing.
+//! Keys are sorted, no whitespace is written and floats use the shortest representation that round-trips, in the same format as ECMAScript, e.g. `1e+21` or `0.002`.
+//! ```rust
+//! use mawu::{json::canonicalize, mawu};
+//!
+//! let value = mawu!({"b": 4.50, "a": [true, null]});
+//! assert_eq!(canonicalize(&value).unwrap(), r#"{"a":[true,null],"b":4.5}"#);
+//! ```
+//!
+//! ### JSON Pointer and Patch
+//! Values deep inside a JSON document can be addressed with JSON Pointers (RFC 6901) using `pointer` and `pointer_mut`, and changed with JSON Patches (RFC 6902) using `apply_json_patch`.
+//! `pointer_set` sets the value at a pointer, appending to arrays with `-`, `pointer_remove` removes and returns it, and `ensure_path` returns the value at a path, creating every missing object along the way, for building nested values.
+//! `deep_get` and `deep_get_mut` take the path as a slice of `PathSeg::Key` and `PathSeg::Index` instead, so keys containing `/` or `~` need no escaping and no pointer string has to be built or parsed.
+//! `walk` goes the other way, yielding every value of a document depth first together with its pointer, with object keys in sorted order.
+//! A patch is applied as a whole, if any of its operations fails, the value is left unchanged.
+//! `diff` creates the patch turning one value into another, for a full diff and patch workflow.
+//! ```rust
+//! use mawu::{mawu, mawu_value::MawuValue};
+//!
+//! let mut config = mawu!({"server": {"ports": [80, 443]}});
+//! assert_eq!(config.pointer("/server/ports/1").unwrap(), &MawuValue::Uint(443));
+//!
+//! let patch = mawu!([
+//!     {"op": "test", "path": "/server/ports/0", "value": 80},
+//!     {"op": "replace", "path": "/server/ports/0", "value": 8080},
+//!     {"op": "add", "path": "/server/name", "value": "mawu"}
+//! ]);
+//! config.apply_json_patch(&patch).unwrap();
+//! assert_eq!(config, mawu!({"server": {"ports": [8080, 443], "name": "mawu"}}));
+//! ```
+//!
+//! ### JSON Schema validation
+//! `validate::against_schema` checks a `MawuValue` against a minimal JSON Schema, supporting the keywords `type`, `required`, `properties`, `items`, `enum`, `minimum`, `maximum`, `minLength` and `maxLength`.
+//! Instead of stopping at the first problem, every error is collected as a `ValidationError`, holding the JSON Pointer of the failing value and a `ValidationErrorKind` like `MissingRequired`, `AboveMaximum` or `WrongType`.
+//! ```rust
+//! use mawu::{mawu, validate::against_schema};
+//!
+//! let schema = mawu!({
+//!     "type": "object",
+//!     "required": ["name"],
+//!     "properties": {"age": {"type": "integer", "minimum": 0}}
+//! });
+//! let errors = against_schema(&mawu!({"age": -1}), &schema).unwrap_err();
+//! assert_eq!(errors[0].to_string(), "At document root: Missing required property: name");
+//! assert_eq!(errors[1].to_string(), "At /age: Number is below the minimum of 0");
+//! ```
+//!
+//! `schema::infer`, also available as `validate::infer_schema`, goes the other way, describing an example value with `type`, `required`, `properties` and `items`, as a starting point for a hand written schema.
+//! Every key of an object is required, and the elements of an array are described by a single schema, uniting their types if they differ.
+//! ```rust
+//! use mawu::{mawu, validate::{against_schema, infer_schema}};
+//!
+//! let sample = mawu!({"name": "mawu", "tags": ["json", 1.5]});
+//! let schema = infer_schema(&sample);
+//! assert_eq!(schema.get("properties").unwrap().get("tags").unwrap(), &mawu!({"type": "array", "items": {"type": ["number", "string"]}}));
+//! assert!(against_schema(&sample, &schema).is_ok());
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 /// Contains all the errors that can be returned by Mawu
 pub mod errors;
 /// Contains a wrapper for all data values supported by Mawu
 pub mod mawu_value;
+/// Contains the borrowed counterpart to `MawuValue`
+pub mod mawu_value_ref;
+/// Contains the `Arc` backed, cheaply clonable `SharedMawuValue`
+pub mod shared_mawu_value;
+/// Contains `InternedMawuValue`, sharing repeated object keys
+pub mod interned_mawu_value;
+/// Contains `CsvTable`, the column ordered alternative to `MawuValue::CSVObject`
+pub mod csv_table;
+/// Contains the `mawu!` macro
+mod macros;
+/// Contains the `alloc` types every module needs without the `std` prelude
+mod prelude;
 /// Contains all the lexers for CSV and JSON files
 mod lexers;
 /// Contains all the serializers for CSV and JSON files
 mod serializers;
 /// Contains all utility functions
 mod utils;
+/// Contains the serde glue for typed reading and writing
+#[cfg(feature = "serde")]
+mod serde_support;
+
+// used by the `mawu!` macro, which can't rely on `std` being available in the calling crate
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::{string::ToString, vec};
+    pub use crate::macros::MawuLiteral;
+}
 
 /// Reads CSV and JSON files into `MawuValue`
+#[cfg(feature = "std")]
 pub mod read {
     use std::path::Path;
 
@@ -1007,9 +1480,12 @@ pub mod read {
         utils::file_handling,
     };
 
+    pub use crate::utils::file_handling::Encoding;
+
     /// Reads a headed CSV file and returns a `MawuValue::CSVObject` or an error if the file could not be read or parsed.
     ///
     /// Call `as_csv_object` or `to_csv_object` on the result to get the `Vec<HashMap<String, MawuValue>>`
+    /// Whitespace around unquoted values is removed, like with `CsvParseOptions::trim_fields`.
     ///
     /// # Arguments
     /// * `path` - The path to the CSV file, relative or absolute
@@ -1032,6 +1508,7 @@ pub mod read {
     /// Reads a headless CSV file and returns a `MawuValue::CSVArray` or an error if the file could not be read or parsed.
     ///
     /// Call `as_csv_array` or `to_csv_array` on the result to get the `Vec<Vec<MawuValue>>`
+    /// Whitespace around unquoted values is removed, like with `CsvParseOptions::trim_fields`.
     ///
     /// # Arguments
     /// * `path` - The path to the CSV file, relative or absolute
@@ -1073,10 +1550,1461 @@ pub mod read {
             file_handling::read_file(path)?
         )
     }
+
+    /// Reads a headed CSV file in the given encoding and returns a `MawuValue::CSVObject` or an error if the file could not be read, decoded or parsed.
+    ///
+    /// Works exactly like `csv_headed`, but for files not encoded in UTF-8.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    /// * `encoding` - The `Encoding` of the file
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::read::{csv_headed_with_encoding, Encoding};
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/utf16le-headed.csv";
+    /// let csv_value = csv_headed_with_encoding(path_to_file, Encoding::Utf16Le).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn csv_headed_with_encoding<T: AsRef<Path>>(path: T, encoding: Encoding) -> Result<MawuValue, MawuError> {
+        csv_lexer::headed(
+            file_handling::read_file_with_encoding(path, encoding)?
+        )
+    }
+
+    /// Reads a headless CSV file in the given encoding and returns a `MawuValue::CSVArray` or an error if the file could not be read, decoded or parsed.
+    ///
+    /// Works exactly like `csv_headless`, but for files not encoded in UTF-8.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    /// * `encoding` - The `Encoding` of the file
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::read::{csv_headless_with_encoding, Encoding};
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/latin1-headed.csv";
+    /// let csv_value = csv_headless_with_encoding(path_to_file, Encoding::Latin1).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn csv_headless_with_encoding<T: AsRef<Path>>(path: T, encoding: Encoding) -> Result<MawuValue, MawuError> {
+        csv_lexer::headless(
+            file_handling::read_file_with_encoding(path, encoding)?
+        )
+    }
+
+    /// Reads a JSON file in the given encoding and returns a `MawuValue` or an error if the file could not be read, decoded or parsed.
+    ///
+    /// Works exactly like `json`, but for files not encoded in UTF-8.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the JSON file, relative or absolute
+    /// * `encoding` - The `Encoding` of the file
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::read::{json_with_encoding, Encoding};
+    /// let path_to_file = "data/json/json-test-data/latin1-object.json";
+    /// let json_value = json_with_encoding(path_to_file, Encoding::Latin1).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn json_with_encoding<T: AsRef<Path>>(path: T, encoding: Encoding) -> Result<MawuValue, MawuError> {
+        json_lexer::json_lexer(
+            file_handling::read_file_with_encoding(path, encoding)?
+        )
+    }
+}
+
+/// Lower level access to JSON data
+pub mod json {
+    #[cfg(feature = "std")]
+    use std::{io::{Read, Write}, path::Path};
+
+    use alloc::{string::{String, ToString}, vec::Vec};
+
+    #[cfg(feature = "std")]
+    use crate::{
+        errors::json_error::{JsonError, JsonParseError, JsonWriteError},
+        lexers::json_array_stream::JsonArrayStream,
+        mawu_value::MawuObject,
+        utils::file_handling,
+    };
+    use crate::{
+        errors::MawuError,
+        interned_mawu_value::InternedMawuValue,
+        lexers::{json_borrowed_lexer, json_lexer},
+        mawu_value::MawuValue,
+        mawu_value_ref::MawuValueRef,
+        serializers::{json_canonicalizer, json_serializer},
+    };
+
+    pub use crate::lexers::{
+        json_parse_options::JsonParseOptions,
+        json_tokenizer::{JsonEvent, JsonTokenizer},
+    };
+    pub use crate::serializers::json_write_options::{JsonWriteOptions, NonFiniteFloats};
+
+    /// Parses a JSON string and returns a `MawuValue` or an error if it could not be parsed.
+    ///
+    /// Works exactly like `read::json`, without reading a file first, and is available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::from_str, mawu_value::MawuValue};
+    ///
+    /// let value = from_str(r#"{"key": [1, -1, "value"]}"#).unwrap();
+    /// assert_eq!(value.get("key").unwrap().len(), 3);
+    /// assert_eq!(from_str("null").unwrap(), MawuValue::None);
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str(input: &str) -> Result<MawuValue, MawuError> {
+        json_lexer::json_lexer(input.chars().collect())
+    }
+
+    /// Parses a JSON string according to the given `JsonParseOptions`, see `from_str` and `read_with`.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str_with, JsonParseOptions};
+    ///
+    /// assert!(from_str_with("[01]", &JsonParseOptions::lenient()).is_ok());
+    /// assert!(from_str_with("[01]", &JsonParseOptions::strict()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_with(input: &str, options: &JsonParseOptions) -> Result<MawuValue, MawuError> {
+        json_lexer::json_lexer_with(input.chars().collect(), options)
+    }
+
+    /// Parses the first JSON value of a string, returning it together with the byte offset right after it.
+    ///
+    /// Anything following the value is not looked at, so concatenated documents without a delimiter
+    /// between them can be parsed one after the other by slicing `input` at the returned offset.
+    /// Parsing stops right after the value, whitespace following it is not part of the offset.
+    /// The value is parsed by the borrowing parser of `from_str_borrowed` with the default `JsonParseOptions`, so extra commas are skipped and a missing comma is an error.
+    /// An empty or whitespace-only `input` is parsed as `MawuValue::None`, with an offset of `input.len()`.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse the first value of
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::from_str_partial, mawu};
+    ///
+    /// let input = r#"{"a": 1} [2]"#;
+    /// let (first, offset) = from_str_partial(input).unwrap();
+    /// assert_eq!(first, mawu!({"a": 1}));
+    /// assert_eq!(offset, 8);
+    /// let (second, rest) = from_str_partial(&input[offset..]).unwrap();
+    /// assert_eq!(second, mawu!([2]));
+    /// assert_eq!(offset + rest, input.len());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_partial(input: &str) -> Result<(MawuValue, usize), MawuError> {
+        json_borrowed_lexer::json_borrowed_lexer_partial(input, &JsonParseOptions::default())
+            .map(|(value, offset)| (value.to_owned(), offset))
+    }
+
+    /// Parses every JSON value of a string holding several documents one after the other, returning them in order.
+    ///
+    /// The documents can be separated by any amount of whitespace, including none at all, and can span several lines,
+    /// unlike NDJSON, where every line holds exactly one document.
+    /// A document that is neither an object nor an array has to be followed by whitespace or the `{` or `[` of
+    /// the next one, so `truefalse` is an error instead of `true` and `false`.
+    /// Each document is parsed exactly like `from_str_partial` parses it.
+    /// An empty or whitespace-only `input` returns an empty `Vec`.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON documents to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::from_str_many, mawu};
+    ///
+    /// let values = from_str_many("{\"a\": [\n  1\n]}\n{\"a\": [2]}{}").unwrap();
+    /// assert_eq!(values, vec![mawu!({"a": [1]}), mawu!({"a": [2]}), mawu!({})]);
+    /// assert_eq!(from_str_many("1 2[3]").unwrap(), vec![mawu!(1), mawu!(2), mawu!([3])]);
+    /// assert!(from_str_many("truefalse").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the first error any of the documents has, the documents before it are lost.
+    pub fn from_str_many(input: &str) -> Result<Vec<MawuValue>, MawuError> {
+        let mut values = Vec::new();
+        let mut rest = input.trim_start_matches([' ', '\t', '\n', '\r']);
+        while !rest.is_empty() {
+            let (value, offset) = from_str_partial(rest)?;
+            // only the end of an object or array tells where a document ends on its own
+            let separated = value.is_object() || value.is_array();
+            values.push(value);
+            rest = &rest[offset..];
+            match rest.chars().next() {
+                Some(c) if !separated && !matches!(c, ' ' | '\t' | '\n' | '\r' | '{' | '[') => {
+                    return Err(MawuError::JsonError(crate::errors::json_error::JsonError::ParseError(
+                        crate::errors::json_error::JsonParseError::UnexpectedCharacter(c.to_string()),
+                    )));
+                }
+                _ => rest = rest.trim_start_matches([' ', '\t', '\n', '\r']),
+            }
+        }
+        Ok(values)
+    }
+
+    /// Parses a JSON string like `from_str`, but parses the elements of a top-level array across multiple threads.
+    ///
+    /// The array is split into chunks between its elements, commas inside of strings or nested values never split it.
+    /// The chunks are parsed in parallel with rayon and concatenated in order, so the returned value is the same
+    /// `from_str` would have returned. Any other document, and documents smaller than about 64kb, are parsed in one piece.
+    /// Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str, from_str_parallel};
+    ///
+    /// let input = format!("[{}]", (0..20_000).map(|n| format!(r#"{{"id": {}, "tags": ["a,b", "]"]}}"#, n)).collect::<Vec<_>>().join(","));
+    /// let value = from_str_parallel(&input).unwrap();
+    /// assert_eq!(value.len(), 20_000);
+    /// assert_eq!(value, from_str(&input).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s. If more than one chunk contains an error, any one of them is returned.
+    #[cfg(feature = "rayon")]
+    pub fn from_str_parallel(input: &str) -> Result<MawuValue, MawuError> {
+        json_lexer::json_parallel_lexer_with(input, &JsonParseOptions::default())
+    }
+
+    /// Parses a JSON string according to the given `JsonParseOptions`, see `from_str_parallel` and `from_str_with`.
+    /// Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str_parallel_with, JsonParseOptions};
+    ///
+    /// assert!(from_str_parallel_with("[1, 'two']", &JsonParseOptions::lenient()).is_ok());
+    /// assert!(from_str_parallel_with("[1, 'two']", &JsonParseOptions::strict()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s. If more than one chunk contains an error, any one of them is returned.
+    #[cfg(feature = "rayon")]
+    pub fn from_str_parallel_with(input: &str, options: &JsonParseOptions) -> Result<MawuValue, MawuError> {
+        json_lexer::json_parallel_lexer_with(input, options)
+    }
+
+    /// Parses a JSON string and deserializes it into a `T` with serde, without any other JSON library.
+    /// Requires the `serde` feature.
+    ///
+    /// The string is parsed exactly like `from_str` parses it, the resulting `MawuValue` is then deserialized.
+    /// Unit enum variants are read from strings, all other variants from objects with the variant name as their only key.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     name: String,
+    ///     ports: Vec<u16>,
+    ///     debug: Option<bool>,
+    /// }
+    ///
+    /// let config: Config = mawu::json::from_str_typed(r#"{"name": "mawu", "ports": [80, 443]}"#).unwrap();
+    /// assert_eq!(config.name, "mawu");
+    /// assert_eq!(config.ports, vec![80, 443]);
+    /// assert_eq!(config.debug, None);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonError::DeserializeError` if the JSON does not match `T`, and any `MawuError` parsing can return.
+    #[cfg(feature = "serde")]
+    pub fn from_str_typed<T: serde::de::DeserializeOwned>(input: &str) -> Result<T, MawuError> {
+        crate::serde_support::de::from_mawu_value(from_str(input)?).map_err(|e| {
+            MawuError::JsonError(crate::errors::json_error::JsonError::DeserializeError(e.0))
+        })
+    }
+
+    /// Parses a JSON string into a `MawuValueRef`, borrowing strings and keys from `input` instead of allocating them.
+    ///
+    /// Only strings containing escape sequences allocate, which makes parsing documents with many strings considerably faster.
+    /// Like `from_str`, anything after the top-level value is ignored, see `JsonParseOptions::allow_trailing_characters`.
+    /// Use `from_str_borrowed_with` and `JsonParseOptions::strict()` to make it an error instead.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse, all returned strings borrow from it
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str_borrowed, from_str_borrowed_with, JsonParseOptions};
+    ///
+    /// let input = String::from(r#"{"name": "mawu", "quote": "\"hi\""}"#);
+    /// let value = from_str_borrowed(&input).unwrap();
+    /// assert_eq!(value.get("name").unwrap().as_str(), Some("mawu"));
+    /// assert!(value.get("name").unwrap().is_borrowed());
+    /// assert!(!value.get("quote").unwrap().is_borrowed());
+    ///
+    /// // trailing content is ignored by default
+    /// assert_eq!(from_str_borrowed(r#""a"  "b""#).unwrap().as_str(), Some("a"));
+    /// let strict = JsonParseOptions::strict();
+    /// assert!(from_str_borrowed_with(r#""a"  "b""#, &strict).is_err());
+    /// assert!(from_str_borrowed_with(r#""a"  "b""#, &strict.allow_trailing_characters(true)).is_ok());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_borrowed(input: &str) -> Result<MawuValueRef<'_>, MawuError> {
+        json_borrowed_lexer::json_borrowed_lexer(input, &JsonParseOptions::default())
+    }
+
+    /// Parses a JSON string into a `MawuValueRef` according to the given `JsonParseOptions`, see `from_str_borrowed`.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse, all returned strings borrow from it
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str_borrowed_with, JsonParseOptions};
+    ///
+    /// assert!(from_str_borrowed_with("[01]", &JsonParseOptions::lenient()).is_ok());
+    /// assert!(from_str_borrowed_with("[01]", &JsonParseOptions::strict()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_borrowed_with<'a>(input: &'a str, options: &JsonParseOptions) -> Result<MawuValueRef<'a>, MawuError> {
+        json_borrowed_lexer::json_borrowed_lexer(input, options)
+    }
+
+    /// Parses a JSON string and returns an `InternedMawuValue` or an error if it could not be parsed.
+    ///
+    /// Every distinct object key is allocated only once and shared by all objects using it, which
+    /// saves most of the memory the keys of large arrays of similar objects take up in a `MawuValue`.
+    /// The input is parsed by the borrowing parser of `from_str_borrowed` with the default `JsonParseOptions`,
+    /// the keys are interned afterwards.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use mawu::json::from_str_interned;
+    ///
+    /// let value = from_str_interned(r#"[{"id": 1, "name": "a"}, {"id": 2, "name": "b"}]"#).unwrap();
+    /// let rows = value.as_array().unwrap();
+    /// assert_eq!(rows[1].get("name").unwrap().as_str(), Some("b"));
+    /// assert!(Arc::ptr_eq(&rows[0].as_object().unwrap()[0].0, &rows[1].as_object().unwrap()[0].0));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_interned(input: &str) -> Result<InternedMawuValue, MawuError> {
+        from_str_interned_with(input, &JsonParseOptions::default())
+    }
+
+    /// Parses a JSON string into an `InternedMawuValue` according to the given `JsonParseOptions`, see `from_str_interned`.
+    ///
+    /// # Arguments
+    /// * `input` - The JSON to parse
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{from_str_interned_with, JsonParseOptions};
+    ///
+    /// assert!(from_str_interned_with("{a: 1}", &JsonParseOptions::lenient()).is_ok());
+    /// assert!(from_str_interned_with("{a: 1}", &JsonParseOptions::strict()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_interned_with(input: &str, options: &JsonParseOptions) -> Result<InternedMawuValue, MawuError> {
+        Ok(InternedMawuValue::from(json_borrowed_lexer::json_borrowed_lexer(input, options)?))
+    }
+
+    /// Serializes a `MawuValue` into a JSON string, exactly like `write` and `write_pretty` would write it to a file.
+    ///
+    /// # Arguments
+    /// * `value` - The `MawuValue` to serialize
+    /// * `spaces` - The number of spaces to indent with, `0` writes everything on one line
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::to_string, mawu};
+    ///
+    /// assert_eq!(to_string(&mawu!([1, "a\"b", null]), 0).unwrap(), r#"[1,"a\"b",null]"#);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonWriteError` for CSV values and floats that are `NaN` or infinite
+    pub fn to_string(value: &MawuValue, spaces: u8) -> Result<String, MawuError> {
+        json_serializer::serialize_json(value, spaces)
+    }
+
+    /// Serializes a `MawuValue` into a JSON string according to the given `JsonWriteOptions`.
+    ///
+    /// Works exactly like `to_string` otherwise.
+    ///
+    /// # Arguments
+    /// * `value` - The `MawuValue` to serialize
+    /// * `spaces` - The number of spaces to indent with, `0` writes everything on one line
+    /// * `options` - The `JsonWriteOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::{to_string_with, JsonWriteOptions, NonFiniteFloats}, mawu_value::MawuValue};
+    ///
+    /// let value = MawuValue::from(vec![MawuValue::Float(1.5), MawuValue::Float(f64::NAN)]);
+    /// let options = JsonWriteOptions::new().non_finite_floats(NonFiniteFloats::Null);
+    /// assert_eq!(to_string_with(&value, 0, &options).unwrap(), "[1.5,null]");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonWriteError` for CSV values, and for floats that are `NaN` or infinite unless `NonFiniteFloats::Null` is used
+    pub fn to_string_with(value: &MawuValue, spaces: u8, options: &JsonWriteOptions) -> Result<String, MawuError> {
+        json_serializer::serialize_json_with(value, spaces, options)
+    }
+
+    /// Serializes a `MawuValue` into JSON and writes it to `writer`, exactly like `to_string` would format it.
+    ///
+    /// Nothing is written if the value can not be serialized.
+    ///
+    /// # Arguments
+    /// * `writer` - Anything implementing `std::io::Write`, e.g. a `File` or a `Vec<u8>`
+    /// * `value` - The `MawuValue` to serialize
+    /// * `spaces` - The number of spaces to indent with, `0` writes everything on one line
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::to_writer, mawu};
+    ///
+    /// let mut out = Vec::new();
+    /// to_writer(&mut out, &mawu!({"key": [1, null]}), 0).unwrap();
+    /// assert_eq!(out, br#"{"key":[1,null]}"#);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonWriteError` for CSV values and floats that are `NaN` or infinite, and a `JsonWriteError::IoError` if writing to `writer` fails
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: Write>(mut writer: W, value: &MawuValue, spaces: u8) -> Result<(), MawuError> {
+        let json = json_serializer::serialize_json(value, spaces)?;
+        writer.write_all(json.as_bytes()).map_err(|e| MawuError::JsonError(JsonError::WriteError(JsonWriteError::IoError(e))))
+    }
+
+    /// Serializes any type implementing serde's `Serialize` into a JSON string.
+    ///
+    /// The value is first turned into a `MawuValue` and then written by the same serializer as `to_string`, so the output is formatted exactly the same.
+    /// Unit enum variants are written as strings, all other variants as objects with the variant name as their only key.
+    /// Map keys have to be strings, chars, integers or bools.
+    ///
+    /// # Arguments
+    /// * `value` - The value to serialize
+    /// * `spaces` - The number of spaces to indent with, `0` writes everything on one line
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Point(i32, i32);
+    ///
+    /// assert_eq!(mawu::json::to_string_typed(&vec![Point(1, -2)], 0).unwrap(), "[[1,-2]]");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonError::SerializeError` if `T` can not be represented as JSON, and any `MawuError` `to_string` can return.
+    #[cfg(feature = "serde")]
+    pub fn to_string_typed<T: serde::Serialize + ?Sized>(value: &T, spaces: u8) -> Result<String, MawuError> {
+        let value = crate::serde_support::ser::to_mawu_value(value).map_err(|e| {
+            MawuError::JsonError(crate::errors::json_error::JsonError::SerializeError(e.0))
+        })?;
+        json_serializer::serialize_json(&value, spaces)
+    }
+
+    /// Reads a JSON file and returns a `MawuValue` or an error if the file could not be read or parsed.
+    ///
+    /// Works exactly like `read::json`, but parses according to the given `JsonParseOptions`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the JSON file, relative or absolute
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{read_with, JsonParseOptions};
+    ///
+    /// let path_to_file = "data/json/json-test-data/jsonTestSuite-data/test_parsing/n_string_unescaped_tab.json";
+    /// assert!(read_with(path_to_file, &JsonParseOptions::lenient()).is_ok());
+    /// assert!(read_with(path_to_file, &JsonParseOptions::strict()).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_with<T: AsRef<Path>>(path: T, options: &JsonParseOptions) -> Result<MawuValue, MawuError> {
+        json_lexer::json_lexer_with(file_handling::read_file(path)?, options)
+    }
+
+    /// Reads a JSON file and returns its top-level object, or an error if the file could not be read or parsed, or holds anything but an object.
+    ///
+    /// RFC 8259 allows any value at the top level, `read::json` reads `42` just fine.
+    /// Use this instead of checking `is_object` on the result of `read::json` yourself.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the JSON file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::read_as_object;
+    ///
+    /// let object = read_as_object("data/json/json-test-data/simple-object.json").unwrap();
+    /// assert!(object.contains_key("key2"));
+    /// assert!(read_as_object("data/json/json-test-data/rfc8259-test-data/array.json").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonParseError::ExpectedTopLevelObject` if the file holds any other value, and any `MawuError` `read::json` can return.
+    #[cfg(feature = "std")]
+    pub fn read_as_object<T: AsRef<Path>>(path: T) -> Result<MawuObject, MawuError> {
+        match json_lexer::json_lexer(file_handling::read_file(path)?)? {
+            MawuValue::Object(object) => Ok(object),
+            other => Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::ExpectedTopLevelObject(other.type_name().to_string()),
+            ))),
+        }
+    }
+
+    /// Reads a JSON file and returns the values of its top-level array, or an error if the file could not be read or parsed, or holds anything but an array.
+    ///
+    /// Works exactly like `read_as_object`, but for arrays.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the JSON file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::read_as_array;
+    ///
+    /// let array = read_as_array("data/json/json-test-data/rfc8259-test-data/array.json").unwrap();
+    /// assert_eq!(array.len(), 2);
+    /// assert!(read_as_array("data/json/json-test-data/simple-object.json").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonParseError::ExpectedTopLevelArray` if the file holds any other value, and any `MawuError` `read::json` can return.
+    #[cfg(feature = "std")]
+    pub fn read_as_array<T: AsRef<Path>>(path: T) -> Result<Vec<MawuValue>, MawuError> {
+        match json_lexer::json_lexer(file_handling::read_file(path)?)? {
+            MawuValue::Array(array) => Ok(array),
+            other => Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::ExpectedTopLevelArray(other.type_name().to_string()),
+            ))),
+        }
+    }
+
+    /// Reads a JSON file and returns a `JsonTokenizer` over its contents or an error if the file could not be read.
+    ///
+    /// The tokenizer yields one `JsonEvent` at a time instead of building the whole `MawuValue`,
+    /// so you can skip everything you are not interested in.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the JSON file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{tokenize, JsonEvent};
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let path_to_file = "data/json/json-test-data/simple-object.json";
+    /// let mut tokenizer = tokenize(path_to_file).unwrap();
+    /// let mut key2 = None;
+    /// while let Some(event) = tokenizer.next() {
+    ///     if event.unwrap() == JsonEvent::Key("key2".to_string()) {
+    ///         if let Some(Ok(JsonEvent::Value(value))) = tokenizer.next() {
+    ///             key2 = Some(value);
+    ///         }
+    ///     }
+    /// }
+    /// assert_eq!(key2, Some(MawuValue::Uint(1)));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn tokenize<T: AsRef<Path>>(path: T) -> Result<JsonTokenizer, MawuError> {
+        Ok(JsonTokenizer::new(file_handling::read_file(path)?))
+    }
+
+    /// Reads a JSON file and returns a `JsonTokenizer` over its contents or an error if the file could not be read.
+    ///
+    /// Works exactly like `json::tokenize`, but tokenizes according to the given `JsonParseOptions`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the JSON file, relative or absolute
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{tokenize, tokenize_with, JsonParseOptions};
+    ///
+    /// let path_to_file = "data/json/json-test-data/jsonTestSuite-data/test_parsing/n_array_extra_comma.json";
+    /// assert!(tokenize(path_to_file).unwrap().all(|event| event.is_ok()));
+    /// assert!(tokenize_with(path_to_file, &JsonParseOptions::strict()).unwrap().any(|event| event.is_err()));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn tokenize_with<T: AsRef<Path>>(path: T, options: &JsonParseOptions) -> Result<JsonTokenizer, MawuError> {
+        Ok(JsonTokenizer::new_with(file_handling::read_file(path)?, options))
+    }
+
+    /// Lazily parses the elements of a top-level JSON array, returning one fully parsed element per call to `next()`.
+    ///
+    /// Only one element at a time is held in memory, making this the way to go for huge
+    /// `[ {...}, {...}, ... ]` files. The reader should supply UTF-8 encoded data.
+    ///
+    /// An element that fails to parse returns its error and the iterator moves on to the next
+    /// element. Errors in the array itself, e.g. a missing `[` or the data ending before the
+    /// closing `]`, are returned once and end the iteration.
+    /// Everything is parsed according to the same `JsonParseOptions` as `read::json` uses, so the
+    /// elements of an array `read::json` accepts are exactly the ones it returns, see `json::array_stream_with`
+    /// for other options.
+    ///
+    /// # Arguments
+    /// * `reader` - Anything implementing `std::io::Read`, e.g. a `File`
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::fs::File;
+    /// use mawu::json::array_stream;
+    ///
+    /// let file = File::open("data/json/json-test-data/rfc8259-test-data/array.json").unwrap();
+    /// for element in array_stream(file) {
+    ///     let element = element.unwrap();
+    ///     assert!(element.has_key("City"));
+    /// }
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn array_stream<R: Read>(reader: R) -> impl Iterator<Item = Result<MawuValue, MawuError>> {
+        JsonArrayStream::new(reader)
+    }
+
+    /// Lazily parses the elements of a top-level JSON array, returning one fully parsed element per call to `next()`.
+    ///
+    /// Works exactly like `json::array_stream`, but parses according to the given `JsonParseOptions`.
+    ///
+    /// # Arguments
+    /// * `reader` - Anything implementing `std::io::Read`, e.g. a `File`
+    /// * `options` - The `JsonParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::{array_stream, array_stream_with, JsonParseOptions};
+    ///
+    /// assert_eq!(array_stream("[1, 2,]".as_bytes()).count(), 2);
+    /// let elements = array_stream_with("[1, 2,]".as_bytes(), &JsonParseOptions::strict()).collect::<Vec<_>>();
+    /// assert!(elements[2].is_err());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn array_stream_with<R: Read>(reader: R, options: &JsonParseOptions) -> impl Iterator<Item = Result<MawuValue, MawuError>> {
+        JsonArrayStream::new_with(reader, options)
+    }
+
+    /// Serializes a `MawuValue` into its canonical form, as defined by the JSON Canonicalization Scheme (RFC 8785).
+    ///
+    /// Equal values always produce the exact same string, making it the right input for hashing or signing.
+    /// Object keys are sorted by their UTF-16 code units and no whitespace is written.
+    /// Floats use the shortest representation that parses back to the same `f64`, written in
+    /// exponential notation (e.g. `1e+21`, `1e-7`) outside of `1e-6 <= |float| < 1e21` and without a
+    /// fraction if they hold an integer, exactly like ECMAScript's `Number.prototype.toString`.
+    /// `-0.0` is written as `0`.
+    /// Unlike RFC 8785, `Uint` and `Int` are always written as the exact integer, even outside of the
+    /// range a `f64` can hold without loss.
+    ///
+    /// # Arguments
+    /// * `value` - The `MawuValue` to canonicalize
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::canonicalize, mawu};
+    ///
+    /// let value = mawu!({"b": [1.50, 1e30], "a": {"z": null, "c": "\u{20ac}"}});
+    /// assert_eq!(canonicalize(&value).unwrap(), "{\"a\":{\"c\":\"\u{20ac}\",\"z\":null},\"b\":[1.5,1e+30]}");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `JsonWriteError` for CSV values and floats that are `NaN` or infinite.
+    pub fn canonicalize(value: &MawuValue) -> Result<String, MawuError> {
+        json_canonicalizer::canonicalize_json(value)
+    }
+}
+
+/// Lower level access to CSV data
+pub mod csv {
+    #[cfg(feature = "std")]
+    use std::{io::{Read, Write}, path::Path};
+
+    use alloc::{string::String, vec::Vec};
+
+    #[cfg(feature = "std")]
+    use crate::{
+        errors::csv_error::{CsvError, CsvParseError, CsvWriteError},
+        utils::file_handling,
+    };
+    use crate::{
+        errors::MawuError,
+        lexers::{csv_lexer, csv_sniffer},
+        mawu_value::MawuValue,
+        serializers::csv_serializer,
+    };
+
+    pub use crate::csv_table::CsvTable;
+    pub use crate::lexers::csv_parse_options::{CsvParseOptions, DuplicateHeaders, RaggedRows};
+    pub use crate::serializers::csv_write_options::{CsvWriteOptions, QuoteStyle};
+
+    /// Parses a CSV string according to the given `CsvParseOptions`, see `read_with`.
+    /// Available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The CSV to parse
+    /// * `options` - The `CsvParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{from_str_with, CsvParseOptions}, mawu_value::MawuValue};
+    ///
+    /// let csv_value = from_str_with("name;age\nAda;36\n", &CsvParseOptions::new().delimiter(';')).unwrap();
+    /// assert_eq!(csv_value.as_csv_object().unwrap()[0].get("age").unwrap(), &MawuValue::Uint(36));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_with(input: &str, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+        csv_lexer::csv_lexer_with(input.chars().collect(), options)
+    }
+
+    /// Splits a single CSV record into its fields, without inferring their types.
+    ///
+    /// Fields are separated by `delimiter`, and can be quoted with `quote` to contain the delimiter, newlines
+    /// or, doubled, the quote itself. Whitespace around a quoted field is skipped, unquoted fields are kept as they are.
+    /// One trailing line ending is ignored, and an empty `line` has no fields. Available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `line` - The record to split
+    /// * `delimiter` - The character separating the fields
+    /// * `quote` - The character quoting fields
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::split_record;
+    ///
+    /// let fields = split_record("1,\"Doe, Jane\",\"say \"\"hi\"\"\",\r\n", ',', '"').unwrap();
+    /// assert_eq!(fields, vec!["1", "Doe, Jane", "say \"hi\"", ""]);
+    /// assert!(split_record("1,\"open", ',', '"').is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `CsvParseError::UnterminatedQuote` if a quoted field is never closed, a `CsvParseError::UnescapedCharacter`
+    /// for anything but whitespace between the closing quote and the next delimiter, and a `CsvParseError::UnexpectedNewline`
+    /// for a newline outside of quotes.
+    pub fn split_record(line: &str, delimiter: char, quote: char) -> Result<Vec<String>, MawuError> {
+        csv_lexer::split_record(line, delimiter, quote)
+    }
+
+    /// Parses a headed CSV string and returns a `MawuValue::CSVObject` or an error if it could not be parsed.
+    ///
+    /// Works exactly like `read::csv_headed`, without reading a file first, and is available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The CSV to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::from_str_headed, mawu_value::MawuValue};
+    ///
+    /// let csv_value = from_str_headed("name,age\nAda,36\n").unwrap();
+    /// assert_eq!(csv_value.as_csv_object().unwrap()[0].get("name").unwrap(), &MawuValue::from("Ada"));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_headed(input: &str) -> Result<MawuValue, MawuError> {
+        from_str_with(input, &CsvParseOptions::default().trim_fields(true))
+    }
+
+    /// Parses a headless CSV string and returns a `MawuValue::CSVArray` or an error if it could not be parsed.
+    ///
+    /// Works exactly like `read::csv_headless`, without reading a file first, and is available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The CSV to parse
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::from_str_headless, mawu_value::MawuValue};
+    ///
+    /// let csv_value = from_str_headless("Ada,36\nGrace,45\n").unwrap();
+    /// assert_eq!(csv_value.as_csv_array().unwrap()[1][0], MawuValue::from("Grace"));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_headless(input: &str) -> Result<MawuValue, MawuError> {
+        from_str_with(input, &CsvParseOptions::new().has_headers(false).trim_fields(true))
+    }
+
+    /// Reads CSV from a reader, like a `TcpStream` or a `File`, and parses it according to the given `CsvParseOptions`.
+    ///
+    /// Everything the reader returns is read first, then decoded and parsed exactly like `read_with` reads a file,
+    /// so the input has to be valid UTF-8, a leading BOM is stripped and with the `flate2` feature gzip compressed input is decompressed.
+    ///
+    /// # Arguments
+    /// * `reader` - Anything implementing `std::io::Read`
+    /// * `options` - The `CsvParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{from_reader_with, CsvParseOptions}, mawu_value::MawuValue};
+    ///
+    /// let csv_value = from_reader_with("name;age\nAda;36\n".as_bytes(), &CsvParseOptions::new().delimiter(';')).unwrap();
+    /// assert_eq!(csv_value.as_csv_object().unwrap()[0].get("age").unwrap(), &MawuValue::Uint(36));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `MawuError::IoError` if the reader fails or does not return valid UTF-8, and any other `MawuError` parsing can return.
+    #[cfg(feature = "std")]
+    pub fn from_reader_with<R: Read>(reader: R, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+        csv_lexer::csv_lexer_with(file_handling::read_reader(reader)?, options)
+    }
+
+    /// Reads headed CSV from a reader and returns a `MawuValue::CSVObject`, see `from_reader_with` and `read::csv_headed`.
+    ///
+    /// # Arguments
+    /// * `reader` - Anything implementing `std::io::Read`
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::fs::File;
+    /// use mawu::{csv::from_reader_headed, read::csv_headed};
+    ///
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/all-types.csv";
+    /// let csv_value = from_reader_headed(File::open(path_to_file).unwrap()).unwrap();
+    /// assert_eq!(csv_value, csv_headed(path_to_file).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `MawuError::IoError` if the reader fails or does not return valid UTF-8, and any other `MawuError` parsing can return.
+    #[cfg(feature = "std")]
+    pub fn from_reader_headed<R: Read>(reader: R) -> Result<MawuValue, MawuError> {
+        from_reader_with(reader, &CsvParseOptions::default().trim_fields(true))
+    }
+
+    /// Reads headless CSV from a reader and returns a `MawuValue::CSVArray`, see `from_reader_with` and `read::csv_headless`.
+    ///
+    /// # Arguments
+    /// * `reader` - Anything implementing `std::io::Read`
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::from_reader_headless, mawu_value::MawuValue};
+    ///
+    /// let csv_value = from_reader_headless("1,2\n3,4\n".as_bytes()).unwrap();
+    /// assert_eq!(csv_value.as_csv_array().unwrap()[1][1], MawuValue::Uint(4));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `MawuError::IoError` if the reader fails or does not return valid UTF-8, and any other `MawuError` parsing can return.
+    #[cfg(feature = "std")]
+    pub fn from_reader_headless<R: Read>(reader: R) -> Result<MawuValue, MawuError> {
+        from_reader_with(reader, &CsvParseOptions::new().has_headers(false).trim_fields(true))
+    }
+
+    /// Reads a CSV file and returns a `MawuValue::CSVObject` or `MawuValue::CSVArray` or an error if the file could not be read or parsed.
+    ///
+    /// Parses according to the given `CsvParseOptions`, a `MawuValue::CSVObject` is returned if
+    /// `CsvParseOptions::has_headers` is `true` (the default), a `MawuValue::CSVArray` otherwise.
+    /// `read::csv_headed` and `read::csv_headless` are the same as calling this function with
+    /// `CsvParseOptions::new().trim_fields(true)` and `CsvParseOptions::new().has_headers(false).trim_fields(true)`.
+    /// With `CsvParseOptions::default()` unquoted values are not trimmed, so ` 42 ` stays a `MawuValue::String`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    /// * `options` - The `CsvParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{read_with, CsvParseOptions}, mawu_value::MawuValue};
+    ///
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/auto-semicolon.csv";
+    /// let options = CsvParseOptions::new().delimiter(';').infer_types(false);
+    /// let csv_value = read_with(path_to_file, &options).unwrap();
+    /// assert_eq!(csv_value.as_csv_object().unwrap()[0].get("age").unwrap(), &MawuValue::String("36".to_string()));
+    ///
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv";
+    /// assert_eq!(
+    ///     read_with(path_to_file, &CsvParseOptions::new().trim_fields(true)).unwrap(),
+    ///     mawu::read::csv_headed(path_to_file).unwrap()
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_with<T: AsRef<Path>>(path: T, options: &CsvParseOptions) -> Result<MawuValue, MawuError> {
+        csv_lexer::csv_lexer_with(file_handling::read_file(path)?, options)
+    }
+
+    /// Parses a headed CSV string into a `CsvTable` according to the given `CsvParseOptions`, see `read_table_with`.
+    /// Available without the `std` feature.
+    ///
+    /// # Arguments
+    /// * `input` - The CSV to parse
+    /// * `options` - The `CsvParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{from_str_table_with, CsvParseOptions}, mawu_value::MawuValue};
+    ///
+    /// let table = from_str_table_with("name;age\nAda;36\n", &CsvParseOptions::new().delimiter(';')).unwrap();
+    /// assert_eq!(table.get(0, "name").unwrap(), &MawuValue::from("Ada"));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    pub fn from_str_table_with(input: &str, options: &CsvParseOptions) -> Result<CsvTable, MawuError> {
+        csv_lexer::table_with(input.chars().collect(), options)
+    }
+
+    /// Reads a headed CSV file and returns a `CsvTable` or an error if the file could not be read or parsed.
+    ///
+    /// Reads the same file `read::csv_headed` does, but keeps the header once and every record as
+    /// a `Vec` in column order, instead of building a `HashMap` for every record.
+    /// `MawuValue::from` turns the table into the `MawuValue::CSVObject` `read::csv_headed` would have returned.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::read_table, mawu_value::MawuValue, read::csv_headed};
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/all-types.csv";
+    /// let table = read_table(path_to_file).unwrap();
+    /// assert_eq!(MawuValue::from(table), csv_headed(path_to_file).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_table<T: AsRef<Path>>(path: T) -> Result<CsvTable, MawuError> {
+        read_table_with(path, &CsvParseOptions::default().trim_fields(true))
+    }
+
+    /// Reads a headed CSV file like `read_table`, but parses according to the given `CsvParseOptions`.
+    ///
+    /// The first record is always used as the header, `CsvParseOptions::has_headers` is ignored.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    /// * `options` - The `CsvParseOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{read_table_with, CsvParseOptions}, mawu_value::MawuValue};
+    ///
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/auto-semicolon.csv";
+    /// let table = read_table_with(path_to_file, &CsvParseOptions::new().delimiter(';')).unwrap();
+    /// assert_eq!(table.get(0, "age").unwrap(), &MawuValue::Uint(36));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_table_with<T: AsRef<Path>>(path: T, options: &CsvParseOptions) -> Result<CsvTable, MawuError> {
+        csv_lexer::table_with(file_handling::read_file(path)?, options)
+    }
+
+    /// Reads a headed CSV file separated by the given delimiter and returns a `MawuValue::CSVObject` or an error if the file could not be read or parsed.
+    ///
+    /// Works exactly like `read::csv_headed`, with `delimiter` taking the place of the comma.
+    /// Quoting rules stay the same, so quoted values may contain the delimiter.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    /// * `delimiter` - The character separating the values, must not be `"` or a newline
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::read_headed_with_delimiter;
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv";
+    /// let csv_value = read_headed_with_delimiter(path_to_file, '\t').unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_headed_with_delimiter<T: AsRef<Path>>(path: T, delimiter: char) -> Result<MawuValue, MawuError> {
+        read_with(path, &CsvParseOptions::new().delimiter(delimiter).trim_fields(true))
+    }
+
+    /// Reads a headless CSV file separated by the given delimiter and returns a `MawuValue::CSVArray` or an error if the file could not be read or parsed.
+    ///
+    /// Works exactly like `read::csv_headless`, with `delimiter` taking the place of the comma.
+    /// Quoting rules stay the same, so quoted values may contain the delimiter.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    /// * `delimiter` - The character separating the values, must not be `"` or a newline
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::read_headless_with_delimiter;
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv";
+    /// let csv_value = read_headless_with_delimiter(path_to_file, '\t').unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_headless_with_delimiter<T: AsRef<Path>>(path: T, delimiter: char) -> Result<MawuValue, MawuError> {
+        read_with(path, &CsvParseOptions::new().delimiter(delimiter).has_headers(false).trim_fields(true))
+    }
+
+    /// Reads a headed CSV file like `read::csv_headed`, but parses its records across multiple threads.
+    ///
+    /// The file is split into chunks at record boundaries, newlines inside of quoted values never split a record.
+    /// The chunks are parsed in parallel with rayon and concatenated in order, so the returned value is the same
+    /// `read::csv_headed` would have returned. Files smaller than about 64k characters are parsed in one piece.
+    /// Requires the `rayon` feature.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::read_csv_headed_parallel, read::csv_headed};
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/all-types.csv";
+    /// assert_eq!(read_csv_headed_parallel(path_to_file).unwrap(), csv_headed(path_to_file).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s. If more than one chunk contains an error, any one of them is returned.
+    #[cfg(feature = "rayon")]
+    pub fn read_csv_headed_parallel<T: AsRef<Path>>(path: T) -> Result<MawuValue, MawuError> {
+        csv_lexer::headed_parallel_with(file_handling::read_file(path)?, &CsvParseOptions::default().trim_fields(true))
+    }
+
+    /// Reads a headed TSV (tab-separated values) file and returns a `MawuValue::CSVObject` or an error if the file could not be read or parsed.
+    ///
+    /// Shorthand for `read_headed_with_delimiter(path, '\t')`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the TSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::read_tsv_headed, mawu_value::MawuValue};
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv";
+    /// let tsv_value = read_tsv_headed(path_to_file).unwrap();
+    /// assert_eq!(tsv_value.as_csv_object().unwrap()[0].get("note").unwrap(), &MawuValue::from("likes\ttabs"));
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_tsv_headed<T: AsRef<Path>>(path: T) -> Result<MawuValue, MawuError> {
+        read_headed_with_delimiter(path, '\t')
+    }
+
+    /// Reads a headless TSV (tab-separated values) file and returns a `MawuValue::CSVArray` or an error if the file could not be read or parsed.
+    ///
+    /// Shorthand for `read_headless_with_delimiter(path, '\t')`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the TSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::read_tsv_headless;
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv";
+    /// let tsv_value = read_tsv_headless(path_to_file).unwrap();
+    /// assert_eq!(tsv_value.as_csv_array().unwrap().len(), 4);
+    /// ```
+    ///
+    /// # Errors
+    /// Only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_tsv_headless<T: AsRef<Path>>(path: T) -> Result<MawuValue, MawuError> {
+        read_headless_with_delimiter(path, '\t')
+    }
+
+    /// Detects the delimiter of a CSV sample.
+    ///
+    /// Looks at the first 10 records of the sample, ignoring anything in quotes, and returns the
+    /// one of `,`, `;`, `\t` and `|` appearing the same number of times in every record.
+    /// If none or more than one of them do, `None` is returned instead of a guess.
+    ///
+    /// # Arguments
+    /// * `sample` - The start of a CSV file
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::detect_delimiter;
+    ///
+    /// assert_eq!(detect_delimiter("name;age\nAda;36\n"), Some(';'));
+    /// assert_eq!(detect_delimiter("name,age;id\nAda,36;1\n"), None);
+    /// ```
+    pub fn detect_delimiter(sample: &str) -> Option<char> {
+        csv_sniffer::detect_delimiter(sample)
+    }
+
+    /// Guesses if a CSV sample starts with a header.
+    ///
+    /// This is a heuristic and can be wrong!
+    /// The first record is taken for a header if all of its values are unique, not empty and not
+    /// numbers, while at least one of the next 9 records contains a number.
+    /// Files without any numbers are therefore always reported as headless.
+    ///
+    /// # Arguments
+    /// * `sample` - The start of a CSV file
+    /// * `delimiter` - The character separating the values
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::has_header;
+    ///
+    /// assert!(has_header("name,age\nAda,36\n", ','));
+    /// assert!(!has_header("1,2\n3,4\n", ','));
+    /// ```
+    pub fn has_header(sample: &str, delimiter: char) -> bool {
+        csv_sniffer::has_header(sample, delimiter)
+    }
+
+    /// Reads a headed CSV file with an unknown delimiter and returns a `MawuValue::CSVObject` or an error if the file could not be read or parsed.
+    ///
+    /// The delimiter is detected with `detect_delimiter` on the first 64 KiB of the file.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::{read_csv_auto, read_tsv_headed};
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv";
+    /// let csv_value = read_csv_auto(path_to_file).unwrap();
+    /// assert_eq!(csv_value, read_tsv_headed(path_to_file).unwrap());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `CsvParseError::UndetectableDelimiter` if the delimiter is ambiguous or could not be found,
+    /// otherwise only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_csv_auto<T: AsRef<Path>>(path: T) -> Result<MawuValue, MawuError> {
+        let contents = file_handling::read_file(path)?;
+        let sample = contents.iter().take(csv_sniffer::SAMPLE_SIZE).collect::<String>();
+        match csv_sniffer::detect_delimiter(&sample) {
+            Some(delimiter) => csv_lexer::headed_with(contents, &CsvParseOptions::new().delimiter(delimiter).trim_fields(true)),
+            None => Err(MawuError::CsvError(CsvError::ParseError(
+                CsvParseError::UndetectableDelimiter,
+            ))),
+        }
+    }
+
+    /// Reads a CSV file with an unknown delimiter, that may or may not have a header.
+    /// Returns a `MawuValue::CSVObject` if a header was detected, a `MawuValue::CSVArray` if not, or an error if the file could not be read or parsed.
+    ///
+    /// The delimiter is detected with `detect_delimiter`, the header with `has_header`, both on the first 64 KiB of the file.
+    /// Detecting the header is a heuristic and can be wrong, check the returned type with `is_csv_object` or `is_csv_array`.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::csv::read_csv_auto_headed;
+    /// let path_to_file = "data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv";
+    /// let csv_value = read_csv_auto_headed(path_to_file).unwrap();
+    /// assert!(csv_value.is_csv_object());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `CsvParseError::UndetectableDelimiter` if the delimiter is ambiguous or could not be found,
+    /// otherwise only returns `MawuError`'s
+    #[cfg(feature = "std")]
+    pub fn read_csv_auto_headed<T: AsRef<Path>>(path: T) -> Result<MawuValue, MawuError> {
+        let contents = file_handling::read_file(path)?;
+        let sample = contents.iter().take(csv_sniffer::SAMPLE_SIZE).collect::<String>();
+        match csv_sniffer::detect_delimiter(&sample) {
+            Some(delimiter) => {
+                let options = CsvParseOptions::new()
+                    .delimiter(delimiter)
+                    .has_headers(csv_sniffer::has_header(&sample, delimiter))
+                    .trim_fields(true);
+                csv_lexer::csv_lexer_with(contents, &options)
+            }
+            None => Err(MawuError::CsvError(CsvError::ParseError(
+                CsvParseError::UndetectableDelimiter,
+            ))),
+        }
+    }
+
+    /// Serializes a `MawuValue::CSVObject` or `MawuValue::CSVArray` into a CSV string, exactly like `write` and `write_pretty` would write it to a file.
+    ///
+    /// # Arguments
+    /// * `value` - The `MawuValue` to serialize
+    /// * `spaces` - The number of spaces to pad every value with
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::to_string, mawu_value::MawuValue};
+    ///
+    /// let csv_value = MawuValue::CSVArray(vec![vec![MawuValue::from("a"), MawuValue::from(1)]]);
+    /// assert_eq!(to_string(&csv_value, 0).unwrap().trim_end(), "\"a\",1");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `CsvWriteError::NotCsvType` for values other than `MawuValue::CSVObject` and `MawuValue::CSVArray`,
+    /// and a `CsvWriteError::InconsistentColumns` if the rows of a `MawuValue::CSVObject` do not all have the same columns.
+    pub fn to_string(value: &MawuValue, spaces: u8) -> Result<String, MawuError> {
+        to_string_with(value, spaces, &CsvWriteOptions::default())
+    }
+
+    /// Serializes a `MawuValue::CSVObject` or `MawuValue::CSVArray` into a CSV string according to the given `CsvWriteOptions`.
+    ///
+    /// Works exactly like `to_string` otherwise.
+    ///
+    /// # Arguments
+    /// * `value` - The `MawuValue` to serialize
+    /// * `spaces` - The number of spaces to pad every value with
+    /// * `options` - The `CsvWriteOptions` to use
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{to_string_with, CsvWriteOptions, QuoteStyle}, mawu_value::MawuValue};
+    ///
+    /// let csv_value = MawuValue::CSVArray(vec![vec![MawuValue::from("a"), MawuValue::from("b,c"), MawuValue::from(1)]]);
+    /// let minimal = CsvWriteOptions::new().quote_style(QuoteStyle::Minimal);
+    /// assert_eq!(to_string_with(&csv_value, 0, &minimal).unwrap().trim_end(), "a,\"b,c\",1");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the same `CsvWriteError`s as `to_string`,
+    /// and a `CsvWriteError::NeedsQuoting` if `QuoteStyle::Never` is used for a value that would need quotes.
+    pub fn to_string_with(value: &MawuValue, spaces: u8, options: &CsvWriteOptions) -> Result<String, MawuError> {
+        if value.is_csv_object() {
+            csv_serializer::serialize_csv_headed(value.clone(), spaces, options)
+        } else {
+            csv_serializer::serialize_csv_unheaded(value.clone(), spaces, options)
+        }
+    }
+
+    /// Writes a `MawuValue::CSVObject` to `writer` one record at a time, starting with the header.
+    ///
+    /// Unlike `to_string`, the whole file is never built in memory, so huge tables can be written with little memory.
+    /// The records are formatted like `to_string` formats them, each ending with a newline, and the columns are in the order
+    /// of the first row. Every record is a separate write, wrap unbuffered writers like a `File` in a `std::io::BufWriter`.
+    ///
+    /// # Arguments
+    /// * `writer` - Anything implementing `std::io::Write`, e.g. a `BufWriter<File>` or a `Vec<u8>`
+    /// * `value` - The `MawuValue::CSVObject` to write
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{csv::{from_str_headed, write_csv_headed_to}, mawu_value::MawuValue};
+    ///
+    /// let csv_value = from_str_headed("name\nAda\nGrace\n").unwrap();
+    /// let mut out = Vec::new();
+    /// write_csv_headed_to(&mut out, &csv_value).unwrap();
+    /// assert_eq!(out, b"name\n\"Ada\"\n\"Grace\"\n");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `CsvWriteError::NotCsvType` for values other than `MawuValue::CSVObject`, a `CsvWriteError::InconsistentColumns` if the rows
+    /// do not all have the same columns and a `CsvWriteError::IoError` if writing to `writer` fails.
+    /// The records before an error have already been written.
+    #[cfg(feature = "std")]
+    pub fn write_csv_headed_to<W: Write>(writer: W, value: &MawuValue) -> Result<(), MawuError> {
+        if !value.is_csv_object() {
+            return Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(value.type_name().to_string()))));
+        }
+        csv_serializer::serialize_csv_to_writer(writer, value, ',', true)
+    }
+
+    /// Reads a headed CSV file and deserializes every row into a `T` with serde, using the header names as field names.
+    /// Requires the `serde` and `std` features.
+    ///
+    /// Values are read as strings and only parsed into numbers or bools if the field of `T` is one, so a `String` field
+    /// keeps a zip code like `02139` as it is. Empty values deserialize into `None` for `Option` fields.
+    ///
+    /// # Arguments
+    /// * `path` - The path to the CSV file, relative or absolute
+    ///
+    /// # Example
+    /// ```rust
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u8,
+    ///     city: String,
+    /// }
+    ///
+    /// let people: Vec<Person> = mawu::csv::from_path_typed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// assert_eq!(people.len(), 2);
+    /// assert_eq!(people[0].name, "Ada");
+    /// assert_eq!(people[1].age, 42);
+    /// assert_eq!(people[0].city, "London, UK");
+    /// ```
+    ///
+    /// # Errors
+    /// Returns a `CsvError::DeserializeError` holding the row number if a row does not match `T`, and any `MawuError` reading or parsing can return.
+    #[cfg(all(feature = "std", feature = "serde"))]
+    pub fn from_path_typed<T: serde::de::DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<Vec<T>, MawuError> {
+        // types come from `T`, inferring them first would turn a zip code of `01234` into `1234`
+        let rows = read_with(path, &CsvParseOptions::default().infer_types(false))?.to_csv_object().unwrap_or_default();
+        rows.into_iter()
+            .enumerate()
+            .map(|(index, row)| {
+                crate::serde_support::de::from_csv_row(row).map_err(|e| {
+                    MawuError::CsvError(CsvError::DeserializeError(alloc::format!("row {}: {}", index + 1, e)))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Validation of `MawuValue`'s against JSON Schemas
+pub mod validate {
+    use alloc::vec::Vec;
+
+    use crate::{mawu_value::MawuValue, utils::json_schema};
+
+    pub use crate::errors::json_error::{ValidationError, ValidationErrorKind};
+
+    /// Validates a `MawuValue` against a minimal JSON Schema, returning every error found instead of only the first.
+    ///
+    /// Supported keywords are `type`, `required`, `properties`, `items`, `enum`, `minimum`, `maximum`, `minLength` and `maxLength`.
+    /// All other keywords are ignored. Numbers are compared by value, so `1`, `1u64` and `1.0` are all equal and integers.
+    ///
+    /// # Arguments
+    /// * `value` - The `MawuValue` to validate
+    /// * `schema` - The JSON Schema, a `MawuValue::Object`
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{mawu, validate::{against_schema, ValidationErrorKind}};
+    ///
+    /// let schema = mawu!({
+    ///     "type": "object",
+    ///     "required": ["name"],
+    ///     "properties": {"port": {"type": "integer", "minimum": 1, "maximum": 65535}}
+    /// });
+    /// assert!(against_schema(&mawu!({"name": "mawu", "port": 8080}), &schema).is_ok());
+    ///
+    /// let errors = against_schema(&mawu!({"port": 70000}), &schema).unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert_eq!(errors[0].path, "");
+    /// assert_eq!(errors[0].kind, ValidationErrorKind::MissingRequired("name".to_string()));
+    /// assert_eq!(errors[1].path, "/port");
+    /// assert_eq!(errors[1].kind, ValidationErrorKind::AboveMaximum(65535.0));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns all `ValidationError`'s, each with the JSON Pointer of the failing value.
+    /// A malformed schema keyword is reported as `ValidationErrorKind::InvalidSchema`.
+    pub fn against_schema(value: &MawuValue, schema: &MawuValue) -> Result<(), Vec<ValidationError>> {
+        json_schema::validate(value, schema)
+    }
+
+    /// Infers a minimal JSON Schema from an example value, which the value itself is valid against.
+    ///
+    /// The schema uses the keywords `type`, `required`, `properties` and `items`, every key of an object
+    /// is required. The elements of an array are described by a single `items` schema: their types are
+    /// united, only keys found in every object are required and the schemas of nested values are merged.
+    /// CSV values are described like an array of objects or an array of arrays.
+    ///
+    /// # Arguments
+    /// * `value` - The example `MawuValue` to describe
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{mawu, validate::{against_schema, infer_schema}};
+    ///
+    /// let sample = mawu!({"users": [{"name": "ada", "admin": true}, {"name": "grace"}]});
+    /// let schema = infer_schema(&sample);
+    /// assert_eq!(schema, mawu!({
+    ///     "type": "object",
+    ///     "required": ["users"],
+    ///     "properties": {"users": {"type": "array", "items": {
+    ///         "type": "object",
+    ///         "required": ["name"],
+    ///         "properties": {"name": {"type": "string"}, "admin": {"type": "boolean"}}
+    ///     }}}
+    /// }));
+    /// assert!(against_schema(&sample, &schema).is_ok());
+    /// ```
+    pub fn infer_schema(value: &MawuValue) -> MawuValue {
+        json_schema::infer(value)
+    }
+}
+
+/// Infers JSON Schemas from example values, see `validate` for checking values against them
+pub mod schema {
+    use crate::{mawu_value::MawuValue, utils::json_schema};
+
+    /// Infers a minimal JSON Schema from an example value, exactly like `validate::infer_schema`.
+    ///
+    /// # Arguments
+    /// * `value` - The example `MawuValue` to describe
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{mawu, schema, validate::against_schema};
+    ///
+    /// let sample = mawu!({"name": "mawu", "version": [0, 6, 2]});
+    /// let schema = schema::infer(&sample);
+    /// assert_eq!(schema.get("required").unwrap(), &mawu!(["name", "version"]));
+    /// assert!(against_schema(&sample, &schema).is_ok());
+    /// ```
+    pub fn infer(value: &MawuValue) -> MawuValue {
+        json_schema::infer(value)
+    }
 }
 
+#[cfg(feature = "std")]
 use std::path::Path;
-use crate::{errors::MawuError, mawu_value::MawuValue, serializers::{csv_serializer, json_serializer}, utils::file_handling::write_file};
+#[cfg(feature = "std")]
+use crate::{errors::MawuError, mawu_value::MawuValue, serializers::{csv_serializer, csv_write_options::CsvWriteOptions, json_serializer}, utils::file_handling::write_file};
 
 /// Writes a file with the given contents.
 /// Writes a CSV-file if the contents are a `MawuValue::CSVObject` our `MawuValue::CSVArray` and a JSON-file if the contents are any other `MawuValue`.
@@ -1155,6 +3083,7 @@ use crate::{errors::MawuError, mawu_value::MawuValue, serializers::{csv_serializ
 /// # // Cleaning up, as `cargo test` actually creates the file on disc during testing
 /// # std::fs::remove_file(path_to_file).unwrap();
 /// ```
+#[cfg(feature = "std")]
 pub fn write<T: AsRef<Path>, C: Into<MawuValue>>(path: T, contents: C) -> Result<(), MawuError> {
     write_pretty(path, contents, 0)
 }
@@ -1230,12 +3159,13 @@ pub fn write<T: AsRef<Path>, C: Into<MawuValue>>(path: T, contents: C) -> Result
 /// # // Cleaning up, as `cargo test` actually creates the file on disc during testing
 /// # std::fs::remove_file(path_to_file).unwrap();
 /// ```
+#[cfg(feature = "std")]
 pub fn write_pretty<T: AsRef<Path>, C: Into<MawuValue>>(path: T, contents: C, spaces: u8) -> Result<(), MawuError> {
     let contents = contents.into();
     match contents {
-        MawuValue::CSVObject(v) => write_file(path, csv_serializer::serialize_csv_headed(MawuValue::CSVObject(v.clone()), spaces)?),
-        MawuValue::CSVArray(v) => write_file(path, csv_serializer::serialize_csv_unheaded(MawuValue::CSVArray(v.clone()), spaces)?),
-        _ => write_file(path, json_serializer::serialize_json(contents, spaces, 0)?),
+        MawuValue::CSVObject(v) => write_file(path, csv_serializer::serialize_csv_headed(MawuValue::CSVObject(v.clone()), spaces, &CsvWriteOptions::default())?),
+        MawuValue::CSVArray(v) => write_file(path, csv_serializer::serialize_csv_unheaded(MawuValue::CSVArray(v.clone()), spaces, &CsvWriteOptions::default())?),
+        _ => write_file(path, json_serializer::serialize_json(&contents, spaces)?),
     }
 }
 
@@ -0,0 +1,285 @@
+use crate::prelude::*;
+use crate::mawu_value::MawuValue;
+
+/// Constructs a `MawuValue` from a JSON like literal.
+///
+/// `null`, `true` and `false` are turned into `MawuValue::None` and `MawuValue::Bool`,
+/// `[...]` into a `MawuValue::Array` and `{...}` into a `MawuValue::Object`, nested as deep as needed.
+/// Literals are turned into the value parsing them as JSON would return: string literals into a
+/// `MawuValue::String` as they are, without any type inference, non-negative integers into a
+/// `MawuValue::Uint`, negative ones into a `MawuValue::Int` and floats into a `MawuValue::Float`.
+/// Everything else is treated as a rust expression and converted with `MawuValue::from`, so the
+/// same type inference applies, e.g. a `String` holding `"1"` becomes a `MawuValue::Uint`.
+///
+/// Keys of an object have to be a single token, either a string literal or a rust expression in
+/// parentheses.
+///
+/// ## Examples
+/// ```rust
+/// use mawu::{mawu, mawu_value::MawuValue};
+///
+/// assert_eq!(mawu!(null), MawuValue::None);
+/// assert_eq!(mawu!(true), MawuValue::Bool(true));
+/// assert_eq!(mawu!(42), MawuValue::Uint(42));
+/// assert_eq!(mawu!(-42), MawuValue::Int(-42));
+/// assert_eq!(mawu!("hello"), MawuValue::from("hello"));
+/// assert_eq!(mawu!("01234"), MawuValue::String("01234".to_string()));
+/// assert_eq!(mawu!(""), MawuValue::String(String::new()));
+/// assert_eq!(mawu!({"a": [1, "1"]}), mawu::json::from_str(r#"{"a": [1, "1"]}"#).unwrap());
+/// ```
+///
+/// Arrays and objects can be nested:
+/// ```rust
+/// use mawu::{mawu, mawu_value::MawuValue};
+///
+/// let value = mawu!({
+///     "name": "x",
+///     "nums": [1, 2, 3],
+///     "nested": {"empty": {}, "list": [null, false, [-1.5]]}
+/// });
+/// assert_eq!(value.get("name").unwrap(), &MawuValue::from("x"));
+/// assert_eq!(value.get("nums").unwrap(), &MawuValue::from(vec![1u64, 2, 3]));
+/// let nested = value.get("nested").unwrap();
+/// assert_eq!(nested.get("empty").unwrap(), &MawuValue::new_object());
+/// assert_eq!(nested.get("list").unwrap().len(), 3);
+/// ```
+///
+/// Rust expressions can be interpolated as values, and as keys inside parentheses:
+/// ```rust
+/// use mawu::{mawu, mawu_value::MawuValue};
+///
+/// let key = "computed";
+/// let numbers = vec![4, 5];
+/// let value = mawu!({
+///     (key): numbers.len() * 2,
+///     "sum": numbers.iter().sum::<i32>(),
+///     "numbers": numbers
+/// });
+/// assert_eq!(value.get("computed").unwrap(), &MawuValue::Uint(4));
+/// assert_eq!(value.get("sum").unwrap(), &MawuValue::Int(9));
+/// assert_eq!(value.get("numbers").unwrap(), &MawuValue::from(vec![4, 5]));
+/// ```
+#[macro_export]
+macro_rules! mawu {
+    // arrays, every element is pushed onto the accumulator in `[...]`
+    (@array [$($elems:expr,)*]) => {
+        $crate::__private::vec![$($elems,)*]
+    };
+    (@array [$($elems:expr,)*] , $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)*] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!(null),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] true $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!(true),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] false $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!(false),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!([$($array)*]),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] {$($object:tt)*} $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!({$($object)*}),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $next:literal, $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!(@literal $next),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $last:literal) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!(@literal $last),])
+    };
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!($next),] $($rest)*)
+    };
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::mawu!(@array [$($elems,)* $crate::mawu!($last),])
+    };
+
+    // objects, every key-value-pair is inserted into the `HashMap` named `$object`
+    (@object $object:ident ()) => {};
+    (@object $object:ident (, $($rest:tt)*)) => {
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] (null $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!(null));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] (true $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!(true));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] (false $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!(false));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ([$($array:tt)*] $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!([$($array)*]));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ({$($inner:tt)*} $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!({$($inner)*}));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ($value:literal, $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!(@literal $value));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ($value:literal)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!(@literal $value));
+    };
+    (@object $object:ident [$key:tt] ($value:expr, $($rest:tt)*)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!($value));
+        $crate::mawu!(@object $object ($($rest)*));
+    };
+    (@object $object:ident [$key:tt] ($value:expr)) => {
+        $crate::mawu!(@insert $object $key $crate::mawu!($value));
+    };
+    (@object $object:ident ($key:tt : $($rest:tt)*)) => {
+        $crate::mawu!(@object $object [$key] ($($rest)*));
+    };
+    (@insert $object:ident $key:tt $value:expr) => {
+        let _ = $object.insert($crate::__private::ToString::to_string(&$key), $value);
+    };
+    (@literal $literal:literal) => {
+        $crate::__private::MawuLiteral::into_mawu_value($literal)
+    };
+
+    // entry points
+    (null) => {
+        $crate::mawu_value::MawuValue::None
+    };
+    (true) => {
+        $crate::mawu_value::MawuValue::Bool(true)
+    };
+    (false) => {
+        $crate::mawu_value::MawuValue::Bool(false)
+    };
+    ([$($array:tt)*]) => {
+        $crate::mawu_value::MawuValue::Array($crate::mawu!(@array [] $($array)*))
+    };
+    ({$($inner:tt)*}) => {{
+        #[allow(unused_mut)]
+        let mut object = $crate::mawu_value::MawuObject::new();
+        $crate::mawu!(@object object ($($inner)*));
+        $crate::mawu_value::MawuValue::Object(object)
+    }};
+    ($literal:literal) => {
+        $crate::mawu!(@literal $literal)
+    };
+    ($other:expr) => {
+        $crate::mawu_value::MawuValue::from($other)
+    };
+}
+
+/// Converts the literals of `mawu!` into the `MawuValue` parsing them as JSON would return
+#[doc(hidden)]
+pub trait MawuLiteral {
+    fn into_mawu_value(self) -> MawuValue;
+}
+
+impl MawuLiteral for &str {
+    fn into_mawu_value(self) -> MawuValue {
+        MawuValue::String(self.to_string())
+    }
+}
+
+impl MawuLiteral for char {
+    fn into_mawu_value(self) -> MawuValue {
+        MawuValue::String(self.to_string())
+    }
+}
+
+impl MawuLiteral for bool {
+    fn into_mawu_value(self) -> MawuValue {
+        MawuValue::Bool(self)
+    }
+}
+
+impl MawuLiteral for f32 {
+    fn into_mawu_value(self) -> MawuValue {
+        MawuValue::from(self)
+    }
+}
+
+impl MawuLiteral for f64 {
+    fn into_mawu_value(self) -> MawuValue {
+        MawuValue::from(self)
+    }
+}
+
+macro_rules! unsigned_literal {
+    ($($t:ty),*) => {$(
+        impl MawuLiteral for $t {
+            fn into_mawu_value(self) -> MawuValue {
+                MawuValue::Uint(self as u64)
+            }
+        }
+    )*};
+}
+unsigned_literal!(u8, u16, u32, u64, usize);
+
+// like in parsed JSON, only negative integers are a `MawuValue::Int`
+macro_rules! signed_literal {
+    ($($t:ty),*) => {$(
+        impl MawuLiteral for $t {
+            fn into_mawu_value(self) -> MawuValue {
+                if self >= 0 {
+                    MawuValue::Uint(self as u64)
+                } else {
+                    MawuValue::Int(self as i64)
+                }
+            }
+        }
+    )*};
+}
+signed_literal!(i8, i16, i32, i64, isize);
+
+#[test]
+fn mawu_macro_shapes() {
+    use crate::mawu_value::MawuValue;
+    use std::collections::HashMap;
+
+    assert_eq!(mawu!([]), MawuValue::new_array());
+    assert_eq!(mawu!({}), MawuValue::new_object());
+    assert_eq!(mawu!([null, true, false,]), MawuValue::from(vec![MawuValue::None, MawuValue::Bool(true), MawuValue::Bool(false)]));
+    assert_eq!(mawu!([[1], {"a": 2}, -3]), MawuValue::from(vec![
+        MawuValue::from(vec![1u64]),
+        MawuValue::from(HashMap::from([("a", 2u64)])),
+        MawuValue::Int(-3),
+    ]));
+    let object = mawu!({"a": null, "b": [true, 1.5], "c": {"d": "e"},});
+    assert_eq!(object.len(), 3);
+    assert_eq!(object.get("a").unwrap(), &MawuValue::None);
+    assert_eq!(object.get("b").unwrap(), &MawuValue::from(vec![MawuValue::Bool(true), MawuValue::Float(1.5)]));
+    assert_eq!(object.get("c").unwrap().get("d").unwrap(), &MawuValue::from("e"));
+}
+
+#[test]
+fn mawu_macro_literals() {
+    use crate::mawu_value::MawuValue;
+
+    // string literals are never inferred
+    let object = mawu!({"zip": "01234", "v": "1.0", "path": "", "flag": "true", "null": "null"});
+    assert_eq!(object.get("zip").unwrap(), &MawuValue::String("01234".to_string()));
+    assert_eq!(object.get("v").unwrap(), &MawuValue::String("1.0".to_string()));
+    assert_eq!(object.get("path").unwrap(), &MawuValue::String(String::new()));
+    assert_eq!(object.get("flag").unwrap(), &MawuValue::String("true".to_string()));
+    assert_eq!(object.get("null").unwrap(), &MawuValue::String("null".to_string()));
+    assert_eq!(mawu!(["1", 'c']), MawuValue::from(vec![MawuValue::String("1".to_string()), MawuValue::String("c".to_string())]));
+
+    // integers match the parser, only negative ones are signed
+    assert_eq!(mawu!(0), MawuValue::Uint(0));
+    assert_eq!(mawu!([1, -1, 1i64, 1.5, 2u8]), MawuValue::from(vec![
+        MawuValue::Uint(1),
+        MawuValue::Int(-1),
+        MawuValue::Uint(1),
+        MawuValue::Float(1.5),
+        MawuValue::Uint(2),
+    ]));
+    assert_eq!(mawu!({"a": 1, "b": -2}), crate::json::from_str(r#"{"a": 1, "b": -2}"#).unwrap());
+
+    // expressions still go through `MawuValue::from`
+    let one = String::from("1");
+    assert_eq!(mawu!([one, 1 + 1]), MawuValue::from(vec![MawuValue::Uint(1), MawuValue::Int(2)]));
+}
@@ -1,18 +1,51 @@
-use core::fmt;
-use std::collections::HashMap;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    mem::size_of,
+};
+#[cfg(feature = "std")]
+use std::collections::{hash_map::DefaultHasher, HashMap};
+
+// without `std` there is no `HashMap`, CSV rows and objects are backed by a `BTreeMap` instead
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as HashMap;
+
+use crate::{
+    errors::MawuError,
+    shared_mawu_value::SharedMawuValue,
+    utils::{json_patch, json_pointer},
+};
+
+/// The map backing `MawuValue::Object`.
+///
+/// A `HashMap` by default, or a `BTreeMap` with the `btreemap` feature enabled or without the
+/// `std` feature, iterating and serializing objects sorted by key.
+#[cfg(all(feature = "std", not(feature = "btreemap")))]
+pub type MawuObject = HashMap<String, MawuValue>;
+/// The map backing `MawuValue::Object`.
+///
+/// A `HashMap` by default, or a `BTreeMap` with the `btreemap` feature enabled or without the
+/// `std` feature, iterating and serializing objects sorted by key.
+#[cfg(any(feature = "btreemap", not(feature = "std")))]
+pub type MawuObject = BTreeMap<String, MawuValue>;
 
 #[derive(Clone, Debug, PartialEq)]
 /// MawuValue wraps all data types supported by Mawu.
 /// It can be constructed using the `MawuValue::from` function on almost any basic rust type,
 /// including Option's, Vector's and HashMap's.
 /// Using the `MawuValue::default` or `MawuValue::new` function will return an `MawuValue::None`.
+///
+/// Cloning a `MawuValue` copies the whole tree, including every string, which is expensive for large documents.
+/// To share a value read-only, e.g. across threads, use `into_shared` and clone the `SharedMawuValue` instead.
 pub enum MawuValue {
     /// Only used to hold a headed CSV file
     CSVObject(Vec<HashMap<String, MawuValue>>),
     /// Only used to hold a headless CSV file
     CSVArray(Vec<Vec<MawuValue>>),
     /// Represents a JSON Object, with string keys and values made of `MawuValue`'s
-    Object(HashMap<String, MawuValue>),
+    Object(MawuObject),
     /// Represents a JSON Array, made of `MawuValue`'s
     Array(Vec<MawuValue>),
     /// Represents an unsigned integer
@@ -29,6 +62,165 @@ pub enum MawuValue {
     None,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// MawuNumber wraps the numeric `MawuValue` types, so any number can be handled at once.
+/// It is returned by `MawuValue::as_number`.
+pub enum MawuNumber {
+    /// Represents an unsigned integer
+    Uint(u64),
+    /// Represents a signed integer
+    Int(i64),
+    /// Represents a floating point number
+    Float(f64),
+}
+
+impl MawuNumber {
+    /// Returns the number as an `f64`.
+    /// Integers too large for an `f64` lose precision.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuNumber;
+    ///
+    /// assert_eq!(MawuNumber::Uint(1).as_f64(), 1.0);
+    /// assert_eq!(MawuNumber::Int(-1).as_f64(), -1.0);
+    /// assert_eq!(MawuNumber::Float(1.5).as_f64(), 1.5);
+    /// ```
+    pub fn as_f64(&self) -> f64 {
+        match *self {
+            MawuNumber::Uint(v) => v as f64,
+            MawuNumber::Int(v) => v as f64,
+            MawuNumber::Float(v) => v,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// The scalar types a `MawuValue` can be converted into with `MawuValue::coerce_to`.
+pub enum MawuScalarType {
+    /// Coerce into a `MawuValue::Uint`
+    Uint,
+    /// Coerce into a `MawuValue::Int`
+    Int,
+    /// Coerce into a `MawuValue::Float`
+    Float,
+    /// Coerce into a `MawuValue::String`
+    String,
+    /// Coerce into a `MawuValue::Bool`
+    Bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// One step of a path for `MawuValue::deep_get`, either into an object or into an array.
+pub enum PathSeg<'a> {
+    /// The key of an object, used as is, so it may contain `/` or `~`
+    Key(&'a str),
+    /// The index of an array element
+    Index(usize),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Statistics over the numeric values of a CSV column, returned by `MawuValue::csv_column_stats`.
+pub struct ColumnStats {
+    /// The number of numeric values
+    pub count: usize,
+    /// The number of values skipped, because they were not numeric or empty
+    pub skipped: usize,
+    /// The sum of all numeric values
+    pub sum: f64,
+    /// The arithmetic mean of all numeric values
+    pub mean: f64,
+    /// The smallest numeric value
+    pub min: f64,
+    /// The largest numeric value
+    pub max: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Builds a `MawuValue::Object` one key-value pair at a time.
+/// Setting a key twice replaces the earlier value, like inserting into a `HashMap`.
+///
+/// ## Example
+/// ```rust
+/// use mawu::mawu_value::{MawuArrayBuilder, MawuObjectBuilder, MawuValue};
+///
+/// let value = MawuObjectBuilder::new()
+///     .set("name", "mawu")
+///     .set("tags", MawuArrayBuilder::new().add("csv").add("json"))
+///     .set("nested", MawuObjectBuilder::new().set("ok", true))
+///     .build();
+/// assert_eq!(value.get("name").unwrap(), &MawuValue::from("mawu"));
+/// assert_eq!(value.get("tags").unwrap().len(), 2);
+/// assert_eq!(value.get("nested").unwrap().get("ok").unwrap(), &MawuValue::Bool(true));
+/// ```
+pub struct MawuObjectBuilder {
+    object: MawuObject,
+}
+
+impl MawuObjectBuilder {
+    /// Creates a new, empty `MawuObjectBuilder`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key` to `value`, converting both with `into`
+    pub fn set<K: Into<String>, V: Into<MawuValue>>(mut self, key: K, value: V) -> Self {
+        self.object.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the built `MawuValue::Object`
+    pub fn build(self) -> MawuValue {
+        MawuValue::Object(self.object)
+    }
+}
+
+impl From<MawuObjectBuilder> for MawuValue {
+    fn from(value: MawuObjectBuilder) -> Self {
+        value.build()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+/// Builds a `MawuValue::Array` one value at a time, keeping the order the values were added in.
+///
+/// ## Example
+/// ```rust
+/// use mawu::mawu_value::{MawuArrayBuilder, MawuValue};
+///
+/// let value = MawuArrayBuilder::new().add(1).add("two").add(MawuValue::None).build();
+/// assert_eq!(value.len(), 3);
+/// assert_eq!(value.as_array().unwrap()[1], MawuValue::from("two"));
+/// ```
+pub struct MawuArrayBuilder {
+    array: Vec<MawuValue>,
+}
+
+impl MawuArrayBuilder {
+    /// Creates a new, empty `MawuArrayBuilder`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `value` to the end of the array, converting it with `into`
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<V: Into<MawuValue>>(mut self, value: V) -> Self {
+        self.array.push(value.into());
+        self
+    }
+
+    /// Returns the built `MawuValue::Array`
+    pub fn build(self) -> MawuValue {
+        MawuValue::Array(self.array)
+    }
+}
+
+impl From<MawuArrayBuilder> for MawuValue {
+    fn from(value: MawuArrayBuilder) -> Self {
+        value.build()
+    }
+}
+
 impl fmt::Display for MawuValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
@@ -59,6 +251,98 @@ impl fmt::Display for MawuValue {
     }
 }
 
+/// `MawuValue` implements `Eq` so it can be used as a key in a `HashMap` or inside a `HashSet`.
+///
+/// Floats are still compared with `==`, so a `MawuValue::Float(f64::NAN)` is never equal to
+/// itself and cannot be found again once inserted. Mawu never produces a `NaN` while parsing.
+impl Eq for MawuValue {}
+
+/// Hashes the variant and its contents, consistent with `PartialEq`.
+///
+/// Floats are hashed by their bit pattern, with `-0.0` hashed as `0.0` as they compare equal.
+/// Objects are hashed independently of the order of their key-value-pairs.
+impl Hash for MawuValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            MawuValue::CSVObject(v) => {
+                state.write_usize(v.len());
+                for row in v {
+                    hash_object(row.iter(), state);
+                }
+            }
+            MawuValue::CSVArray(v) => v.hash(state),
+            MawuValue::Object(v) => hash_object(v.iter(), state),
+            MawuValue::Array(v) => v.hash(state),
+            MawuValue::Uint(v) => v.hash(state),
+            MawuValue::Int(v) => v.hash(state),
+            MawuValue::Float(v) => {
+                if *v == 0.0 {
+                    0.0f64.to_bits().hash(state)
+                } else {
+                    v.to_bits().hash(state)
+                }
+            }
+            MawuValue::String(v) => v.hash(state),
+            MawuValue::Bool(v) => v.hash(state),
+            MawuValue::None => {}
+        }
+    }
+}
+
+/// Takes the pairs of an object or CSV row, as both are hashed the same
+#[cfg(feature = "std")]
+fn hash_object<'a, H: Hasher>(object: impl ExactSizeIterator<Item = (&'a String, &'a MawuValue)>, state: &mut H) {
+    state.write_usize(object.len());
+    // the iteration order of a `HashMap` is random, so the hashes of the pairs are combined with a commutative sum
+    let sum = object.fold(0u64, |acc, pair| {
+        let mut hasher = DefaultHasher::new();
+        pair.hash(&mut hasher);
+        acc.wrapping_add(hasher.finish())
+    });
+    state.write_u64(sum);
+}
+
+/// Takes the pairs of an object or CSV row, as both are hashed the same
+// without `std` both are backed by a `BTreeMap`, iterating in a fixed order
+#[cfg(not(feature = "std"))]
+fn hash_object<'a, H: Hasher>(object: impl ExactSizeIterator<Item = (&'a String, &'a MawuValue)>, state: &mut H) {
+    state.write_usize(object.len());
+    for pair in object {
+        pair.hash(state);
+    }
+}
+
+#[test]
+fn hash_scalars() {
+    use std::collections::HashSet;
+
+    let mut set = HashSet::new();
+    assert!(set.insert(MawuValue::Uint(1)));
+    assert!(set.insert(MawuValue::Int(1)));
+    assert!(set.insert(MawuValue::Float(1.0)));
+    assert!(set.insert(MawuValue::String("1".to_string())));
+    assert!(set.insert(MawuValue::Bool(true)));
+    assert!(set.insert(MawuValue::None));
+    assert!(!set.insert(MawuValue::Uint(1)));
+    assert!(!set.insert(MawuValue::String("1".to_string())));
+    assert!(!set.insert(MawuValue::None));
+    assert_eq!(set.len(), 6);
+
+    assert!(set.insert(MawuValue::Float(0.0)));
+    assert!(!set.insert(MawuValue::Float(-0.0)));
+
+    let object_a = MawuValue::Object(MawuObject::from([
+        ("a".to_string(), MawuValue::Uint(1)),
+        ("b".to_string(), MawuValue::Uint(2)),
+    ]));
+    let mut object_b = MawuObject::new();
+    object_b.insert("b".to_string(), MawuValue::Uint(2));
+    object_b.insert("a".to_string(), MawuValue::Uint(1));
+    assert!(set.insert(object_a));
+    assert!(!set.insert(MawuValue::Object(object_b)));
+}
+
 #[test]
 #[ignore]
 fn mawu_value_display_needs_nocapture() {
@@ -132,6 +416,56 @@ where
     }
 }
 
+impl<K, V> From<BTreeMap<K, V>> for MawuValue
+where
+    K: Into<String>,
+    V: Into<MawuValue>,
+{
+    fn from(value: BTreeMap<K, V>) -> Self {
+        MawuValue::Object(
+            value
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Pairs in source order, e.g. `MawuValue::from([("a", 1), ("b", 2)])`.
+/// Later duplicate keys replace earlier ones.
+///
+/// There is no `From<(K, V)>` for a single pair, as it would conflict with the `Vec<T>` and
+/// `Vec<(K, V)>` impls, use a one element array like `MawuValue::from([("k", 5)])` instead.
+impl<V, const N: usize> From<[(&str, V); N]> for MawuValue
+where
+    V: Into<MawuValue>,
+{
+    fn from(value: [(&str, V); N]) -> Self {
+        MawuValue::Object(
+            value
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v.into()))
+                .collect(),
+        )
+    }
+}
+
+/// Like the array version, cloning each value
+impl<V> From<&[(&str, V)]> for MawuValue
+where
+    V: Into<MawuValue> + Clone,
+{
+    fn from(value: &[(&str, V)]) -> Self {
+        MawuValue::Object(
+            value
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone().into()))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
 impl<K, V> From<HashMap<K, V>> for MawuValue
 where
     K: Into<String>,
@@ -162,6 +496,62 @@ impl From<&MawuValue> for MawuValue {
     }
 }
 
+/// Collects into a `MawuValue::Array`
+impl FromIterator<MawuValue> for MawuValue {
+    fn from_iter<I: IntoIterator<Item = MawuValue>>(iter: I) -> Self {
+        MawuValue::Array(iter.into_iter().collect())
+    }
+}
+
+/// Collects into a `MawuValue::Object`, later duplicate keys replace earlier ones
+impl FromIterator<(String, MawuValue)> for MawuValue {
+    fn from_iter<I: IntoIterator<Item = (String, MawuValue)>>(iter: I) -> Self {
+        MawuValue::Object(iter.into_iter().collect())
+    }
+}
+
+/// Works on json arrays `MawuValue::Array`, and does nothing on any other type, like `push`
+impl Extend<MawuValue> for MawuValue {
+    fn extend<I: IntoIterator<Item = MawuValue>>(&mut self, iter: I) {
+        if let MawuValue::Array(v) = self {
+            v.extend(iter);
+        }
+    }
+}
+
+/// Works on json objects `MawuValue::Object`, and does nothing on any other type
+impl Extend<(String, MawuValue)> for MawuValue {
+    fn extend<I: IntoIterator<Item = (String, MawuValue)>>(&mut self, iter: I) {
+        if let MawuValue::Object(v) = self {
+            v.extend(iter);
+        }
+    }
+}
+
+#[test]
+fn collect_and_extend() {
+    let mut object = ["a", "b", "c"]
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (k.to_string(), MawuValue::from(i)))
+        .collect::<MawuValue>();
+    assert!(object.is_object());
+    assert_eq!(object.len(), 3);
+    assert_eq!(object.get("c").unwrap(), &MawuValue::Uint(2));
+    object.extend(vec![("d".to_string(), MawuValue::None), ("a".to_string(), MawuValue::Bool(true))]);
+    assert_eq!(object.len(), 4);
+    assert_eq!(object.get("a").unwrap(), &MawuValue::Bool(true));
+
+    let mut array = (1..=3).map(MawuValue::from).collect::<MawuValue>();
+    assert_eq!(array, MawuValue::from(vec![1, 2, 3]));
+    array.extend(vec![MawuValue::from(4)]);
+    assert_eq!(array.len(), 4);
+
+    let mut not_a_collection = MawuValue::from(1);
+    not_a_collection.extend(vec![MawuValue::from(4)]);
+    assert_eq!(not_a_collection, MawuValue::from(1));
+}
+
 impl From<usize> for MawuValue {
     fn from(value: usize) -> Self {
         MawuValue::Uint(value as u64)
@@ -240,72 +630,114 @@ impl From<bool> for MawuValue {
     }
 }
 
+/// Infers the type of a string like `parse` would, trying `u64`, `i64`, `f64` and `bool` in turn.
+/// Returns `None` if the value stays a `MawuValue::String`.
+///
+/// Instead of attempting every parse, only the ones that can succeed are tried: a value made of
+/// an optional sign and digits only is an integer, unless it is too large, and `f64` is only tried
+/// if the value (after its sign) starts with a digit, a `.` or the `i` and `n` of `inf` and `nan`.
+fn infer_type(value: &str) -> Option<MawuValue> {
+    if value.is_empty() {
+        return Some(MawuValue::None);
+    }
+    let bytes = value.as_bytes();
+    let unsigned = match bytes {
+        [b'+' | b'-', rest @ ..] => rest,
+        _ => bytes,
+    };
+    if !unsigned.is_empty() && unsigned.iter().all(u8::is_ascii_digit) {
+        if bytes[0] == b'-' {
+            if let Ok(i) = value.parse::<i64>() {
+                return Some(MawuValue::Int(i));
+            }
+        } else if let Ok(u) = value.parse::<u64>() {
+            return Some(MawuValue::Uint(u));
+        }
+        // too large for either, parsed as a float below
+    }
+    if matches!(unsigned.first(), Some(b'0'..=b'9' | b'.' | b'i' | b'I' | b'n' | b'N')) {
+        if let Ok(f) = value.parse::<f64>() {
+            if f.is_nan() || f.is_infinite() {
+                return Some(MawuValue::None);
+            }
+            return Some(MawuValue::Float(f));
+        }
+    }
+    match value {
+        "true" => Some(MawuValue::Bool(true)),
+        "false" => Some(MawuValue::Bool(false)),
+        _ => None,
+    }
+}
+
 impl From<String> for MawuValue {
+    /// Infers the type of the string exactly like `From<&str>`, moving it into a `MawuValue::String` if no other type fits
     fn from(value: String) -> Self {
-        if value.is_empty() {
-            MawuValue::None
-        } else if value.parse::<u64>().is_ok() {
-            MawuValue::Uint(value.parse().unwrap())
-        } else if value.parse::<i64>().is_ok() {
-            MawuValue::Int(value.parse().unwrap())
-        } else if value.parse::<f64>().is_ok() {
-            let test_bind = value.parse::<f64>().unwrap();
-            if test_bind.is_nan() || test_bind.is_infinite() {
-                MawuValue::None
-            } else {
-                MawuValue::Float(value.parse().unwrap())
-            }
-        } else if value.parse::<bool>().is_ok() {
-            MawuValue::Bool(value.parse().unwrap())
-        } else {
-            MawuValue::String(value)
+        match infer_type(&value) {
+            Some(v) => v,
+            None => MawuValue::String(value),
         }
     }
 }
 
 impl From<&String> for MawuValue {
+    /// Infers the type of the string exactly like `From<&str>`
     fn from(value: &String) -> Self {
-        if value.is_empty() {
-            MawuValue::None
-        } else if value.parse::<u64>().is_ok() {
-            MawuValue::Uint(value.parse().unwrap())
-        } else if value.parse::<i64>().is_ok() {
-            MawuValue::Int(value.parse().unwrap())
-        } else if value.parse::<f64>().is_ok() {
-            let test_bind = value.parse::<f64>().unwrap();
-            if test_bind.is_nan() || test_bind.is_infinite() {
-                MawuValue::None
-            } else {
-                MawuValue::Float(value.parse().unwrap())
-            }
-        } else if value.parse::<bool>().is_ok() {
-            MawuValue::Bool(value.parse().unwrap())
-        } else {
-            MawuValue::String(value.to_string())
-        }
+        MawuValue::from(value.as_str())
     }
 }
 
 impl From<&str> for MawuValue {
+    /// Infers the type of the string, the first of these that fits is returned:
+    ///
+    /// 1. `MawuValue::None` for an empty string
+    /// 2. `MawuValue::Uint`
+    /// 3. `MawuValue::Int`
+    /// 4. `MawuValue::Float`, or `MawuValue::None` for `inf` and `NaN`
+    /// 5. `MawuValue::Bool` for exactly `true` or `false`
+    /// 6. `MawuValue::String`
+    ///
+    /// As numbers come before booleans, `"1"` and `"0"` are always numbers.
+    /// `CsvParseOptions::true_tokens` and `false_tokens` can turn them into booleans while reading CSV.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// assert_eq!(MawuValue::from("1"), MawuValue::Uint(1));
+    /// assert_eq!(MawuValue::from("-1"), MawuValue::Int(-1));
+    /// assert_eq!(MawuValue::from("true"), MawuValue::Bool(true));
+    /// assert_eq!(MawuValue::from("TRUE"), MawuValue::String("TRUE".to_string()));
+    /// ```
     fn from(value: &str) -> Self {
-        if value.is_empty() {
-            MawuValue::None
-        } else if value.parse::<u64>().is_ok() {
-            MawuValue::Uint(value.parse().unwrap())
-        } else if value.parse::<i64>().is_ok() {
-            MawuValue::Int(value.parse().unwrap())
-        } else if value.parse::<f64>().is_ok() {
-            let test_bind = value.parse::<f64>().unwrap();
-            if test_bind.is_nan() || test_bind.is_infinite() {
-                MawuValue::None
-            } else {
-                MawuValue::Float(value.parse().unwrap())
-            }
-        } else if value.parse::<bool>().is_ok() {
-            MawuValue::Bool(value.parse().unwrap())
-        } else {
-            MawuValue::String(value.to_string())
-        }
+        infer_type(value).unwrap_or_else(|| MawuValue::String(value.to_string()))
+    }
+}
+
+#[test]
+fn container_predicates() {
+    // value, is_container, is_csv, is_empty_container
+    let values = vec![
+        (MawuValue::CSVObject(Vec::new()), true, true, true),
+        (MawuValue::new_csv_object(), true, true, false),
+        (MawuValue::CSVArray(Vec::new()), true, true, true),
+        (MawuValue::new_csv_array(), true, true, false),
+        (MawuValue::new_object(), true, false, true),
+        (MawuValue::from([("a", 1)]), true, false, false),
+        (MawuValue::new_array(), true, false, true),
+        (MawuValue::from(vec![0]), true, false, false),
+        (MawuValue::Uint(0), false, false, false),
+        (MawuValue::Int(-1), false, false, false),
+        (MawuValue::Float(0.0), false, false, false),
+        (MawuValue::String(String::new()), false, false, false),
+        (MawuValue::Bool(false), false, false, false),
+        (MawuValue::None, false, false, false),
+    ];
+    for (value, container, csv, empty_container) in values {
+        assert_eq!(value.is_container(), container, "{:?}", value);
+        assert_eq!(value.is_scalar(), !container, "{:?}", value);
+        assert_eq!(value.is_csv(), csv, "{:?}", value);
+        assert_eq!(value.is_empty_container(), empty_container, "{:?}", value);
     }
 }
 
@@ -316,14 +748,14 @@ fn new_array_object() {
     let csv_array = MawuValue::new_csv_array();
     let csv_object = MawuValue::new_csv_object();
     assert_eq!(array, MawuValue::Array(vec![]));
-    assert_eq!(object, MawuValue::Object(HashMap::new()));
+    assert_eq!(object, MawuValue::Object(MawuObject::new()));
     assert_eq!(csv_array, MawuValue::CSVArray(vec![vec![]]));
     assert_eq!(csv_object, MawuValue::CSVObject(vec![HashMap::new()]));
 }
 
 #[test]
 fn from_hashmap() {
-    let mawu_value = MawuValue::Object(HashMap::from([(
+    let mawu_value = MawuValue::Object(MawuObject::from([(
         "key".to_string(),
         MawuValue::from(u8::MAX),
     )]));
@@ -341,6 +773,14 @@ fn creating_csv_object() {
     assert!(mawu_value.is_csv_object());
 }
 
+#[test]
+fn mawu_value_is_send_sync() {
+    // fails to compile as soon as a non thread-safe type ends up inside `MawuValue`
+    fn _assert_send_sync<T: Send + Sync>() {}
+    _assert_send_sync::<MawuValue>();
+    _assert_send_sync::<crate::errors::MawuError>();
+}
+
 #[test]
 fn creating_csv_array() {
     let mawu_value = MawuValue::CSVArray(vec![vec![MawuValue::from(u8::MAX)]]);
@@ -428,7 +868,7 @@ impl MawuValue {
     /// );
     /// ```
     pub fn new_object() -> MawuValue {
-        MawuValue::Object(HashMap::new())
+        MawuValue::Object(MawuObject::new())
     }
 
     /// Used only to create a new array you want to fill yourself
@@ -739,6 +1179,77 @@ impl MawuValue {
         }
     }
 
+    /// Check if the value is a container, so an array, object or either of the CSV types
+    ///
+    /// ## Returns
+    /// `true` if the value is a container, `false` otherwise.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// assert!(MawuValue::new_array().is_container());
+    /// assert!(MawuValue::new_csv_object().is_container());
+    /// assert!(!MawuValue::from("array").is_container());
+    /// ```
+    pub fn is_container(&self) -> bool {
+        matches!(
+            self,
+            MawuValue::CSVObject(_) | MawuValue::CSVArray(_) | MawuValue::Object(_) | MawuValue::Array(_)
+        )
+    }
+
+    /// Check if the value is a scalar, so anything but a container. `None` is a scalar as well.
+    ///
+    /// ## Returns
+    /// `true` if the value is not an array, object or either of the CSV types, `false` otherwise.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// assert!(MawuValue::from(1.5).is_scalar());
+    /// assert!(MawuValue::None.is_scalar());
+    /// assert!(!MawuValue::new_object().is_scalar());
+    /// ```
+    pub fn is_scalar(&self) -> bool {
+        !self.is_container()
+    }
+
+    /// Check if the value is either of the CSV types
+    ///
+    /// ## Returns
+    /// `true` if the value is a `CSVObject` or `CSVArray`, `false` otherwise.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// assert!(MawuValue::new_csv_array().is_csv());
+    /// assert!(!MawuValue::new_array().is_csv());
+    /// ```
+    pub fn is_csv(&self) -> bool {
+        matches!(self, MawuValue::CSVObject(_) | MawuValue::CSVArray(_))
+    }
+
+    /// Check if the value is a container without any elements.
+    /// Unlike `is_empty`, this is `false` for every scalar, including `0`, `""` and `None`.
+    ///
+    /// ## Returns
+    /// `true` if the value is an array, object or either of the CSV types and empty, `false` otherwise.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// assert!(MawuValue::new_array().is_empty_container());
+    /// assert!(!MawuValue::from(vec![1]).is_empty_container());
+    /// assert!(!MawuValue::None.is_empty_container());
+    /// ```
+    pub fn is_empty_container(&self) -> bool {
+        self.is_container() && self.is_empty()
+    }
+
     /// Convenience method to check if the value is negative.
     ///
     /// ## Returns
@@ -828,20 +1339,19 @@ impl MawuValue {
         }
     }
 
-    /// Returns `Some(&HashMap<String, MawuValue>)` if the value is an object, `None` otherwise.
+    /// Returns `Some(&MawuObject)` if the value is an object, `None` otherwise.
     ///
     /// Consider using `to_object` instead if you prefer to get an owned value
     ///
     /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::mawu_value::{MawuObject, MawuValue};
     ///
-    /// let object = MawuValue::Object(HashMap::from([("a".to_string(), MawuValue::Int(-1))]));
+    /// let object = MawuValue::Object(MawuObject::from([("a".to_string(), MawuValue::Int(-1))]));
     /// let mawu_value = object.as_object().unwrap();
     /// assert_eq!(mawu_value.get("a").unwrap(), &MawuValue::Int(-1));
     /// ```
-    pub fn as_object(&self) -> Option<&HashMap<String, MawuValue>> {
+    pub fn as_object(&self) -> Option<&MawuObject> {
         match self {
             MawuValue::Object(v) => Some(v),
             _ => None,
@@ -905,6 +1415,24 @@ impl MawuValue {
         }
     }
 
+    /// Returns `Some(&[u8])` holding the UTF-8 bytes of the value if it is a String, `None` otherwise.
+    /// Nothing is copied, useful for hashing or writing the string as it is.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let string = MawuValue::String("test".to_string());
+    /// assert_eq!(string.as_bytes(), Some("test".as_bytes()));
+    /// assert_eq!(MawuValue::Uint(1).as_bytes(), None);
+    /// ```
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            MawuValue::String(v) => Some(v.as_bytes()),
+            _ => None,
+        }
+    }
+
     /// Returns `Some(&u64)` if the value is an integer, `None` otherwise.
     ///
     /// Consider using `to_uint` instead if you prefer to get an owned value
@@ -962,63 +1490,236 @@ impl MawuValue {
         }
     }
 
-    /// Returns `Some(&bool)` if the value is a boolean, `None` otherwise.
-    ///
-    /// Consider using `to_bool` instead if you prefer to get an owned value
+    /// Returns `Some(MawuNumber)` if the value is a number (unsigned integer, signed integer or float), `None` otherwise.
     ///
     /// ## Example
     /// ```rust
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::mawu_value::{MawuNumber, MawuValue};
     ///
-    /// let boolean = MawuValue::Bool(true);
-    /// let mawu_value = boolean.as_bool().unwrap();
-    /// assert_eq!(mawu_value, &true);
+    /// let number = MawuValue::Int(-1);
+    /// match number.as_number().unwrap() {
+    ///     MawuNumber::Uint(_) | MawuNumber::Int(_) => println!("integer"),
+    ///     MawuNumber::Float(_) => println!("float"),
+    /// }
+    /// assert_eq!(number.as_number().unwrap().as_f64(), -1.0);
+    /// assert!(MawuValue::from("text").as_number().is_none());
     /// ```
-    pub fn as_bool(&self) -> Option<&bool> {
+    pub fn as_number(&self) -> Option<MawuNumber> {
         match self {
-            MawuValue::Bool(v) => Some(v),
+            MawuValue::Uint(v) => Some(MawuNumber::Uint(*v)),
+            MawuValue::Int(v) => Some(MawuNumber::Int(*v)),
+            MawuValue::Float(v) => Some(MawuNumber::Float(*v)),
             _ => None,
         }
     }
 
-    /// Returns `None` if the value is `None` and `Some(())` otherwise.
+    /// Compares two values, treating numbers as equal if they differ by at most `epsilon`.
+    /// Numbers are compared across the numeric variants, so `Uint(1)` and `Float(1.0)` are equal.
+    /// Any other values are compared with `==`.
     ///
-    /// Consider using `to_none` instead if you prefer to get an owned value
+    /// `NaN` is never approximately equal to anything, not even to `NaN` itself.
     ///
     /// ## Example
     /// ```rust
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let none = MawuValue::None;
-    /// let mawu_value = none.as_none();
-    /// assert!(mawu_value.is_none());
+    /// let sum = MawuValue::from(0.1 + 0.2);
+    /// assert_ne!(sum, MawuValue::from(0.3));
+    /// assert!(sum.approx_eq(&MawuValue::from(0.3), 1e-9));
+    /// assert!(MawuValue::Uint(1).approx_eq(&MawuValue::Float(1.0), 0.0));
+    /// assert!(!MawuValue::from(f64::NAN).approx_eq(&MawuValue::from(f64::NAN), 1.0));
     /// ```
-    pub fn as_none(&self) -> Option<()> {
-        match self {
-            MawuValue::None => None,
-            _ => Some(()),
+    pub fn approx_eq(&self, other: &MawuValue, epsilon: f64) -> bool {
+        match (self.as_number(), other.as_number()) {
+            (Some(a), Some(b)) => (a.as_f64() - b.as_f64()).abs() <= epsilon,
+            _ => self == other,
         }
     }
 
-    /// Returns a owned copy of the value as an `Vec<HashMap<String, MawuValue>>`.
-    /// Returns `None` if the value is not an `CSV-Object`.
-    /// In contrast to the rest of the `to_*` methods, this method does not cast any non
-    /// `MawuValue::CSVObject` values to `MawuValue::CSVObject`.
+    /// Compares two values like `==`, but treats a key of an object mapped to `MawuValue::None` as if it was absent.
     ///
-    /// Consider using `as_csv_array` instead if you prefer to get a borrowed value
+    /// This applies to nested objects and the rows of `MawuValue::CSVObject` as well, so a config with an
+    /// explicit `null` equals one leaving the key out. `None` elements of arrays are still compared, as they
+    /// take up a position. `PartialEq` stays strict.
     ///
     /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::{mawu, mawu_value::MawuValue};
     ///
-    /// let csv_object = MawuValue::CSVObject(vec![HashMap::from([("key".to_string(), MawuValue::from("value"))])]);
-    /// let mawu_value = csv_object.to_csv_object().unwrap();
-    /// assert_eq!(mawu_value[0].get("key").unwrap(), &MawuValue::String("value".to_string()));
-    pub fn to_csv_object(&self) -> Option<Vec<HashMap<String, MawuValue>>> {
-        match self {
-            MawuValue::CSVObject(v) => Some(v.clone()),
-            _ => None,
+    /// let explicit = mawu!({"name": "mawu", "proxy": null, "server": {"port": 80, "host": null}});
+    /// let missing = mawu!({"name": "mawu", "server": {"port": 80}});
+    /// assert_ne!(explicit, missing);
+    /// assert!(explicit.eq_ignoring_nulls(&missing));
+    /// assert!(!mawu!([null]).eq_ignoring_nulls(&mawu!([])));
+    /// ```
+    pub fn eq_ignoring_nulls(&self, other: &MawuValue) -> bool {
+        match (self, other) {
+            (MawuValue::Object(a), MawuValue::Object(b)) => entries_eq_ignoring_nulls(a.iter(), b.iter()),
+            (MawuValue::CSVObject(a), MawuValue::CSVObject(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| entries_eq_ignoring_nulls(a.iter(), b.iter()))
+            }
+            (MawuValue::Array(a), MawuValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq_ignoring_nulls(b))
+            }
+            (MawuValue::CSVArray(a), MawuValue::CSVArray(b)) => {
+                a.len() == b.len()
+                    && a.iter().zip(b).all(|(a, b)| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.eq_ignoring_nulls(b)))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Converts a scalar value into the `target` type, returning `None` if the value can not be
+    /// represented in it without losing information.
+    ///
+    /// | | `Uint` / `Int` | `Float` | `String` | `Bool` |
+    /// |---|---|---|---|---|
+    /// | `Uint`, `Int` | if in range | if exactly representable | always | `0` and `1` only |
+    /// | `Float` | whole numbers in range only | always | always | never |
+    /// | `String` | parsed, surrounding whitespace ignored | parsed, finite only | always | `true` or `false`, ignoring case |
+    /// | `Bool` | `0` or `1` | never | `"true"` or `"false"` | always |
+    ///
+    /// `None`, arrays, objects and both CSV types are never coerced.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::{MawuScalarType, MawuValue};
+    ///
+    /// assert_eq!(MawuValue::String("42".to_string()).coerce_to(MawuScalarType::Uint), Some(MawuValue::Uint(42)));
+    /// assert_eq!(MawuValue::from(true).coerce_to(MawuScalarType::String), Some(MawuValue::String("true".to_string())));
+    /// assert_eq!(MawuValue::from(4.5).coerce_to(MawuScalarType::Int), None);
+    /// ```
+    pub fn coerce_to(&self, target: MawuScalarType) -> Option<MawuValue> {
+        match (self, target) {
+            (MawuValue::Uint(v), MawuScalarType::Uint) => Some(MawuValue::Uint(*v)),
+            (MawuValue::Uint(v), MawuScalarType::Int) => i64::try_from(*v).ok().map(MawuValue::Int),
+            // above 2^53 not every integer is a float, only exact ones are converted
+            (MawuValue::Uint(v), MawuScalarType::Float) => {
+                let f = *v as f64;
+                if f < u64::MAX as f64 && f as u64 == *v {
+                    Some(MawuValue::Float(f))
+                } else {
+                    None
+                }
+            }
+            (MawuValue::Int(v), MawuScalarType::Uint) => u64::try_from(*v).ok().map(MawuValue::Uint),
+            (MawuValue::Int(v), MawuScalarType::Int) => Some(MawuValue::Int(*v)),
+            (MawuValue::Int(v), MawuScalarType::Float) => {
+                let f = *v as f64;
+                if f < i64::MAX as f64 && f as i64 == *v {
+                    Some(MawuValue::Float(f))
+                } else {
+                    None
+                }
+            }
+            // `u64::MAX as f64` rounds up to 2^64, so the upper bounds are exclusive
+            (MawuValue::Float(v), MawuScalarType::Uint) => {
+                if *v >= 0.0 && *v < u64::MAX as f64 && (*v as u64) as f64 == *v {
+                    Some(MawuValue::Uint(*v as u64))
+                } else {
+                    None
+                }
+            }
+            (MawuValue::Float(v), MawuScalarType::Int) => {
+                if *v >= i64::MIN as f64 && *v < i64::MAX as f64 && (*v as i64) as f64 == *v {
+                    Some(MawuValue::Int(*v as i64))
+                } else {
+                    None
+                }
+            }
+            (MawuValue::Float(v), MawuScalarType::Float) => Some(MawuValue::Float(*v)),
+            (MawuValue::Uint(_) | MawuValue::Int(_), MawuScalarType::Bool) => match self.as_number() {
+                Some(MawuNumber::Uint(0)) | Some(MawuNumber::Int(0)) => Some(MawuValue::Bool(false)),
+                Some(MawuNumber::Uint(1)) | Some(MawuNumber::Int(1)) => Some(MawuValue::Bool(true)),
+                _ => None,
+            },
+            (MawuValue::Uint(_) | MawuValue::Int(_) | MawuValue::Float(_), MawuScalarType::String) => {
+                Some(MawuValue::String(self.to_string()))
+            }
+            (MawuValue::String(v), MawuScalarType::Uint) => v.trim().parse::<u64>().ok().map(MawuValue::Uint),
+            (MawuValue::String(v), MawuScalarType::Int) => v.trim().parse::<i64>().ok().map(MawuValue::Int),
+            (MawuValue::String(v), MawuScalarType::Float) => v
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .filter(|f| f.is_finite())
+                .map(MawuValue::Float),
+            (MawuValue::String(v), MawuScalarType::String) => Some(MawuValue::String(v.clone())),
+            (MawuValue::String(v), MawuScalarType::Bool) => {
+                let v = v.trim();
+                if v.eq_ignore_ascii_case("true") {
+                    Some(MawuValue::Bool(true))
+                } else if v.eq_ignore_ascii_case("false") {
+                    Some(MawuValue::Bool(false))
+                } else {
+                    None
+                }
+            }
+            (MawuValue::Bool(v), MawuScalarType::Uint) => Some(MawuValue::Uint(*v as u64)),
+            (MawuValue::Bool(v), MawuScalarType::Int) => Some(MawuValue::Int(*v as i64)),
+            (MawuValue::Bool(v), MawuScalarType::String) => Some(MawuValue::String(v.to_string())),
+            (MawuValue::Bool(v), MawuScalarType::Bool) => Some(MawuValue::Bool(*v)),
+            _ => None,
+        }
+    }
+
+    /// Returns `Some(&bool)` if the value is a boolean, `None` otherwise.
+    ///
+    /// Consider using `to_bool` instead if you prefer to get an owned value
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let boolean = MawuValue::Bool(true);
+    /// let mawu_value = boolean.as_bool().unwrap();
+    /// assert_eq!(mawu_value, &true);
+    /// ```
+    pub fn as_bool(&self) -> Option<&bool> {
+        match self {
+            MawuValue::Bool(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns `None` if the value is `None` and `Some(())` otherwise.
+    ///
+    /// Consider using `to_none` instead if you prefer to get an owned value
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let none = MawuValue::None;
+    /// let mawu_value = none.as_none();
+    /// assert!(mawu_value.is_none());
+    /// ```
+    pub fn as_none(&self) -> Option<()> {
+        match self {
+            MawuValue::None => None,
+            _ => Some(()),
+        }
+    }
+
+    /// Returns a owned copy of the value as an `Vec<HashMap<String, MawuValue>>`.
+    /// Returns `None` if the value is not an `CSV-Object`.
+    /// In contrast to the rest of the `to_*` methods, this method does not cast any non
+    /// `MawuValue::CSVObject` values to `MawuValue::CSVObject`.
+    ///
+    /// Consider using `as_csv_array` instead if you prefer to get a borrowed value
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let csv_object = MawuValue::CSVObject(vec![HashMap::from([("key".to_string(), MawuValue::from("value"))])]);
+    /// let mawu_value = csv_object.to_csv_object().unwrap();
+    /// assert_eq!(mawu_value[0].get("key").unwrap(), &MawuValue::String("value".to_string()));
+    pub fn to_csv_object(&self) -> Option<Vec<HashMap<String, MawuValue>>> {
+        match self {
+            MawuValue::CSVObject(v) => Some(v.clone()),
+            _ => None,
         }
     }
 
@@ -1044,7 +1745,7 @@ impl MawuValue {
         }
     }
 
-    /// Returns a owned copy of the value as an `HashMap<String, MawuValue>`.
+    /// Returns a owned copy of the value as a `MawuObject`.
     /// Returns `None` if the value is not an `Object`.
     /// In contrast to the rest of the `to_*` methods, this method does not cast any non
     /// `MawuValue::Object` values to `MawuValue::Object`.
@@ -1053,14 +1754,13 @@ impl MawuValue {
     ///
     /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::mawu_value::{MawuObject, MawuValue};
     ///
-    /// let object = MawuValue::Object(HashMap::from([("key".to_string(), MawuValue::from("value"))]));
+    /// let object = MawuValue::Object(MawuObject::from([("key".to_string(), MawuValue::from("value"))]));
     /// let mawu_value = object.to_object().unwrap();
     /// assert_eq!(mawu_value.get("key").unwrap(), &MawuValue::String("value".to_string()));
     /// ```
-    pub fn to_object(&self) -> Option<HashMap<String, MawuValue>> {
+    pub fn to_object(&self) -> Option<MawuObject> {
         match self {
             MawuValue::Object(v) => Some(v.clone()),
             _ => None,
@@ -1452,333 +2152,1808 @@ impl MawuValue {
         }
     }
 
-    /// Clears the value
-    /// For arrays and objects, it removes all values, the allocated size is not changed.
-    /// For each other type, it sets the value to `MawuValue::None`.
+    /// Returns the value as a `&str`, or `default` if the value is not a string.
+    /// A thin wrapper around `as_str`.
     ///
-    /// ## Examples
+    /// ## Example
     /// ```rust
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut int = MawuValue::Int(-42);
-    /// int.clear();
-    /// assert!(int.is_none());
+    /// assert_eq!(MawuValue::from("mawu").as_str_or("default"), "mawu");
+    /// assert_eq!(MawuValue::None.as_str_or("default"), "default");
     /// ```
-    pub fn clear(&mut self) {
-        match self {
-            MawuValue::CSVObject(v) => v.clear(),
-            MawuValue::CSVArray(v) => v.clear(),
-            MawuValue::Array(v) => v.clear(),
-            MawuValue::Object(v) => v.clear(),
-            _ => *self = MawuValue::None,
-        }
+    pub fn as_str_or<'a>(&'a self, default: &'a str) -> &'a str {
+        self.as_str().unwrap_or(default)
     }
 
-    /// Returns an iterator over the values of an array
-    /// Only works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray` 
-    /// The values are borrowed (`&MawuValue`'s).
+    /// Returns the value as an `u64`, or `default` if it can not be represented as one.
+    /// A thin wrapper around `to_uint`.
     ///
-    /// ## Examples
+    /// ## Example
     /// ```rust
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut array = MawuValue::Array(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// let mut iterator = array.iter_array();
-    /// assert_eq!(iterator.next(), Some(&MawuValue::from(1)));
-    /// assert_eq!(iterator.next(), Some(&MawuValue::from(2)));
-    /// assert_eq!(iterator.next(), Some(&MawuValue::from(3)));
-    /// assert_eq!(iterator.next(), None);
+    /// assert_eq!(MawuValue::from(42).to_u64_or(8080), 42);
+    /// assert_eq!(MawuValue::from(-42).to_u64_or(8080), 8080);
     /// ```
-    pub fn iter_array(&self) -> impl Iterator<Item = &MawuValue> {
-        self.as_array().unwrap().iter()
+    pub fn to_u64_or(&self, default: u64) -> u64 {
+        self.to_uint().unwrap_or(default)
     }
 
-    /// Returns an iterator over the key-value-pairs of an object
-    /// Only works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
-    /// The values are borrowed (`&MawuValue`'s).
-    /// The keys are borrowed (`&String`'s).
+    /// Returns the value as an `i64`, or `default` if it can not be represented as one.
+    /// A thin wrapper around `to_int`.
     ///
     /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
-    /// let mut iterator = object.iter_object();
-    /// for (key, value) in iterator {
-    ///     if key == "key1" {
-    ///         assert_eq!(value, &MawuValue::from(1));
-    ///     } else if key == "key2" {
-    ///         assert_eq!(value, &MawuValue::from(2));
-    ///     } else if key == "key3" {
-    ///         assert_eq!(value, &MawuValue::from(3));
-    ///     }
-    /// }
+    /// assert_eq!(MawuValue::from(-42).to_i64_or(0), -42);
+    /// assert_eq!(MawuValue::from("text").to_i64_or(0), 0);
     /// ```
-    pub fn iter_object(&self) -> impl Iterator<Item = (&String, &MawuValue)> {
-        self.as_object().unwrap().iter()
+    pub fn to_i64_or(&self, default: i64) -> i64 {
+        self.to_int().unwrap_or(default)
     }
 
-    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
-    /// Returns a reference to the value with the given key.
-    ///
-    /// The key is may be any type that can be converted to a `String`.
+    /// Returns the value as a `f64`, or `default` if the value is not a number.
+    /// A thin wrapper around `to_float`.
     ///
-    /// ## Examples
+    /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
-    /// assert_eq!(object.get("key1").unwrap(), &MawuValue::from(1));
-    /// assert_eq!(object.get("key2").unwrap(), &MawuValue::from(2));
-    /// assert_eq!(object.get("key3").unwrap(), &MawuValue::from(3));
-    /// assert_eq!(object.get("key4"), None);
+    /// assert_eq!(MawuValue::from(0.5).to_f64_or(1.0), 0.5);
+    /// assert_eq!(MawuValue::None.to_f64_or(1.0), 1.0);
     /// ```
-    ///
-    pub fn get<S>(&self, key: S) -> Option<&MawuValue>
-    where
-        S: Into<String>,
-    {
-        match self {
-            MawuValue::Object(v) => v.get(key.into().as_str()),
-            _ => None,
-        }
+    pub fn to_f64_or(&self, default: f64) -> f64 {
+        self.to_float().unwrap_or(default)
     }
 
-    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
-    /// Inserts the given value at the given index.
+    /// Returns the value as a `bool`, or `default` if it can not be represented as one.
+    /// A thin wrapper around `to_bool`.
     ///
-    /// ## Examples
+    /// ## Example
     /// ```rust
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// array.array_insert(0, MawuValue::from(0));
-    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(0), MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]));
+    /// assert_eq!(MawuValue::from(false).to_bool_or(true), false);
+    /// assert_eq!(MawuValue::from(1).to_bool_or(true), true);
     /// ```
-    pub fn array_insert(&mut self, index: usize, value: MawuValue) {
-        match self {
-            MawuValue::Array(v) => v.insert(index, value),
-            MawuValue::CSVArray(v) => v[index].push(value),
-            _ => {}
-        }
+    pub fn to_bool_or(&self, default: bool) -> bool {
+        self.to_bool().unwrap_or(default)
     }
 
-    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
-    /// Inserts the given value with the given key.
+    /// Returns the value if it is a `MawuValue::String`.
+    /// Meant for quick scripts and tests, prefer `as_str` in production code.
     ///
-    /// ## Returns
-    /// Returns `Some(MawuValue)` if the key already existed. The value was replaced and returned.
-    /// Returns `None` if the key did not exist.
-    /// Returns `Some(MawuValue)` if the `MawuValue` was not an `MawuValue::Object`. The `MawuValue` passed into the function was returned.
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value is not a `MawuValue::String`.
     ///
-    /// ## Examples
+    /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
-    /// object.object_insert("key4", MawuValue::from(10));
-    /// assert_eq!(object.get("key4").unwrap(), &MawuValue::from(10));
+    /// assert_eq!(MawuValue::from("mawu").expect_str("name"), "mawu");
     /// ```
-    pub fn object_insert<S: Into<String>, M: Into<MawuValue>>(
-        &mut self,
-        key: S,
-        value: M,
-    ) -> Option<MawuValue> {
-        match self {
-            MawuValue::Object(v) => {
-                let tmp = v.insert(key.into(), value.into());
-                if tmp.is_none() {
-                    None
-                } else {
-                    Some(tmp.unwrap())
-                }
-            }
-            _ => Some(value.into()),
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::from(1).expect_str("name");
+    /// ```
+    pub fn expect_str(&self, msg: &str) -> &str {
+        match self.as_str() {
+            Some(v) => v,
+            None => self.type_mismatch("MawuValue::String", msg),
         }
     }
 
-    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
-    /// Removes the value at the given index and returns it.
-    /// The same restricitions as `Vec::remove` apply, as this is just a convenience function
-    /// calling it.
+    /// Returns the value as an `u64`, if it can be represented as one.
+    /// Meant for quick scripts and tests, prefer `to_uint` in production code.
+    ///
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value can not be represented as an `u64`.
     ///
     /// ## Example
     /// ```rust
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// assert_eq!(array.array_remove(1), Some(MawuValue::from(2)));
-    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(1), MawuValue::from(3)]));
+    /// assert_eq!(MawuValue::from(42).expect_u64("port"), 42);
     /// ```
-    pub fn array_remove(&mut self, index: usize) -> Option<MawuValue> {
-        match self {
-            MawuValue::Array(v) => Some(v.remove(index)),
-            _ => None,
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::from(-42).expect_u64("port");
+    /// ```
+    pub fn expect_u64(&self, msg: &str) -> u64 {
+        match self.to_uint() {
+            Some(v) => v,
+            None => self.type_mismatch("an u64", msg),
         }
     }
 
-    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
-    /// Returns a reference to the value at the given index.
-    /// The same restricitions as `Vec::get` apply, as this is just a convenience function
-    /// calling it.
+    /// Returns the value as an `i64`, if it can be represented as one.
+    /// Meant for quick scripts and tests, prefer `to_int` in production code.
+    ///
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value can not be represented as an `i64`.
     ///
     /// ## Example
     /// ```rust
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// assert_eq!(array.array_peek(1).unwrap(), &MawuValue::from(2));
-    /// assert_eq!(array.array_peek(3), None);
+    /// assert_eq!(MawuValue::from(-42).expect_i64("offset"), -42);
     /// ```
-    pub fn array_peek(&self, index: usize) -> Option<&MawuValue> {
-        match self {
-            MawuValue::Array(v) => {
-                if index < v.len() {
-                    v.get(index)
-                } else {
-                    None
-                }
-            },
-            _ => None,
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::from(u64::MAX).expect_i64("offset");
+    /// ```
+    pub fn expect_i64(&self, msg: &str) -> i64 {
+        match self.to_int() {
+            Some(v) => v,
+            None => self.type_mismatch("an i64", msg),
         }
     }
 
-    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
-    /// Removes the value with the given key and returns it.
-    /// The same restricitions as `HashMap::remove` apply, as this is just a convenience function
-    /// calling it.
+    /// Returns the value as a `f64`, if it is a number.
+    /// Meant for quick scripts and tests, prefer `to_float` in production code.
+    ///
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value is not a number.
     ///
     /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
-    /// assert_eq!(object.object_remove("key2"), Some(MawuValue::from(2)));
-    /// assert_eq!(object, MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key3".to_string(), MawuValue::from(3))]));
+    /// assert_eq!(MawuValue::from(0.5).expect_f64("ratio"), 0.5);
     /// ```
-    pub fn object_remove<S: Into<String>>(&mut self, key: S) -> Option<MawuValue> {
-        match self {
-            MawuValue::Object(v) => v.remove(key.into().as_str()),
-            _ => None,
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::from(true).expect_f64("ratio");
+    /// ```
+    pub fn expect_f64(&self, msg: &str) -> f64 {
+        match self.to_float() {
+            Some(v) => v,
+            None => self.type_mismatch("a number", msg),
         }
     }
 
-    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
-    /// Checks if the object contains the given key
+    /// Returns the value if it is a `MawuValue::Bool`.
+    /// Meant for quick scripts and tests, prefer `as_bool` in production code.
+    ///
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value is not a `MawuValue::Bool`.
     ///
     /// ## Example
     /// ```rust
-    /// use std::collections::HashMap;
     /// use mawu::mawu_value::MawuValue;
     ///
-    /// let object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
-    /// assert!(object.has_key("key1"));
-    /// assert!(!object.has_key("key4"));
+    /// assert_eq!(MawuValue::from(true).expect_bool("debug"), true);
     /// ```
-    pub fn has_key<S: Into<String>>(&self, key: S) -> bool {
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::from(1).expect_bool("debug");
+    /// ```
+    pub fn expect_bool(&self, msg: &str) -> bool {
+        match self.as_bool().copied() {
+            Some(v) => v,
+            None => self.type_mismatch("MawuValue::Bool", msg),
+        }
+    }
+
+    /// Returns the value if it is a `MawuValue::Array`.
+    /// Meant for quick scripts and tests, prefer `as_array` in production code.
+    ///
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value is not a `MawuValue::Array`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// assert_eq!(MawuValue::from(vec![1, 2]).expect_array("tags"), &vec![MawuValue::from(1), MawuValue::from(2)]);
+    /// ```
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::None.expect_array("tags");
+    /// ```
+    pub fn expect_array(&self, msg: &str) -> &Vec<MawuValue> {
+        match self.as_array() {
+            Some(v) => v,
+            None => self.type_mismatch("MawuValue::Array", msg),
+        }
+    }
+
+    /// Returns the value if it is a `MawuValue::Object`.
+    /// Meant for quick scripts and tests, prefer `as_object` in production code.
+    ///
+    /// ## Panics
+    /// Panics with `msg` and the actual type if the value is not a `MawuValue::Object`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::{MawuObject, MawuValue};
+    ///
+    /// assert_eq!(MawuValue::from(vec![("key", 1)]).expect_object("server"), &MawuObject::from([("key".to_string(), MawuValue::from(1))]));
+    /// ```
+    /// ```rust,should_panic
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// MawuValue::from(vec![1]).expect_object("server");
+    /// ```
+    pub fn expect_object(&self, msg: &str) -> &MawuObject {
+        match self.as_object() {
+            Some(v) => v,
+            None => self.type_mismatch("MawuValue::Object", msg),
+        }
+    }
+
+    fn type_mismatch(&self, expected: &str, msg: &str) -> ! {
+        panic!("{}: expected {}, found MawuValue::{}", msg, expected, self.type_name())
+    }
+
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            MawuValue::CSVObject(_) => "CSVObject",
+            MawuValue::CSVArray(_) => "CSVArray",
+            MawuValue::Object(_) => "Object",
+            MawuValue::Array(_) => "Array",
+            MawuValue::Uint(_) => "Uint",
+            MawuValue::Int(_) => "Int",
+            MawuValue::Float(_) => "Float",
+            MawuValue::String(_) => "String",
+            MawuValue::Bool(_) => "Bool",
+            MawuValue::None => "None",
+        }
+    }
+
+    /// Clears the value
+    /// For arrays and objects, it removes all values, the allocated size is not changed.
+    /// For each other type, it sets the value to `MawuValue::None`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut int = MawuValue::Int(-42);
+    /// int.clear();
+    /// assert!(int.is_none());
+    /// ```
+    pub fn clear(&mut self) {
+        match self {
+            MawuValue::CSVObject(v) => v.clear(),
+            MawuValue::CSVArray(v) => v.clear(),
+            MawuValue::Array(v) => v.clear(),
+            MawuValue::Object(v) => v.clear(),
+            _ => *self = MawuValue::None,
+        }
+    }
+
+    /// Takes the value out, leaving a `MawuValue::None` in its place.
+    /// Useful to move a value out of a parsed structure without cloning it.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut int = MawuValue::Int(-42);
+    /// assert_eq!(int.take(), MawuValue::Int(-42));
+    /// assert!(int.is_none());
+    ///
+    /// let mut object = MawuValue::from(vec![("key", vec![1, 2, 3])]);
+    /// if let MawuValue::Object(map) = &mut object {
+    ///     let array = map.get_mut("key").unwrap().take();
+    ///     assert_eq!(array, MawuValue::from(vec![1, 2, 3]));
+    /// }
+    /// assert!(object.get("key").unwrap().is_none());
+    /// ```
+    pub fn take(&mut self) -> MawuValue {
+        core::mem::take(self)
+    }
+
+    /// Moves the value into a `SharedMawuValue`, which can be cloned cheaply and read from anywhere.
+    /// Useful to fan a parsed document out to many threads without copying it.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu;
+    ///
+    /// let config = mawu!({"workers": 4, "name": "mawu"}).into_shared();
+    /// let handles = (0..4).map(|_| {
+    ///     let config = config.clone();
+    ///     std::thread::spawn(move || config.get("name").unwrap().as_str().unwrap().len())
+    /// }).collect::<Vec<_>>();
+    /// for handle in handles {
+    ///     assert_eq!(handle.join().unwrap(), 4);
+    /// }
+    /// ```
+    pub fn into_shared(self) -> SharedMawuValue {
+        SharedMawuValue::new(self)
+    }
+
+    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// Keeps only the key-value-pairs for which `f` returns `true`, removing all others in place.
+    /// Does nothing on any other type.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut object = MawuValue::from(vec![("key1", MawuValue::None), ("key2", MawuValue::from(2))]);
+    /// object.retain_object(|_, value| !value.is_none());
+    /// assert_eq!(object, MawuValue::from(vec![("key2", MawuValue::from(2))]));
+    /// ```
+    pub fn retain_object<F: FnMut(&String, &MawuValue) -> bool>(&mut self, mut f: F) {
+        if let MawuValue::Object(v) = self {
+            v.retain(|key, value| f(key, value));
+        }
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Keeps only the values for which `f` returns `true`, removing all others in place.
+    /// Does nothing on any other type.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut array = MawuValue::from(vec![1, -2, 3, -4]);
+    /// array.retain_array(|value| value.is_positive() == Some(true));
+    /// assert_eq!(array, MawuValue::from(vec![1, 3]));
+    /// ```
+    pub fn retain_array<F: FnMut(&MawuValue) -> bool>(&mut self, f: F) {
+        if let MawuValue::Array(v) = self {
+            v.retain(f);
+        }
+    }
+
+    /// Returns an iterator over the values of an array, or `None` if the value is not an array.
+    /// Only works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// The values are borrowed (`&MawuValue`'s).
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let array = MawuValue::Array(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// let mut iterator = array.iter_array().unwrap();
+    /// assert_eq!(iterator.next(), Some(&MawuValue::from(1)));
+    /// assert_eq!(iterator.next(), Some(&MawuValue::from(2)));
+    /// assert_eq!(iterator.next(), Some(&MawuValue::from(3)));
+    /// assert_eq!(iterator.next(), None);
+    /// assert!(MawuValue::from(1).iter_array().is_none());
+    /// ```
+    pub fn iter_array(&self) -> Option<impl Iterator<Item = &MawuValue>> {
+        self.as_array().map(|v| v.iter())
+    }
+
+    /// Returns an iterator over the values of an array that allows changing them, or `None` if the value is not an array.
+    /// Only works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut array = MawuValue::from(vec![1, 2, 3]);
+    /// for value in array.iter_array_mut().unwrap() {
+    ///     *value = MawuValue::from(value.to_int().unwrap() * 10);
+    /// }
+    /// assert_eq!(array, MawuValue::from(vec![10, 20, 30]));
+    /// ```
+    pub fn iter_array_mut(&mut self) -> Option<impl Iterator<Item = &mut MawuValue>> {
+        match self {
+            MawuValue::Array(v) => Some(v.iter_mut()),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the key-value-pairs of an object, or `None` if the value is not an object.
+    /// Only works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// The values are borrowed (`&MawuValue`'s).
+    /// The keys are borrowed (`&String`'s).
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
+    /// let iterator = object.iter_object().unwrap();
+    /// for (key, value) in iterator {
+    ///     if key == "key1" {
+    ///         assert_eq!(value, &MawuValue::from(1));
+    ///     } else if key == "key2" {
+    ///         assert_eq!(value, &MawuValue::from(2));
+    ///     } else if key == "key3" {
+    ///         assert_eq!(value, &MawuValue::from(3));
+    ///     }
+    /// }
+    /// assert!(MawuValue::from(vec![1]).iter_object().is_none());
+    /// ```
+    pub fn iter_object(&self) -> Option<impl Iterator<Item = (&String, &MawuValue)>> {
+        self.as_object().map(|v| v.iter())
+    }
+
+    /// Returns an iterator over the key-value-pairs of an object that allows changing the values,
+    /// or `None` if the value is not an object.
+    /// Only works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut object = MawuValue::from(vec![("a", 1), ("b", 2)]);
+    /// for (_, value) in object.iter_object_mut().unwrap() {
+    ///     *value = MawuValue::None;
+    /// }
+    /// assert_eq!(object, MawuValue::from(vec![("a", MawuValue::None), ("b", MawuValue::None)]));
+    /// ```
+    pub fn iter_object_mut(&mut self) -> Option<impl Iterator<Item = (&String, &mut MawuValue)>> {
+        match self {
+            MawuValue::Object(v) => Some(v.iter_mut()),
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the keys of an object, or `None` if the value is not an object.
+    /// Only works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    ///
+    /// The keys are in the order the object iterates them, which is sorted with the `btreemap` feature or without `std`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut keys = mawu!({"b": 2, "a": 1}).keys().unwrap().cloned().collect::<Vec<String>>();
+    /// keys.sort();
+    /// assert_eq!(keys, vec!["a", "b"]);
+    /// assert!(MawuValue::from(vec![1]).keys().is_none());
+    /// ```
+    pub fn keys(&self) -> Option<impl Iterator<Item = &String>> {
+        self.as_object().map(|v| v.keys())
+    }
+
+    /// Returns an iterator over the values of an object, or `None` if the value is not an object.
+    /// Only works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    ///
+    /// The values are in the same order as the `keys`.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let object = mawu!({"a": 1, "b": 2});
+    /// assert_eq!(object.values().unwrap().filter_map(MawuValue::to_int).sum::<i64>(), 3);
+    /// assert!(MawuValue::None.values().is_none());
+    /// ```
+    pub fn values(&self) -> Option<impl Iterator<Item = &MawuValue>> {
+        self.as_object().map(|v| v.values())
+    }
+
+    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// Returns a reference to the value with the given key.
+    ///
+    /// The key is may be any type that can be converted to a `String`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
+    /// assert_eq!(object.get("key1").unwrap(), &MawuValue::from(1));
+    /// assert_eq!(object.get("key2").unwrap(), &MawuValue::from(2));
+    /// assert_eq!(object.get("key3").unwrap(), &MawuValue::from(3));
+    /// assert_eq!(object.get("key4"), None);
+    /// ```
+    ///
+    pub fn get<S>(&self, key: S) -> Option<&MawuValue>
+    where
+        S: Into<String>,
+    {
+        match self {
+            MawuValue::Object(v) => v.get(key.into().as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the value with the given key, if it is a string.
+    /// Shorthand for `get(key)` followed by `as_str`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "port": 8080, "version": 0.5, "debug": true, "tags": ["json", "csv"]});
+    /// assert_eq!(config.get_str("name"), Some("mawu"));
+    /// assert_eq!(config.get_str("version"), None);
+    /// assert_eq!(config.get_str("missing"), None);
+    /// ```
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get(key)?.as_str()
+    }
+
+    /// Returns the value with the given key, if it is a number that can be represented as an `u64`.
+    /// Shorthand for `get(key)` followed by `to_uint`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "port": 8080, "version": 0.5, "debug": true, "tags": ["json", "csv"]});
+    /// assert_eq!(config.get_u64("port"), Some(8080));
+    /// assert_eq!(config.get_u64("name"), None);
+    /// assert_eq!(config.get_u64("missing"), None);
+    /// ```
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.get(key)?.to_uint()
+    }
+
+    /// Returns the value with the given key, if it is a number that can be represented as an `i64`.
+    /// Shorthand for `get(key)` followed by `to_int`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "port": 8080, "version": 0.5, "debug": true, "tags": ["json", "csv"]});
+    /// assert_eq!(config.get_i64("port"), Some(8080));
+    /// assert_eq!(config.get_i64("name"), None);
+    /// assert_eq!(config.get_i64("missing"), None);
+    /// ```
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get(key)?.to_int()
+    }
+
+    /// Returns the value with the given key, if it is a number.
+    /// Shorthand for `get(key)` followed by `to_float`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "port": 8080, "version": 0.5, "debug": true, "tags": ["json", "csv"]});
+    /// assert_eq!(config.get_f64("version"), Some(0.5));
+    /// assert_eq!(config.get_f64("name"), None);
+    /// assert_eq!(config.get_f64("missing"), None);
+    /// ```
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key)?.to_float()
+    }
+
+    /// Returns the value with the given key, if it is a boolean.
+    /// Shorthand for `get(key)` followed by `as_bool`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "port": 8080, "version": 0.5, "debug": true, "tags": ["json", "csv"]});
+    /// assert_eq!(config.get_bool("debug"), Some(true));
+    /// assert_eq!(config.get_bool("port"), None);
+    /// assert_eq!(config.get_bool("missing"), None);
+    /// ```
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key)?.as_bool().copied()
+    }
+
+    /// Returns the value with the given key, if it is an array.
+    /// Shorthand for `get(key)` followed by `as_array`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "port": 8080, "version": 0.5, "debug": true, "tags": ["json", "csv"]});
+    /// assert_eq!(config.get_array("tags"), Some(&vec![MawuValue::from("json"), MawuValue::from("csv")]));
+    /// assert_eq!(config.get_array("name"), None);
+    /// assert_eq!(config.get_array("missing"), None);
+    /// ```
+    pub fn get_array(&self, key: &str) -> Option<&Vec<MawuValue>> {
+        self.get(key)?.as_array()
+    }
+
+    /// Returns the value with the given key, if it is an object.
+    /// Shorthand for `get(key)` followed by `as_object`.
+    /// Returns `None` if this is not an object, the key is missing or the value has a different type.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let config = mawu!({"name": "mawu", "server": {"port": 8080}});
+    /// assert_eq!(config.get_object("server").unwrap().get("port"), Some(&MawuValue::Uint(8080)));
+    /// assert_eq!(config.get_object("name"), None);
+    /// assert_eq!(config.get_object("missing"), None);
+    /// ```
+    pub fn get_object(&self, key: &str) -> Option<&MawuObject> {
+        self.get(key)?.as_object()
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Returns a reference to the value the JSON Pointer (RFC 6901) points to.
+    ///
+    /// The empty pointer `""` points to the value itself, every other pointer is made of `/`
+    /// separated keys and array indices, with `~0` standing for `~` and `~1` for `/` inside of keys.
+    /// Returns `None` if the pointer is invalid or does not point to an existing value.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let value = mawu!({"users": [{"name": "Ada"}], "a/b": 1});
+    /// assert_eq!(value.pointer("/users/0/name").unwrap(), &MawuValue::from("Ada"));
+    /// assert_eq!(value.pointer("/a~1b").unwrap(), &MawuValue::Uint(1));
+    /// assert_eq!(value.pointer("/users/1"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&MawuValue> {
+        json_pointer::resolve(self, &json_pointer::parse_pointer(pointer).ok()?)
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Returns a mutable reference to the value the JSON Pointer (RFC 6901) points to.
+    /// Works exactly like `pointer` otherwise.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut value = mawu!({"users": [{"name": "Ada"}]});
+    /// *value.pointer_mut("/users/0/name").unwrap() = MawuValue::from("Grace");
+    /// assert_eq!(value.pointer("/users/0/name").unwrap(), &MawuValue::from("Grace"));
+    /// ```
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut MawuValue> {
+        json_pointer::resolve_mut(self, &json_pointer::parse_pointer(pointer).ok()?)
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Returns a reference to the value at the end of the path, or `None` if there is no such value.
+    ///
+    /// Works like `pointer`, but takes the path as `PathSeg`'s instead of a string, so keys never
+    /// have to be escaped and nothing is parsed. A `PathSeg::Key` only steps into objects,
+    /// a `PathSeg::Index` only into arrays. The empty path returns the value itself.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::{MawuValue, PathSeg}};
+    ///
+    /// let value = mawu!({"users": [{"name": "Ada"}], "a/b": 1});
+    /// assert_eq!(value.deep_get(&[PathSeg::Key("users"), PathSeg::Index(0), PathSeg::Key("name")]).unwrap(), &MawuValue::from("Ada"));
+    /// assert_eq!(value.deep_get(&[PathSeg::Key("a/b")]).unwrap(), &MawuValue::Uint(1));
+    /// assert_eq!(value.deep_get(&[PathSeg::Key("users"), PathSeg::Key("0")]), None);
+    /// ```
+    pub fn deep_get(&self, path: &[PathSeg]) -> Option<&MawuValue> {
+        path.iter().try_fold(self, |current, segment| match (current, segment) {
+            (MawuValue::Object(o), PathSeg::Key(key)) => o.get(*key),
+            (MawuValue::Array(a), PathSeg::Index(index)) => a.get(*index),
+            _ => None,
+        })
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Returns a mutable reference to the value at the end of the path.
+    /// Works exactly like `deep_get` otherwise.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::{MawuValue, PathSeg}};
+    ///
+    /// let mut value = mawu!({"users": [{"name": "Ada"}]});
+    /// *value.deep_get_mut(&[PathSeg::Key("users"), PathSeg::Index(0), PathSeg::Key("name")]).unwrap() = MawuValue::from("Grace");
+    /// assert_eq!(value, mawu!({"users": [{"name": "Grace"}]}));
+    /// ```
+    pub fn deep_get_mut(&mut self, path: &[PathSeg]) -> Option<&mut MawuValue> {
+        path.iter().try_fold(self, |current, segment| match (current, segment) {
+            (MawuValue::Object(o), PathSeg::Key(key)) => o.get_mut(*key),
+            (MawuValue::Array(a), PathSeg::Index(index)) => a.get_mut(*index),
+            _ => None,
+        })
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Returns an iterator over this value and every value inside of it, each with its JSON Pointer (RFC 6901).
+    ///
+    /// The values are visited depth first, a container coming before its contents. The value itself
+    /// has the empty pointer `""`. Object keys are visited in sorted order, array elements by index.
+    /// CSV values are yielded without their contents.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let value = mawu!({"users": [{"name": "Ada"}], "count": 1});
+    /// let pointers: Vec<String> = value.walk().map(|(pointer, _)| pointer).collect();
+    /// assert_eq!(pointers, vec!["", "/count", "/users", "/users/0", "/users/0/name"]);
+    ///
+    /// let strings = value.walk().filter(|(_, value)| value.is_string()).count();
+    /// assert_eq!(strings, 1);
+    /// ```
+    pub fn walk(&self) -> impl Iterator<Item = (String, &MawuValue)> {
+        json_pointer::walk(self)
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Sets the value the JSON Pointer (RFC 6901) points to, replacing it if it exists.
+    ///
+    /// Keys missing from an object are inserted, array elements are addressed by their index and
+    /// replaced, and `-` appends to an array. Every value before the last token has to exist.
+    /// The empty pointer `""` replaces the whole value.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut config = mawu!({"users": [{"name": "Ada"}], "list": [1]});
+    /// config.pointer_set("/users/0/name", MawuValue::from("Grace")).unwrap();
+    /// config.pointer_set("/list/-", MawuValue::Uint(2)).unwrap();
+    /// assert_eq!(config, mawu!({"users": [{"name": "Grace"}], "list": [1, 2]}));
+    ///
+    /// assert!(config.pointer_set("/list/5", MawuValue::None).is_err());
+    /// ```
+    ///
+    /// ## Errors
+    /// Returns a `JsonPointerError::InvalidPointer` if the pointer is invalid,
+    /// `JsonPointerError::PathNotFound` if a value on the way is missing or neither an object nor an array,
+    /// and `JsonPointerError::InvalidIndex` if the index is not an element of the array.
+    pub fn pointer_set(&mut self, pointer: &str, value: MawuValue) -> Result<(), MawuError> {
+        json_patch::set(self, pointer, value)
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Removes and returns the object key or array element the JSON Pointer (RFC 6901) points to.
+    /// Later array elements are shifted down. The empty pointer `""` takes the whole value,
+    /// leaving `MawuValue::None` behind.
+    /// Returns `None` if the pointer is invalid or does not point to an existing value.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut config = mawu!({"name": "mawu", "list": [1, 2, 3]});
+    /// assert_eq!(config.pointer_remove("/name"), Some(MawuValue::from("mawu")));
+    /// assert_eq!(config.pointer_remove("/list/0"), Some(MawuValue::Uint(1)));
+    /// assert_eq!(config, mawu!({"list": [2, 3]}));
+    /// assert_eq!(config.pointer_remove("/list/2"), None);
+    /// ```
+    pub fn pointer_remove(&mut self, pointer: &str) -> Option<MawuValue> {
+        json_patch::remove(self, &json_pointer::parse_pointer(pointer).ok()?).ok()
+    }
+
+    /// Works on json values, and not on `MawuValue::CSVArray` or `MawuValue::CSVObject`
+    /// Returns a mutable reference to the value at the given path, creating all objects on the way
+    /// and inserting `MawuValue::None` if the value is missing. The write side counterpart to `pointer`.
+    ///
+    /// The path is written like a JSON Pointer, but every token is an object key, numbers included.
+    /// Any value on the path that is not an object, arrays included, is overwritten with an empty
+    /// object, so this never fails. Use `pointer_mut` to change values inside of arrays.
+    /// The leading `/` is optional, and a `~` not followed by `0` or `1` is kept as is.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut config = mawu!({"server": {"name": "mawu"}});
+    /// *config.ensure_path("/server/tls/port") = MawuValue::Uint(443);
+    /// assert_eq!(config, mawu!({"server": {"name": "mawu", "tls": {"port": 443}}}));
+    ///
+    /// // `name` is not an object, so it is replaced
+    /// *config.ensure_path("/server/name/first") = MawuValue::from("Ada");
+    /// assert_eq!(config.pointer("/server/name"), Some(&mawu!({"first": "Ada"})));
+    /// ```
+    pub fn ensure_path(&mut self, pointer: &str) -> &mut MawuValue {
+        json_pointer::ensure(self, &json_pointer::parse_path(pointer))
+    }
+
+    /// Applies a JSON Patch (RFC 6902) to the value.
+    ///
+    /// The patch has to be a `MawuValue::Array` of operations, each an object with an `op` of
+    /// `add`, `remove`, `replace`, `move`, `copy` or `test`, a `path` and, depending on the operation,
+    /// a `value` or `from`. All paths are JSON Pointers, as used by `pointer`.
+    /// Numbers are compared by their value in `test`, so `1` and `1.0` are equal.
+    ///
+    /// The patch is applied as a whole: if any operation fails, the value is left unchanged.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut value = mawu!({"foo": ["bar", "baz"]});
+    /// let patch = mawu!([
+    ///     {"op": "add", "path": "/foo/1", "value": "qux"},
+    ///     {"op": "remove", "path": "/foo/0"}
+    /// ]);
+    /// value.apply_json_patch(&patch).unwrap();
+    /// assert_eq!(value, mawu!({"foo": ["qux", "baz"]}));
+    /// ```
+    ///
+    /// ## Errors
+    /// Returns a `JsonPatchError` if the patch is malformed or a `test` failed, and a
+    /// `JsonPointerError` if a path is invalid or does not exist.
+    pub fn apply_json_patch(&mut self, patch: &MawuValue) -> Result<(), MawuError> {
+        json_patch::apply_patch(self, patch)
+    }
+
+    /// Returns a JSON Patch (RFC 6902) describing how to turn this value into `other`.
+    ///
+    /// Applying the patch with `apply_json_patch` to this value results in `other`.
+    /// Objects are compared key by key and arrays index by index, with added or removed
+    /// elements at the end of an array. Anything else that differs is replaced as a whole, so
+    /// inserting an element at the start of an array results in a `replace` of every following element.
+    /// CSV values are always replaced as a whole.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut old = mawu!({"name": "mawu", "version": 1, "tags": ["json"]});
+    /// let new = mawu!({"name": "mawu", "version": 2, "tags": ["json", "csv"]});
+    /// let patch = old.diff(&new);
+    /// assert_eq!(patch, mawu!([
+    ///     {"op": "add", "path": "/tags/-", "value": "csv"},
+    ///     {"op": "replace", "path": "/version", "value": 2}
+    /// ]));
+    /// old.apply_json_patch(&patch).unwrap();
+    /// assert_eq!(old, new);
+    /// ```
+    pub fn diff(&self, other: &MawuValue) -> MawuValue {
+        json_patch::diff(self, other)
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Inserts the given value at the given index.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// array.array_insert(0, MawuValue::from(0));
+    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(0), MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]));
+    /// ```
+    pub fn array_insert(&mut self, index: usize, value: MawuValue) {
+        match self {
+            MawuValue::Array(v) => v.insert(index, value),
+            MawuValue::CSVArray(v) => v[index].push(value),
+            _ => {}
+        }
+    }
+
+    /// Returns a mutable reference to the object, turning a `MawuValue::None` into an empty `MawuValue::Object` first.
+    ///
+    /// This allows building nested objects without creating every level up front.
+    /// Returns `None`, leaving the value unchanged, for any type other than `MawuValue::Object` and `MawuValue::None`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut value = MawuValue::None;
+    /// value.get_or_insert_object().unwrap().insert("a".to_string(), MawuValue::Uint(1));
+    /// value.get_or_insert_object().unwrap().insert("b".to_string(), MawuValue::Uint(2));
+    /// assert_eq!(value, mawu!({"a": 1, "b": 2}));
+    /// assert!(MawuValue::from(1).get_or_insert_object().is_none());
+    /// ```
+    pub fn get_or_insert_object(&mut self) -> Option<&mut MawuObject> {
+        if self.is_none() {
+            *self = MawuValue::new_object();
+        }
+        match self {
+            MawuValue::Object(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the array, turning a `MawuValue::None` into an empty `MawuValue::Array` first.
+    ///
+    /// Works exactly like `get_or_insert_object`, returning `None` for any type other than `MawuValue::Array` and `MawuValue::None`.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut value = mawu!({"list": null});
+    /// let list = value.pointer_mut("/list").unwrap().get_or_insert_array().unwrap();
+    /// list.push(MawuValue::from("first"));
+    /// assert_eq!(value, mawu!({"list": ["first"]}));
+    /// assert!(MawuValue::new_object().get_or_insert_array().is_none());
+    /// ```
+    pub fn get_or_insert_array(&mut self) -> Option<&mut Vec<MawuValue>> {
+        if self.is_none() {
+            *self = MawuValue::new_array();
+        }
+        match self {
+            MawuValue::Array(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// Inserts the given value with the given key.
+    ///
+    /// ## Returns
+    /// Returns `Some(MawuValue)` if the key already existed. The value was replaced and returned.
+    /// Returns `None` if the key did not exist.
+    /// Returns `Some(MawuValue)` if the `MawuValue` was not an `MawuValue::Object`. The `MawuValue` passed into the function was returned.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
+    /// object.object_insert("key4", MawuValue::from(10));
+    /// assert_eq!(object.get("key4").unwrap(), &MawuValue::from(10));
+    /// ```
+    pub fn object_insert<S: Into<String>, M: Into<MawuValue>>(
+        &mut self,
+        key: S,
+        value: M,
+    ) -> Option<MawuValue> {
+        match self {
+            MawuValue::Object(v) => {
+                let tmp = v.insert(key.into(), value.into());
+                if tmp.is_none() {
+                    None
+                } else {
+                    Some(tmp.unwrap())
+                }
+            }
+            _ => Some(value.into()),
+        }
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Removes the value at the given index and returns it.
+    /// The same restricitions as `Vec::remove` apply, as this is just a convenience function
+    /// calling it.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// assert_eq!(array.array_remove(1), Some(MawuValue::from(2)));
+    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(1), MawuValue::from(3)]));
+    /// ```
+    pub fn array_remove(&mut self, index: usize) -> Option<MawuValue> {
+        match self {
+            MawuValue::Array(v) => Some(v.remove(index)),
+            _ => None,
+        }
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Returns a reference to the value at the given index.
+    /// The same restricitions as `Vec::get` apply, as this is just a convenience function
+    /// calling it.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// assert_eq!(array.array_peek(1).unwrap(), &MawuValue::from(2));
+    /// assert_eq!(array.array_peek(3), None);
+    /// ```
+    pub fn array_peek(&self, index: usize) -> Option<&MawuValue> {
+        match self {
+            MawuValue::Array(v) => {
+                if index < v.len() {
+                    v.get(index)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+
+    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// Removes the value with the given key and returns it.
+    /// The same restricitions as `HashMap::remove` apply, as this is just a convenience function
+    /// calling it.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
+    /// assert_eq!(object.object_remove("key2"), Some(MawuValue::from(2)));
+    /// assert_eq!(object, MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key3".to_string(), MawuValue::from(3))]));
+    /// ```
+    pub fn object_remove<S: Into<String>>(&mut self, key: S) -> Option<MawuValue> {
+        match self {
+            MawuValue::Object(v) => v.remove(key.into().as_str()),
+            _ => None,
+        }
+    }
+
+    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// Checks if the object contains the given key
+    ///
+    /// ## Example
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
+    /// assert!(object.has_key("key1"));
+    /// assert!(!object.has_key("key4"));
+    /// ```
+    pub fn has_key<S: Into<String>>(&self, key: S) -> bool {
         match self {
             MawuValue::Object(v) => v.contains_key(key.into().as_str()),
             _ => false,
         }
     }
 
-    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
-    /// Removes and returns the last element of the array
+    /// Works on json objects `MawuValue::Object`, and not on `MawuValue::CSVObject`
+    /// Checks if the object contains the given key, exactly like `has_key` but without allocating a `String` for the key
+    ///
+    /// Returns `false` for all other variants.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let object = mawu!({"key1": 1, "key2": null});
+    /// assert!(object.contains_key("key2"));
+    /// assert!(!object.contains_key("key3"));
+    /// assert!(!mawu!(["key1"]).contains_key("key1"));
+    /// ```
+    pub fn contains_key(&self, key: &str) -> bool {
+        match self {
+            MawuValue::Object(v) => v.contains_key(key),
+            _ => false,
+        }
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Removes and returns the last element of the array
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// assert_eq!(array.pop(), Some(MawuValue::from(3)));
+    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2)]));
+    /// ```
+    pub fn pop(&mut self) -> Option<MawuValue> {
+        match self {
+            MawuValue::Array(v) => v.pop(),
+            _ => None,
+        }
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Appends the given value to the array
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// array.push(MawuValue::from(4));
+    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3), MawuValue::from(4)]));
+    /// ```
+    pub fn push<M: Into<MawuValue>>(&mut self, value: M) {
+        match self {
+            MawuValue::Array(v) => v.push(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
+    /// Checks if the array contains the given value
+    ///
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// assert!(array.contains(&MawuValue::from(2)));
+    /// assert!(!array.contains(&MawuValue::from(4)));
+    /// ```
+    pub fn contains<M: Into<MawuValue>>(&self, value: M) -> bool {
+        match self {
+            MawuValue::Array(v) => v.contains(&value.into()),
+            _ => false,
+        }
+    }
+
+    /// Returns the length of the value
+    ///
+    /// Returns 0 if the value is `None`, `Bool`, `Uint`, `Int` or `Float`
+    /// ## Example
+    /// ```rust
+    /// use mawu::mawu_value::MawuValue;
+    ///
+    /// let array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
+    /// assert_eq!(array.len(), 3);
+    /// let object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
+    /// assert_eq!(object.len(), 3);
+    /// let none = MawuValue::None;
+    /// assert_eq!(none.len(), 0);
+    /// let bool = MawuValue::from(true);
+    /// assert_eq!(bool.len(), 0);
+    /// let uint = MawuValue::from(123);
+    /// assert_eq!(uint.len(), 0);
+    /// let string = MawuValue::from("string");
+    /// assert_eq!(string.len(), 6);
+    /// ```
+    pub fn len(&self) -> usize {
+        match self {
+            MawuValue::CSVObject(v) => v.len(),
+            MawuValue::CSVArray(v) => v.len(),
+            MawuValue::Array(v) => v.len(),
+            MawuValue::Object(v) => v.len(),
+            MawuValue::None => 0,
+            MawuValue::Bool(_) => 0,
+            MawuValue::Uint(_) => 0,
+            MawuValue::Int(_) => 0,
+            MawuValue::Float(_) => 0,
+            MawuValue::String(v) => v.len(),
+        }
+    }
+
+    /// Returns every value stored under the given key, no matter how deeply nested.
+    ///
+    /// The tree is walked depth first, so a matching value comes before any matches nested inside it,
+    /// and the elements of arrays and rows of CSV data are visited in order.
+    /// The order between the key-value-pairs of the same object is not defined, as objects are unordered.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let response = mawu!({"id": 1, "items": [{"id": 2}, {"name": "no id"}, {"id": 3}]});
+    /// let ids = response.find_all("id");
+    /// assert_eq!(ids.len(), 3);
+    /// ```
+    pub fn find_all(&self, key: &str) -> Vec<&MawuValue> {
+        let mut out = Vec::new();
+        self.find_all_into(key, &mut out);
+        out
+    }
+
+    /// Replaces every value stored under a key matching `predicate` with a clone of `replacement`, no matter how deeply nested.
+    ///
+    /// Matching values are replaced as a whole, so nothing inside of them is left, even if it does not match.
+    /// The columns of `MawuValue::CSVObject` rows are matched like object keys.
+    /// Useful to hide secrets before logging a parsed document.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// let mut request = mawu!({"user": "ada", "auth": {"password": "hunter2", "api_token": "abc"}});
+    /// request.redact(|key| key == "password" || key.contains("token"), MawuValue::from("***"));
+    /// assert_eq!(request, mawu!({"user": "ada", "auth": {"password": "***", "api_token": "***"}}));
+    /// ```
+    pub fn redact(&mut self, predicate: impl Fn(&str) -> bool, replacement: MawuValue) {
+        self.redact_with(&predicate, &replacement);
+    }
+
+    /// Returns the deepest level of nesting.
+    /// Primitive types have a depth of 0, every array or object adds one level, so `[]` has a depth of 1 and `[{"a": 1}]` a depth of 2.
+    /// The rows of `MawuValue::CSVArray` and `MawuValue::CSVObject` count as a level of their own.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// assert_eq!(MawuValue::from(1).depth(), 0);
+    /// assert_eq!(mawu!({"a": [1, [2]]}).depth(), 3);
+    /// ```
+    pub fn depth(&self) -> usize {
+        match self {
+            MawuValue::Object(v) => 1 + v.values().map(MawuValue::depth).max().unwrap_or(0),
+            MawuValue::Array(v) => 1 + v.iter().map(MawuValue::depth).max().unwrap_or(0),
+            MawuValue::CSVObject(v) => {
+                1 + v
+                    .iter()
+                    .map(|row| 1 + row.values().map(MawuValue::depth).max().unwrap_or(0))
+                    .max()
+                    .unwrap_or(0)
+            }
+            MawuValue::CSVArray(v) => {
+                1 + v
+                    .iter()
+                    .map(|row| 1 + row.iter().map(MawuValue::depth).max().unwrap_or(0))
+                    .max()
+                    .unwrap_or(0)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Returns the total number of values, counting every array, object and primitive value, including `self`.
+    /// The rows of `MawuValue::CSVArray` and `MawuValue::CSVObject` are counted as values as well, keys are not.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// assert_eq!(MawuValue::from(1).node_count(), 1);
+    /// // the object, the array and its two elements
+    /// assert_eq!(mawu!({"a": [1, 2]}).node_count(), 4);
+    /// ```
+    pub fn node_count(&self) -> usize {
+        match self {
+            MawuValue::Object(v) => 1 + v.values().map(MawuValue::node_count).sum::<usize>(),
+            MawuValue::Array(v) => 1 + v.iter().map(MawuValue::node_count).sum::<usize>(),
+            MawuValue::CSVObject(v) => {
+                1 + v
+                    .iter()
+                    .map(|row| 1 + row.values().map(MawuValue::node_count).sum::<usize>())
+                    .sum::<usize>()
+            }
+            MawuValue::CSVArray(v) => {
+                1 + v
+                    .iter()
+                    .map(|row| 1 + row.iter().map(MawuValue::node_count).sum::<usize>())
+                    .sum::<usize>()
+            }
+            _ => 1,
+        }
+    }
+
+    /// Returns an estimate of the bytes allocated on the heap by this value and everything inside of it.
+    ///
+    /// Counts the capacity of every `String` and `Vec`, strings and keys included, and the entries of
+    /// objects and CSV rows at the size of a key-value-pair each. The spare capacity and bookkeeping of
+    /// maps is not counted, nor is the size of `self`, which lives wherever the value is stored.
+    /// Primitive values have a heap size of 0. Useful to bound caches of parsed documents.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu, mawu_value::MawuValue};
+    ///
+    /// assert_eq!(MawuValue::from(1).heap_size(), 0);
+    /// assert_eq!(MawuValue::String(String::with_capacity(16)).heap_size(), 16);
+    /// assert!(mawu!({"a": [1, 2, 3]}).heap_size() > mawu!({"a": [1]}).heap_size());
+    /// ```
+    pub fn heap_size(&self) -> usize {
+        match self {
+            MawuValue::String(s) => s.capacity(),
+            MawuValue::Object(v) => map_heap_size(v.iter()),
+            MawuValue::Array(v) => vec_heap_size(v),
+            MawuValue::CSVObject(v) => {
+                v.capacity() * size_of::<HashMap<String, MawuValue>>() + v.iter().map(|row| map_heap_size(row.iter())).sum::<usize>()
+            }
+            MawuValue::CSVArray(v) => v.capacity() * size_of::<Vec<MawuValue>>() + v.iter().map(vec_heap_size).sum::<usize>(),
+            _ => 0,
+        }
+    }
+
+    /// Works only on `MawuValue::CSVObject`
+    /// Returns the values of the column with the given name, in row order.
+    ///
+    /// Rows missing the column contribute a `MawuValue::None`, so the n-th value always belongs to the n-th row.
+    /// Returns `None` if the value is not a `MawuValue::CSVObject` or no row has the column.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::{mawu_value::MawuValue, read::csv_headed};
+    ///
+    /// let csv = csv_headed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// let names = csv.csv_column("name").unwrap();
+    /// assert_eq!(names, vec![&MawuValue::from("Ada"), &MawuValue::from("Bob")]);
+    /// assert_eq!(csv.csv_column("email"), None);
+    /// ```
+    pub fn csv_column(&self, name: &str) -> Option<Vec<&MawuValue>> {
+        match self {
+            MawuValue::CSVObject(v) if v.iter().any(|row| row.contains_key(name)) => {
+                Some(v.iter().map(|row| row.get(name).unwrap_or(&NONE)).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Works only on `MawuValue::CSVObject`
+    /// Returns the count, sum, mean, min and max of the numeric values in the column with the given name.
+    ///
+    /// Values are converted with `to_float`, anything that can not be converted, like strings or
+    /// empty values, is skipped and counted in `ColumnStats::skipped`.
+    /// Returns `None` if the value is not a `MawuValue::CSVObject`, no row has the column or the column holds no numeric value.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::read::csv_headed;
+    ///
+    /// let csv = csv_headed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// let ages = csv.csv_column_stats("age").unwrap();
+    /// assert_eq!(ages.count, 2);
+    /// assert_eq!(ages.mean, 39.0);
+    /// assert_eq!(ages.max, 42.0);
+    /// assert!(csv.csv_column_stats("name").is_none());
+    /// ```
+    pub fn csv_column_stats(&self, name: &str) -> Option<ColumnStats> {
+        let column = self.csv_column(name)?;
+        let numbers = column.iter().filter_map(|value| value.to_float()).collect::<Vec<f64>>();
+        if numbers.is_empty() {
+            return None;
+        }
+        let sum = numbers.iter().sum::<f64>();
+        Some(ColumnStats {
+            count: numbers.len(),
+            skipped: column.len() - numbers.len(),
+            sum,
+            mean: sum / numbers.len() as f64,
+            min: numbers.iter().copied().fold(f64::INFINITY, f64::min),
+            max: numbers.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+
+    /// Works only on `MawuValue::CSVObject`
+    /// Returns the values of the column with the given name converted with `to_float`, in row order.
     ///
-    /// ## Example
+    /// Values that can not be converted, like strings or empty values, are handled according to `strict`:
+    /// If `true`, a single one of them makes this function return `None`.
+    /// If `false`, they are replaced by `f64::NAN`, so the n-th value still belongs to the n-th row.
+    /// Returns `None` as well if the value is not a `MawuValue::CSVObject` or no row has the column.
+    ///
+    /// ## Examples
     /// ```rust
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::read::csv_headed;
     ///
-    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// assert_eq!(array.pop(), Some(MawuValue::from(3)));
-    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2)]));
+    /// let csv = csv_headed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// assert_eq!(csv.csv_column_f64("age", true).unwrap(), vec![36.0, 42.0]);
+    /// assert!(csv.csv_column_f64("name", true).is_none());
+    /// assert!(csv.csv_column_f64("name", false).unwrap().iter().all(|v| v.is_nan()));
     /// ```
-    pub fn pop(&mut self) -> Option<MawuValue> {
-        match self {
-            MawuValue::Array(v) => v.pop(),
-            _ => None,
+    pub fn csv_column_f64(&self, name: &str, strict: bool) -> Option<Vec<f64>> {
+        let column = self.csv_column(name)?;
+        if strict {
+            column.iter().map(|value| value.to_float()).collect()
+        } else {
+            Some(column.iter().map(|value| value.to_float().unwrap_or(f64::NAN)).collect())
         }
     }
 
-    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
-    /// Appends the given value to the array
+    /// Works only on `MawuValue::CSVObject`
+    /// Groups the rows by the value of the column with the given name, converted with `to_string`.
     ///
-    /// ## Example
+    /// Each group keeps the rows in their original order.
+    /// Rows missing the column, or with an empty value in it, are grouped under the empty string `""`.
+    /// Returns `None` if the value is not a `MawuValue::CSVObject` or no row has the column.
+    ///
+    /// ## Examples
     /// ```rust
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::read::csv_headed;
     ///
-    /// let mut array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// array.push(MawuValue::from(4));
-    /// assert_eq!(array, MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3), MawuValue::from(4)]));
+    /// let csv = csv_headed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// let by_city = csv.csv_group_by("city").unwrap();
+    /// assert_eq!(by_city.len(), 2);
+    /// assert_eq!(by_city["Paris"].len(), 1);
     /// ```
-    pub fn push<M: Into<MawuValue>>(&mut self, value: M) {
+    pub fn csv_group_by(&self, key: &str) -> Option<HashMap<String, Vec<HashMap<String, MawuValue>>>> {
         match self {
-            MawuValue::Array(v) => v.push(value.into()),
-            _ => {}
+            MawuValue::CSVObject(v) if v.iter().any(|row| row.contains_key(key)) => {
+                let mut groups: HashMap<String, Vec<HashMap<String, MawuValue>>> = HashMap::new();
+                for row in v {
+                    let group = row.get(key).map(MawuValue::to_string).unwrap_or_default();
+                    groups.entry(group).or_default().push(row.clone());
+                }
+                Some(groups)
+            }
+            _ => None,
         }
     }
 
-    /// Works on json arrays `MawuValue::Array`, and not on `MawuValue::CSVArray`
-    /// Checks if the array contains the given value
+    /// Works only on `MawuValue::CSVArray`
+    /// Returns the values of the column at the given index, in row order.
+    /// The first row of the file is included, as a headless file has no header to skip.
     ///
-    /// ## Example
+    /// Rows too short to have the column contribute a `MawuValue::None`, so the n-th value always belongs to the n-th row.
+    /// Returns `None` if the value is not a `MawuValue::CSVArray` or no row has the column.
+    ///
+    /// ## Examples
     /// ```rust
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::{mawu_value::MawuValue, read::csv_headless};
     ///
-    /// let array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// assert!(array.contains(&MawuValue::from(2)));
-    /// assert!(!array.contains(&MawuValue::from(4)));
+    /// let csv = csv_headless("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// let names = csv.csv_column_index(0).unwrap();
+    /// assert_eq!(names, vec![&MawuValue::from("name"), &MawuValue::from("Ada"), &MawuValue::from("Bob")]);
+    /// assert_eq!(csv.csv_column_index(3), None);
     /// ```
-    pub fn contains<M: Into<MawuValue>>(&self, value: M) -> bool {
+    pub fn csv_column_index(&self, index: usize) -> Option<Vec<&MawuValue>> {
         match self {
-            MawuValue::Array(v) => v.contains(&value.into()),
-            _ => false,
+            MawuValue::CSVArray(v) if v.iter().any(|row| row.len() > index) => {
+                Some(v.iter().map(|row| row.get(index).unwrap_or(&NONE)).collect())
+            }
+            _ => None,
         }
     }
 
-    /// Returns the length of the value
+    /// Works only on `MawuValue::Array`
+    /// Converts an array of objects, like most JSON APIs return, into a `MawuValue::CSVObject` with one row per object.
     ///
-    /// Returns 0 if the value is `None`, `Bool`, `Uint`, `Int` or `Float`
-    /// ## Example
+    /// The objects do not need to have the same keys: The columns are the union of all keys, and objects missing
+    /// a key get a `MawuValue::None` in that column, so every row has every column.
+    /// As the rows are maps, they do not keep an order of the columns. Written out, the columns are in the order
+    /// the first row iterates them, which is sorted by name with the `btreemap` feature or without `std`.
+    /// Values are cloned as they are, nested objects make writing the result fail with a `CsvWriteError::UnallowedType`.
+    /// Returns `None` if the value is not a `MawuValue::Array` or any of its elements is not a `MawuValue::Object`.
+    ///
+    /// ## Examples
     /// ```rust
-    /// use mawu::mawu_value::MawuValue;
+    /// use mawu::{mawu, mawu_value::MawuValue};
     ///
-    /// let array = MawuValue::from(vec![MawuValue::from(1), MawuValue::from(2), MawuValue::from(3)]);
-    /// assert_eq!(array.len(), 3);
-    /// let object = MawuValue::from(vec![("key1".to_string(), MawuValue::from(1)), ("key2".to_string(), MawuValue::from(2)), ("key3".to_string(), MawuValue::from(3))]);
-    /// assert_eq!(object.len(), 3);
-    /// let none = MawuValue::None;
-    /// assert_eq!(none.len(), 0);
-    /// let bool = MawuValue::from(true);
-    /// assert_eq!(bool.len(), 0);
-    /// let uint = MawuValue::from(123);
-    /// assert_eq!(uint.len(), 0);
-    /// let string = MawuValue::from("string");
-    /// assert_eq!(string.len(), 6);
+    /// let people = mawu!([{"name": "Ada", "age": 36}, {"name": "Bob", "city": "Paris"}]);
+    /// let csv = people.to_csv_rows().unwrap();
+    /// assert_eq!(csv.csv_column("city").unwrap(), vec![&MawuValue::None, &MawuValue::from("Paris")]);
+    /// assert_eq!(csv.as_csv_object().unwrap()[0].len(), 3);
+    /// assert_eq!(mawu!([1, {"a": 2}]).to_csv_rows(), None);
     /// ```
-    pub fn len(&self) -> usize {
+    pub fn to_csv_rows(&self) -> Option<MawuValue> {
+        let objects = self.as_array()?.iter().map(MawuValue::as_object).collect::<Option<Vec<&MawuObject>>>()?;
+        let mut header: Vec<&String> = objects.iter().flat_map(|object| object.keys()).collect();
+        header.sort();
+        header.dedup();
+        Some(MawuValue::CSVObject(
+            objects
+                .iter()
+                .map(|object| {
+                    header
+                        .iter()
+                        .map(|name| ((*name).clone(), object.get(*name).cloned().unwrap_or_default()))
+                        .collect()
+                })
+                .collect(),
+        ))
+    }
+
+    /// Works only on `MawuValue::CSVObject` and `MawuValue::CSVArray`
+    /// Renders the rows as a human-readable text table, with every column padded to its widest cell, like `column -t`.
+    ///
+    /// A `MawuValue::CSVObject` gets a header row and a separator row, with the columns sorted by name, as the rows do not keep the order of the header.
+    /// A `MawuValue::CSVArray` has no header, so all rows are rendered as they are.
+    /// Cells are converted with `to_string`, so `MawuValue::None` results in an empty cell, and lines are separated by `\n`.
+    /// Returns `None` for all other variants.
+    ///
+    /// ## Examples
+    /// ```rust
+    /// use mawu::read::csv_headed;
+    ///
+    /// let csv = csv_headed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+    /// assert_eq!(csv.to_pretty_table().unwrap(), [
+    ///     "age  city        name",
+    ///     "---  ----------  ----",
+    ///     "36   London, UK  Ada",
+    ///     "42   Paris       Bob",
+    /// ].join("\n"));
+    /// ```
+    pub fn to_pretty_table(&self) -> Option<String> {
+        let rows: Vec<Vec<String>> = match self {
+            MawuValue::CSVObject(v) => {
+                let mut header: Vec<&String> = v.iter().flat_map(|row| row.keys()).collect();
+                header.sort();
+                header.dedup();
+                let mut rows = vec![header.iter().map(|name| name.to_string()).collect::<Vec<String>>()];
+                rows.push(Vec::new());
+                rows.extend(v.iter().map(|row| {
+                    header
+                        .iter()
+                        .map(|name| row.get(*name).map(MawuValue::to_string).unwrap_or_default())
+                        .collect()
+                }));
+                rows
+            }
+            MawuValue::CSVArray(v) => v.iter().map(|row| row.iter().map(MawuValue::to_string).collect()).collect(),
+            _ => return None,
+        };
+        let mut widths: Vec<usize> = Vec::new();
+        for row in &rows {
+            for (index, cell) in row.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(index) {
+                    Some(w) => *w = (*w).max(width),
+                    None => widths.push(width),
+                }
+            }
+        }
+        let lines: Vec<String> = rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let line = if index == 1 && self.is_csv_object() {
+                    // the separator row below the header
+                    widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<String>>().join("  ")
+                } else {
+                    row.iter()
+                        .zip(&widths)
+                        .map(|(cell, w)| format!("{}{}", cell, " ".repeat(w - cell.chars().count())))
+                        .collect::<Vec<String>>()
+                        .join("  ")
+                };
+                // the last column is not padded
+                line.trim_end().to_string()
+            })
+            .collect();
+        Some(lines.join("\n"))
+    }
+
+    fn redact_with<F: Fn(&str) -> bool>(&mut self, predicate: &F, replacement: &MawuValue) {
         match self {
-            MawuValue::CSVObject(v) => v.len(),
-            MawuValue::CSVArray(v) => v.len(),
-            MawuValue::Array(v) => v.len(),
-            MawuValue::Object(v) => v.len(),
-            MawuValue::None => 0,
-            MawuValue::Bool(_) => 0,
-            MawuValue::Uint(_) => 0,
-            MawuValue::Int(_) => 0,
-            MawuValue::Float(_) => 0,
-            MawuValue::String(v) => v.len(),
+            MawuValue::Object(v) => redact_in_object(v.iter_mut(), predicate, replacement),
+            MawuValue::CSVObject(v) => {
+                for row in v {
+                    redact_in_object(row.iter_mut(), predicate, replacement);
+                }
+            }
+            MawuValue::Array(v) => {
+                for value in v {
+                    value.redact_with(predicate, replacement);
+                }
+            }
+            MawuValue::CSVArray(v) => {
+                for value in v.iter_mut().flatten() {
+                    value.redact_with(predicate, replacement);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn find_all_into<'a>(&'a self, key: &str, out: &mut Vec<&'a MawuValue>) {
+        match self {
+            MawuValue::Object(v) => find_all_in_object(v, key, out),
+            MawuValue::CSVObject(v) => {
+                for row in v {
+                    find_all_in_object(row, key, out);
+                }
+            }
+            MawuValue::Array(v) => {
+                for value in v {
+                    value.find_all_into(key, out);
+                }
+            }
+            MawuValue::CSVArray(v) => {
+                for value in v.iter().flatten() {
+                    value.find_all_into(key, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Stands in for missing CSV values, so references to it can be returned
+static NONE: MawuValue = MawuValue::None;
+
+fn find_all_in_object<'a>(object: impl IntoIterator<Item = (&'a String, &'a MawuValue)>, key: &str, out: &mut Vec<&'a MawuValue>) {
+    for (k, value) in object {
+        if k == key {
+            out.push(value);
+        }
+        value.find_all_into(key, out);
+    }
+}
+
+fn redact_in_object<'a, F: Fn(&str) -> bool>(object: impl IntoIterator<Item = (&'a String, &'a mut MawuValue)>, predicate: &F, replacement: &MawuValue) {
+    for (key, value) in object {
+        if predicate(key) {
+            *value = replacement.clone();
+        } else {
+            value.redact_with(predicate, replacement);
         }
     }
+}
+
+/// Compares the pairs of two objects or CSV rows for `eq_ignoring_nulls`
+fn entries_eq_ignoring_nulls<'a>(a: impl Iterator<Item = (&'a String, &'a MawuValue)>, b: impl Iterator<Item = (&'a String, &'a MawuValue)>) -> bool {
+    let (a, b) = (present_entries(a), present_entries(b));
+    a.len() == b.len() && a.iter().zip(&b).all(|((ka, va), (kb, vb))| ka == kb && va.eq_ignoring_nulls(vb))
+}
+
+/// Returns the pairs not mapped to `MawuValue::None`, sorted by key, as the iteration order of a `HashMap` differs between maps
+fn present_entries<'a>(entries: impl Iterator<Item = (&'a String, &'a MawuValue)>) -> Vec<(&'a String, &'a MawuValue)> {
+    let mut present = entries.filter(|(_, value)| !value.is_none()).collect::<Vec<_>>();
+    present.sort_by(|a, b| a.0.cmp(b.0));
+    present
+}
+
+fn vec_heap_size(values: &Vec<MawuValue>) -> usize {
+    values.capacity() * size_of::<MawuValue>() + values.iter().map(MawuValue::heap_size).sum::<usize>()
+}
+
+fn map_heap_size<'a>(object: impl Iterator<Item = (&'a String, &'a MawuValue)>) -> usize {
+    object.map(|(key, value)| size_of::<(String, MawuValue)>() + key.capacity() + value.heap_size()).sum()
+}
 
+#[test]
+fn find_all_nested() {
+    let value = crate::mawu!({
+        "id": 1,
+        "data": [
+            {"id": 2, "children": [{"id": 3}, {"name": "none"}]},
+            [[{"id": 4}]],
+            {"id": {"id": 5}}
+        ],
+        "meta": {"nothing": null}
+    });
+    let mut ids = value
+        .find_all("id")
+        .iter()
+        .filter_map(|v| v.to_int())
+        .collect::<Vec<i64>>();
+    ids.sort();
+    assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+    // the object under "id" is found as well, before the id nested inside it
+    let found = value.find_all("id");
+    assert_eq!(found.len(), 6);
+    let outer = found.iter().position(|v| v.is_object()).unwrap();
+    let inner = found.iter().position(|v| *v == &MawuValue::Uint(5)).unwrap();
+    assert!(outer < inner);
+
+    let in_order = crate::mawu!([{"n": 1}, {"n": 2}, {"n": 3}]);
+    assert_eq!(in_order.find_all("n"), vec![&MawuValue::Uint(1), &MawuValue::Uint(2), &MawuValue::Uint(3)]);
+    assert!(MawuValue::from(1).find_all("n").is_empty());
+}
+
+#[test]
+fn redact_nested_passwords() {
+    let mut value = crate::mawu!({
+        "password": "a",
+        "user": {"name": "ada", "password": "b", "sessions": [{"password": "c", "id": 1}, [{"password": {"nested": "d"}}]]},
+        "passwords": ["e"]
+    });
+    value.redact(|key| key == "password", MawuValue::None);
+    assert_eq!(value, crate::mawu!({
+        "password": null,
+        "user": {"name": "ada", "password": null, "sessions": [{"password": null, "id": 1}, [{"password": null}]]},
+        "passwords": ["e"]
+    }));
+
+    let mut csv = MawuValue::CSVObject(vec![HashMap::from([("password".to_string(), MawuValue::from("f")), ("id".to_string(), MawuValue::from(1))])]);
+    csv.redact(|key| key.contains("pass"), MawuValue::from("***"));
+    assert_eq!(csv.as_csv_object().unwrap()[0].get("password"), Some(&MawuValue::from("***")));
+}
+
+#[test]
+fn pretty_table_alignment() {
+    let mut short = HashMap::new();
+    short.insert("id".to_string(), MawuValue::from(1));
+    short.insert("description".to_string(), MawuValue::from("a"));
+    let mut long = HashMap::new();
+    long.insert("id".to_string(), MawuValue::from(10000));
+    long.insert("description".to_string(), MawuValue::None);
+    long.insert("z".to_string(), MawuValue::from(true));
+    let csv = MawuValue::CSVObject(vec![short, long]);
+    assert_eq!(csv.to_pretty_table().unwrap(), [
+        "description  id     z",
+        "-----------  -----  ----",
+        "a            1",
+        "             10000  true",
+    ].join("\n"));
+
+    let csv = MawuValue::CSVArray(vec![
+        vec![MawuValue::from("äöü"), MawuValue::from(-1.5)],
+        vec![MawuValue::from("x"), MawuValue::from("long cell"), MawuValue::from(1)],
+    ]);
+    assert_eq!(csv.to_pretty_table().unwrap(), "äöü  -1.5\nx    long cell  1");
+    assert_eq!(MawuValue::from("not a table").to_pretty_table(), None);
+    assert_eq!(crate::mawu!({"a": 1}).to_pretty_table(), None);
+}
+
+#[test]
+fn depth_and_node_count() {
+    let value = crate::mawu!({
+        "a": 1,
+        "b": [true, {"c": [null, "d"]}],
+        "e": {}
+    });
+    // object > array > object > array > primitive
+    assert_eq!(value.depth(), 4);
+    // the outer object, "a", "b", true, the inner object, "c", null, "d" and "e"
+    assert_eq!(value.node_count(), 9);
+    assert_eq!(MawuValue::new_array().depth(), 1);
+    assert_eq!(MawuValue::new_array().node_count(), 1);
+    assert_eq!(MawuValue::None.depth(), 0);
+
+    let csv = MawuValue::CSVArray(vec![vec![MawuValue::from(1), MawuValue::from(2)], vec![MawuValue::from(3)]]);
+    assert_eq!(csv.depth(), 2);
+    assert_eq!(csv.node_count(), 6);
+}
+
+#[test]
+fn eq_ignoring_nulls_only_on_request() {
+    let with_null = crate::mawu!({"a": null});
+    let empty = crate::mawu!({});
+    assert_ne!(with_null, empty);
+    assert!(with_null.eq_ignoring_nulls(&empty));
+    assert!(empty.eq_ignoring_nulls(&with_null));
+
+    let nested = crate::mawu!({"a": 1, "b": [{"c": null, "d": 2}, null], "e": {"f": null}});
+    assert!(nested.eq_ignoring_nulls(&crate::mawu!({"b": [{"d": 2}, null], "a": 1, "e": {}})));
+    assert!(!nested.eq_ignoring_nulls(&crate::mawu!({"a": 1, "b": [{"d": 2}], "e": {}})));
+    assert!(!nested.eq_ignoring_nulls(&crate::mawu!({"a": 1, "b": [{"d": 2}, null]})));
+    assert!(!crate::mawu!({"a": null}).eq_ignoring_nulls(&crate::mawu!({"a": 0})));
+
+    let row = |pairs: Vec<(&str, MawuValue)>| pairs.into_iter().map(|(k, v)| (k.to_string(), v)).collect::<HashMap<String, MawuValue>>();
+    let csv = MawuValue::CSVObject(vec![row(vec![("a", MawuValue::from(1)), ("b", MawuValue::None)])]);
+    assert!(csv.eq_ignoring_nulls(&MawuValue::CSVObject(vec![row(vec![("a", MawuValue::from(1))])])));
+}
+
+#[test]
+fn heap_size_grows_with_content() {
+    let small = crate::mawu!({"name": "mawu", "tags": ["json"]});
+    let large = crate::mawu!({"name": "mawu", "tags": ["json", "csv"], "description": "a parser for JSON and CSV", "users": [{"name": "ada"}]});
+    assert!(large.heap_size() > small.heap_size());
+    assert_eq!(MawuValue::None.heap_size(), 0);
+    assert_eq!(MawuValue::Array(Vec::with_capacity(4)).heap_size(), 4 * size_of::<MawuValue>());
+
+    let csv = MawuValue::CSVArray(vec![vec![MawuValue::from("a")], vec![MawuValue::from("bc")]]);
+    let longer = MawuValue::CSVArray(vec![vec![MawuValue::from("a")], vec![MawuValue::from("bc"), MawuValue::from("def")]]);
+    assert!(longer.heap_size() > csv.heap_size());
 }
 
 // While not 100% test coverage, it's a decent sanity check
 
+#[test]
+fn coerce_matrix() {
+    use MawuScalarType as T;
+    // string -> number
+    assert_eq!(MawuValue::String("42".to_string()).coerce_to(T::Uint), Some(MawuValue::Uint(42)));
+    assert_eq!(MawuValue::String(" -42 ".to_string()).coerce_to(T::Int), Some(MawuValue::Int(-42)));
+    assert_eq!(MawuValue::String("-42".to_string()).coerce_to(T::Uint), None);
+    assert_eq!(MawuValue::String("1.5".to_string()).coerce_to(T::Float), Some(MawuValue::Float(1.5)));
+    assert_eq!(MawuValue::String("1.5".to_string()).coerce_to(T::Int), None);
+    assert_eq!(MawuValue::String("inf".to_string()).coerce_to(T::Float), None);
+    assert_eq!(MawuValue::String("NaN".to_string()).coerce_to(T::Float), None);
+    assert_eq!(MawuValue::String("abc".to_string()).coerce_to(T::Uint), None);
+    // number -> string
+    assert_eq!(MawuValue::Uint(42).coerce_to(T::String), Some(MawuValue::String("42".to_string())));
+    assert_eq!(MawuValue::Int(-42).coerce_to(T::String), Some(MawuValue::String("-42".to_string())));
+    assert_eq!(MawuValue::Float(1.5).coerce_to(T::String), Some(MawuValue::String("1.5".to_string())));
+    // string -> bool
+    assert_eq!(MawuValue::String("true".to_string()).coerce_to(T::Bool), Some(MawuValue::Bool(true)));
+    assert_eq!(MawuValue::String(" FALSE".to_string()).coerce_to(T::Bool), Some(MawuValue::Bool(false)));
+    assert_eq!(MawuValue::String("yes".to_string()).coerce_to(T::Bool), None);
+    // bool -> anything
+    assert_eq!(MawuValue::Bool(true).coerce_to(T::String), Some(MawuValue::String("true".to_string())));
+    assert_eq!(MawuValue::Bool(true).coerce_to(T::Uint), Some(MawuValue::Uint(1)));
+    assert_eq!(MawuValue::Bool(false).coerce_to(T::Int), Some(MawuValue::Int(0)));
+    assert_eq!(MawuValue::Bool(true).coerce_to(T::Float), None);
+    // number -> number
+    assert_eq!(MawuValue::Uint(u64::MAX).coerce_to(T::Int), None);
+    assert_eq!(MawuValue::Int(-1).coerce_to(T::Uint), None);
+    assert_eq!(MawuValue::Int(0).coerce_to(T::Uint), Some(MawuValue::Uint(0)));
+    assert_eq!(MawuValue::Int(-3).coerce_to(T::Float), Some(MawuValue::Float(-3.0)));
+    // 2^53 + 1 is the first integer a float can not hold
+    assert_eq!(MawuValue::Uint(9007199254740992).coerce_to(T::Float), Some(MawuValue::Float(9007199254740992.0)));
+    assert_eq!(MawuValue::Uint(9007199254740993).coerce_to(T::Float), None);
+    assert_eq!(MawuValue::Int(-9007199254740993).coerce_to(T::Float), None);
+    assert_eq!(MawuValue::Uint(1 << 63).coerce_to(T::Float), Some(MawuValue::Float(9223372036854775808.0)));
+    assert_eq!(MawuValue::Uint(u64::MAX).coerce_to(T::Float), None);
+    assert_eq!(MawuValue::Int(i64::MAX).coerce_to(T::Float), None);
+    assert_eq!(MawuValue::Int(i64::MIN).coerce_to(T::Float), Some(MawuValue::Float(i64::MIN as f64)));
+    assert_eq!(MawuValue::Float(0.0).coerce_to(T::Uint), Some(MawuValue::Uint(0)));
+    assert_eq!(MawuValue::Float(-2.0).coerce_to(T::Int), Some(MawuValue::Int(-2)));
+    assert_eq!(MawuValue::Float(-2.0).coerce_to(T::Uint), None);
+    assert_eq!(MawuValue::Float(2.5).coerce_to(T::Uint), None);
+    assert_eq!(MawuValue::Float(1e20).coerce_to(T::Uint), None);
+    assert_eq!(MawuValue::Float(f64::NAN).coerce_to(T::Int), None);
+    // number -> bool
+    assert_eq!(MawuValue::Uint(1).coerce_to(T::Bool), Some(MawuValue::Bool(true)));
+    assert_eq!(MawuValue::Int(0).coerce_to(T::Bool), Some(MawuValue::Bool(false)));
+    assert_eq!(MawuValue::Uint(2).coerce_to(T::Bool), None);
+    assert_eq!(MawuValue::Float(1.0).coerce_to(T::Bool), None);
+    // never coerced
+    assert_eq!(MawuValue::None.coerce_to(T::String), None);
+    assert_eq!(MawuValue::from(vec![1]).coerce_to(T::String), None);
+}
+
 #[test]
 fn general_as_all_types() {
     let num_uint = MawuValue::from(u8::MAX);
@@ -1794,7 +3969,7 @@ fn general_as_all_types() {
 
     let array = MawuValue::from(vec!["test", "test2", "test3"]);
     assert_eq!(array.as_array().unwrap()[2], MawuValue::from("test3"));
-    let mut hashmap = HashMap::new();
+    let mut hashmap = MawuObject::new();
     hashmap.insert("test".to_string(), MawuValue::from(123));
     let object = MawuValue::Object(hashmap);
     assert_eq!(
@@ -1808,6 +3983,40 @@ fn general_as_all_types() {
     assert_eq!(str_ing.as_str().unwrap(), "test");
 }
 
+#[test]
+fn take_leaves_none() {
+    let mut array = MawuValue::from(vec![MawuValue::from("a"), MawuValue::from(vec![1, 2])]);
+    if let MawuValue::Array(v) = &mut array {
+        assert_eq!(v[1].take(), MawuValue::from(vec![1, 2]));
+    }
+    assert_eq!(array, MawuValue::from(vec![MawuValue::from("a"), MawuValue::None]));
+    let mut none = MawuValue::None;
+    assert_eq!(none.take(), MawuValue::None);
+}
+
+#[test]
+fn retain_removes_nulls() {
+    let mut object = MawuValue::from(vec![
+        ("a", MawuValue::None),
+        ("b", MawuValue::from(1)),
+        ("c", MawuValue::None),
+        ("d", MawuValue::from(vec![MawuValue::None])),
+    ]);
+    object.retain_object(|_, value| !value.is_none());
+    assert_eq!(object.len(), 2);
+    assert!(object.has_key("b"));
+    assert!(!object.has_key("a"));
+
+    let mut array = MawuValue::from(vec![MawuValue::None, MawuValue::from(1), MawuValue::None]);
+    array.retain_array(|value| !value.is_none());
+    assert_eq!(array, MawuValue::from(vec![1]));
+
+    let mut wrong_type = MawuValue::from(1);
+    wrong_type.retain_array(|_| false);
+    wrong_type.retain_object(|_, _| false);
+    assert_eq!(wrong_type, MawuValue::from(1));
+}
+
 #[test]
 fn general_convenience_functions() {
     let num = MawuValue::from(123);
@@ -1842,7 +4051,7 @@ fn from_vec_and_hashmap() {
     let mawu_hashmap = MawuValue::from(hashmap);
     assert_eq!(
         mawu_hashmap,
-        MawuValue::Object(HashMap::from([("test".into(), "test2".into())]))
+        MawuValue::Object(MawuObject::from([("test".into(), "test2".into())]))
     );
 }
 
@@ -1945,11 +4154,153 @@ fn mawu_value_from_string() {
     assert_eq!(mawu_null_value, MawuValue::None);
     assert_eq!(mawu_null_value.is_none(), true);
     assert_eq!(mawu_null_value.as_none(), None);
+
+    // the cases a cheaper classification could get wrong
+    assert_eq!(MawuValue::from("+5"), MawuValue::Uint(5));
+    assert_eq!(MawuValue::from("-0"), MawuValue::Int(0));
+    assert_eq!(MawuValue::from("18446744073709551616"), MawuValue::Float(18446744073709551616.0));
+    assert_eq!(MawuValue::from("-9223372036854775809"), MawuValue::Float(-9223372036854775809.0));
+    assert_eq!(MawuValue::from(".5"), MawuValue::Float(0.5));
+    assert_eq!(MawuValue::from("1e3"), MawuValue::Float(1000.0));
+    assert_eq!(MawuValue::from("e3"), MawuValue::String("e3".to_string()));
+    assert_eq!(MawuValue::from("-inf"), MawuValue::None);
+    assert_eq!(MawuValue::from("NaN"), MawuValue::None);
+    assert_eq!(MawuValue::from("True"), MawuValue::String("True".to_string()));
+    assert_eq!(MawuValue::from("-"), MawuValue::String("-".to_string()));
+}
+
+// the order of the conversions is load-bearing, numbers have to win over booleans
+#[test]
+fn mawu_value_from_string_precedence() {
+    assert_eq!(MawuValue::from("1"), MawuValue::Uint(1));
+    assert_eq!(MawuValue::from("0"), MawuValue::Uint(0));
+    assert_eq!(MawuValue::from("-1"), MawuValue::Int(-1));
+    assert_eq!(MawuValue::from("1.0"), MawuValue::Float(1.0));
+    assert_eq!(MawuValue::from("true"), MawuValue::Bool(true));
+    assert_eq!(MawuValue::from("false"), MawuValue::Bool(false));
+    assert_eq!(MawuValue::from("1".to_string()), MawuValue::Uint(1));
+    assert_eq!(MawuValue::from(&"0".to_string()), MawuValue::Uint(0));
+    assert_eq!(MawuValue::from("true".to_string()), MawuValue::Bool(true));
+}
+
+#[test]
+fn as_bytes_only_for_strings() {
+    assert_eq!(MawuValue::String("\u{30af}a".to_string()).as_bytes(), Some(&[0xe3, 0x82, 0xaf, b'a'][..]));
+    assert_eq!(MawuValue::String(String::new()).as_bytes(), Some(&[][..]));
+    assert_eq!(MawuValue::Uint(1).as_bytes(), None);
+    assert_eq!(MawuValue::None.as_bytes(), None);
+}
+
+#[test]
+fn iterate_containers() {
+    let mut object = crate::mawu!({"b": 2, "a": [1, 2], "c": null});
+    let mut keys = object.iter_object().unwrap().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec!["a", "b", "c"]);
+    assert!(object.iter_array().is_none());
+    assert!(object.iter_array_mut().is_none());
+    for (key, value) in object.iter_object_mut().unwrap() {
+        if key == "c" {
+            *value = MawuValue::from(3);
+        }
+    }
+    assert_eq!(object.get("c").unwrap(), &MawuValue::from(3));
+
+    let array = object.pointer_mut("/a").unwrap();
+    assert!(array.iter_object().is_none());
+    array.iter_array_mut().unwrap().for_each(|v| *v = MawuValue::Bool(true));
+    assert_eq!(array.iter_array().unwrap().collect::<Vec<_>>(), vec![&MawuValue::Bool(true); 2]);
+    assert!(MawuValue::CSVArray(Vec::new()).iter_array().is_none());
+}
+
+#[test]
+fn deep_get_paths() {
+    use PathSeg::{Index, Key};
+    let mut value = crate::mawu!({"a/b": [{"c~d": [1, 2]}, null], "0": {"1": true}});
+    assert_eq!(value.deep_get(&[]), Some(&value));
+    assert_eq!(value.deep_get(&[Key("a/b"), Index(0), Key("c~d"), Index(1)]), Some(&MawuValue::Uint(2)));
+    assert_eq!(value.deep_get(&[Key("a/b"), Index(1)]), Some(&MawuValue::None));
+    assert_eq!(value.deep_get(&[Key("a/b"), Index(2)]), None);
+    assert_eq!(value.deep_get(&[Key("0"), Key("1")]), Some(&MawuValue::Bool(true)));
+    // keys do not index arrays, and indices do not look up keys
+    assert_eq!(value.deep_get(&[Key("a/b"), Key("0")]), None);
+    assert_eq!(value.deep_get(&[Index(0)]), None);
+    assert_eq!(value.deep_get(&[Key("a"), Key("b")]), None);
+    assert_eq!(value.deep_get(&[Key("a/b"), Index(1), Key("x")]), None);
+
+    *value.deep_get_mut(&[Key("a/b"), Index(0), Key("c~d"), Index(0)]).unwrap() = MawuValue::from("one");
+    assert_eq!(value.pointer("/a~1b/0/c~0d/0"), Some(&MawuValue::from("one")));
+    assert!(value.deep_get_mut(&[Key("missing")]).is_none());
+}
+
+#[test]
+fn object_keys_and_values() {
+    let object = crate::mawu!({"x": 1, "y": [2], "z": null});
+    let mut keys = object.keys().unwrap().map(String::as_str).collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec!["x", "y", "z"]);
+    assert_eq!(object.values().unwrap().count(), 3);
+    assert!(object.values().unwrap().any(|value| value == &MawuValue::None));
+    // keys and values iterate in the same order
+    let pairs = object.keys().unwrap().zip(object.values().unwrap()).collect::<Vec<_>>();
+    assert_eq!(pairs, object.iter_object().unwrap().collect::<Vec<_>>());
+    assert!(object.get("y").unwrap().keys().is_none());
+    assert!(MawuValue::CSVObject(Vec::new()).values().is_none());
+}
+
+#[test]
+fn get_or_insert_containers() {
+    let mut value = MawuValue::None;
+    let object = value.get_or_insert_object().unwrap();
+    object.insert("nested".to_string(), MawuValue::None);
+    object.insert("n".to_string(), MawuValue::Uint(1));
+    let nested = value.pointer_mut("/nested").unwrap();
+    nested.get_or_insert_array().unwrap().push(MawuValue::from(true));
+    nested.get_or_insert_array().unwrap().push(MawuValue::None);
+    assert_eq!(value, crate::mawu!({"nested": [true, null], "n": 1}));
+
+    // the wrong container or a scalar is left as it is
+    assert!(value.get_or_insert_array().is_none());
+    assert!(value.pointer_mut("/nested").unwrap().get_or_insert_object().is_none());
+    assert!(value.pointer_mut("/n").unwrap().get_or_insert_object().is_none());
+    assert_eq!(value, crate::mawu!({"nested": [true, null], "n": 1}));
+    assert!(MawuValue::CSVObject(Vec::new()).get_or_insert_object().is_none());
+}
+
+#[test]
+fn membership() {
+    let object = crate::mawu!({"a": [1, "two", null], "b": {"c": true}});
+    assert!(object.contains_key("a"));
+    assert!(!object.contains_key("c"));
+    assert!(!object.contains(MawuValue::from("a")));
+    let array = object.get("a").unwrap();
+    assert!(array.contains(&MawuValue::from("two")));
+    assert!(array.contains(MawuValue::None));
+    assert!(!array.contains(2));
+    assert!(!array.contains_key("a"));
+    let csv = MawuValue::CSVObject(vec![HashMap::from([("a".to_string(), MawuValue::Uint(1))])]);
+    assert!(!csv.contains_key("a"));
+}
+
+#[test]
+fn array_of_objects_to_csv_rows() {
+    let value = crate::mawu!([{"a": 1, "b": "x"}, {"b": "y", "c": [true]}]);
+    let csv = value.to_csv_rows().unwrap();
+    let rows = csv.as_csv_object().unwrap();
+    assert_eq!(rows.len(), 2);
+    assert!(rows.iter().all(|row| row.len() == 3));
+    assert_eq!(rows[0].get("c").unwrap(), &MawuValue::None);
+    assert_eq!(rows[1].get("a").unwrap(), &MawuValue::None);
+    assert_eq!(rows[1].get("b").unwrap(), &MawuValue::from("y"));
+    assert_eq!(rows[1].get("c").unwrap(), &crate::mawu!([true]));
+    assert_eq!(crate::mawu!([]).to_csv_rows(), Some(MawuValue::CSVObject(Vec::new())));
+    assert_eq!(crate::mawu!({"a": 1}).to_csv_rows(), None);
+    assert_eq!(crate::mawu!([{"a": 1}, null]).to_csv_rows(), None);
 }
 
 #[test]
 fn mawu_value_constructed() {
-    let mawu_object_value = MawuValue::Object(HashMap::new());
+    let mawu_object_value = MawuValue::Object(MawuObject::new());
     let mawu_array_value = MawuValue::Array(vec![]);
     let mawu_csv_object_value = MawuValue::CSVObject(vec![HashMap::new()]);
     let mawu_csv_array_value = MawuValue::CSVArray(vec![vec![]]);
@@ -1959,7 +4310,7 @@ fn mawu_value_constructed() {
     assert_eq!(mawu_csv_object_value.is_csv_object(), true);
     assert_eq!(mawu_csv_array_value.is_csv_array(), true);
 
-    assert_eq!(mawu_object_value.as_object(), Some(&HashMap::new()));
+    assert_eq!(mawu_object_value.as_object(), Some(&MawuObject::new()));
     assert_eq!(mawu_array_value.as_array(), Some(&vec![]));
     assert_eq!(
         mawu_csv_object_value.as_csv_object(),
@@ -1967,3 +4318,279 @@ fn mawu_value_constructed() {
     );
     assert_eq!(mawu_csv_array_value.as_csv_array(), Some(&vec![vec![]]));
 }
+
+#[test]
+fn as_number_variants() {
+    assert_eq!(MawuValue::Uint(1).as_number(), Some(MawuNumber::Uint(1)));
+    assert_eq!(MawuValue::Int(-1).as_number(), Some(MawuNumber::Int(-1)));
+    assert_eq!(MawuValue::Float(1.5).as_number(), Some(MawuNumber::Float(1.5)));
+    assert_eq!(MawuValue::Int(-2).as_number().unwrap().as_f64(), -2.0);
+    assert_eq!(MawuValue::Uint(u64::MAX).as_number().unwrap().as_f64(), u64::MAX as f64);
+    for not_a_number in [
+        MawuValue::String("1".to_string()),
+        MawuValue::Bool(true),
+        MawuValue::None,
+        MawuValue::Array(vec![MawuValue::Uint(1)]),
+        MawuValue::Object(MawuObject::new()),
+        MawuValue::CSVArray(vec![]),
+        MawuValue::CSVObject(vec![]),
+    ] {
+        assert_eq!(not_a_number.as_number(), None);
+    }
+}
+
+#[test]
+fn approx_eq_numbers() {
+    let sum = MawuValue::Float(0.1 + 0.2);
+    assert_ne!(sum, MawuValue::Float(0.3));
+    assert!(sum.approx_eq(&MawuValue::Float(0.3), f64::EPSILON));
+    assert!(!sum.approx_eq(&MawuValue::Float(0.31), 1e-9));
+    assert!(MawuValue::Int(-1).approx_eq(&MawuValue::Float(-1.0000001), 1e-6));
+    assert!(MawuValue::Uint(2).approx_eq(&MawuValue::Int(2), 0.0));
+    assert!(!MawuValue::Float(f64::NAN).approx_eq(&MawuValue::Float(f64::NAN), f64::INFINITY));
+    assert!(!MawuValue::Float(1.0).approx_eq(&MawuValue::Float(f64::NAN), f64::INFINITY));
+    assert!(MawuValue::String("a".to_string()).approx_eq(&MawuValue::String("a".to_string()), 1.0));
+    assert!(!MawuValue::String("1".to_string()).approx_eq(&MawuValue::Uint(1), 1.0));
+    assert!(MawuValue::None.approx_eq(&MawuValue::None, 0.0));
+}
+
+#[test]
+fn typed_getters() {
+    let config = crate::mawu!({
+        "name": "mawu",
+        "port": 8080,
+        "offset": (-1),
+        "ratio": 0.5,
+        "debug": false,
+        "tags": [1, 2],
+        "server": {"host": "localhost"}
+    });
+    assert_eq!(config.get_str("name"), Some("mawu"));
+    assert_eq!(config.get_u64("port"), Some(8080));
+    assert_eq!(config.get_i64("port"), Some(8080));
+    assert_eq!(config.get_i64("offset"), Some(-1));
+    assert_eq!(config.get_f64("ratio"), Some(0.5));
+    assert_eq!(config.get_f64("port"), Some(8080.0));
+    assert_eq!(config.get_bool("debug"), Some(false));
+    assert_eq!(config.get_array("tags").unwrap().len(), 2);
+    assert_eq!(config.get_object("server").unwrap().len(), 1);
+
+    // missing key
+    assert_eq!(config.get_str("missing"), None);
+    assert_eq!(config.get_u64("missing"), None);
+    assert_eq!(config.get_object("missing"), None);
+    // wrong type
+    assert_eq!(config.get_str("port"), None);
+    assert_eq!(config.get_u64("offset"), None);
+    assert_eq!(config.get_i64("name"), None);
+    assert_eq!(config.get_f64("debug"), None);
+    assert_eq!(config.get_bool("name"), None);
+    assert_eq!(config.get_array("server"), None);
+    assert_eq!(config.get_object("tags"), None);
+    // wrong container
+    let array = MawuValue::Array(vec![MawuValue::String("name".to_string())]);
+    assert_eq!(array.get_str("name"), None);
+    assert_eq!(MawuValue::CSVObject(vec![]).get_u64("port"), None);
+    assert_eq!(MawuValue::Uint(1).get_bool("debug"), None);
+}
+
+#[test]
+fn conversion_fallbacks() {
+    let config = crate::mawu!({"name": "mawu", "port": 8080, "offset": (-1), "ratio": 0.5, "debug": true});
+    let missing = MawuValue::None;
+    let field = |key: &str| config.get(key).unwrap_or(&missing);
+
+    assert_eq!(field("name").as_str_or("default"), "mawu");
+    assert_eq!(field("nickname").as_str_or("default"), "default");
+    assert_eq!(field("port").as_str_or("default"), "default");
+    assert_eq!(field("port").to_u64_or(80), 8080);
+    assert_eq!(field("offset").to_u64_or(80), 80);
+    assert_eq!(field("offset").to_i64_or(0), -1);
+    assert_eq!(field("name").to_i64_or(0), 0);
+    assert_eq!(field("ratio").to_f64_or(1.0), 0.5);
+    assert_eq!(field("port").to_f64_or(1.0), 8080.0);
+    assert_eq!(field("ratio_2").to_f64_or(1.0), 1.0);
+    assert!(field("debug").to_bool_or(false));
+    assert!(field("verbose").to_bool_or(true));
+}
+
+#[test]
+fn expect_accessors() {
+    let config = crate::mawu!({"name": "mawu", "port": 8080, "tags": ["json"]});
+    assert_eq!(config.expect_object("config").len(), 3);
+    assert_eq!(config.get("name").unwrap().expect_str("name"), "mawu");
+    assert_eq!(config.get("port").unwrap().expect_u64("port"), 8080);
+    assert_eq!(config.get("tags").unwrap().expect_array("tags").len(), 1);
+    assert_eq!(MawuValue::Uint(1).expect_f64("ratio"), 1.0);
+}
+
+#[test]
+#[should_panic(expected = "offset: expected an u64, found MawuValue::Int")]
+fn expect_u64_on_negative_panics() {
+    MawuValue::Int(-1).expect_u64("offset");
+}
+
+#[test]
+#[should_panic(expected = "port: expected MawuValue::String, found MawuValue::Uint")]
+fn expect_str_on_uint_panics() {
+    MawuValue::Uint(8080).expect_str("port");
+}
+
+#[test]
+fn csv_columns() {
+    let row = |name: &str, age: Option<u64>| {
+        let mut row = HashMap::from([("name".to_string(), MawuValue::String(name.to_string()))]);
+        if let Some(age) = age {
+            row.insert("age".to_string(), MawuValue::Uint(age));
+        }
+        row
+    };
+    let headed = MawuValue::CSVObject(vec![row("Ada", Some(36)), row("Bob", None), row("Eve", Some(29))]);
+    assert_eq!(
+        headed.csv_column("age").unwrap(),
+        vec![&MawuValue::Uint(36), &MawuValue::None, &MawuValue::Uint(29)]
+    );
+    assert_eq!(headed.csv_column("name").unwrap().len(), 3);
+    assert_eq!(headed.csv_column("email"), None);
+    assert_eq!(MawuValue::CSVObject(vec![]).csv_column("name"), None);
+    assert_eq!(MawuValue::new_object().csv_column("name"), None);
+
+    let headless = MawuValue::CSVArray(vec![
+        vec![MawuValue::Uint(1), MawuValue::Uint(2)],
+        vec![MawuValue::Uint(3)],
+        vec![MawuValue::Uint(5), MawuValue::Uint(6)],
+    ]);
+    assert_eq!(headless.csv_column_index(0).unwrap(), vec![&MawuValue::Uint(1), &MawuValue::Uint(3), &MawuValue::Uint(5)]);
+    assert_eq!(headless.csv_column_index(1).unwrap(), vec![&MawuValue::Uint(2), &MawuValue::None, &MawuValue::Uint(6)]);
+    assert_eq!(headless.csv_column_index(2), None);
+    assert_eq!(headed.csv_column_index(0), None);
+}
+
+#[test]
+fn csv_column_statistics() {
+    let row = |value: MawuValue| HashMap::from([("value".to_string(), value), ("name".to_string(), MawuValue::String("x".to_string()))]);
+    let csv = MawuValue::CSVObject(vec![
+        row(MawuValue::Uint(4)),
+        row(MawuValue::Int(-2)),
+        row(MawuValue::Float(1.5)),
+        row(MawuValue::None),
+        row(MawuValue::String("n/a".to_string())),
+    ]);
+    let stats = csv.csv_column_stats("value").unwrap();
+    assert_eq!(stats.count, 3);
+    assert_eq!(stats.skipped, 2);
+    assert_eq!(stats.sum, 3.5);
+    assert!((stats.mean - 3.5 / 3.0).abs() < f64::EPSILON);
+    assert_eq!(stats.min, -2.0);
+    assert_eq!(stats.max, 4.0);
+
+    assert_eq!(csv.csv_column_stats("name"), None);
+    assert_eq!(csv.csv_column_stats("missing"), None);
+    assert_eq!(MawuValue::CSVArray(vec![vec![MawuValue::Uint(1)]]).csv_column_stats("value"), None);
+}
+
+#[test]
+fn csv_column_as_f64() {
+    let row = |value: MawuValue| HashMap::from([("value".to_string(), value)]);
+    let clean = MawuValue::CSVObject(vec![row(MawuValue::Uint(1)), row(MawuValue::Int(-2)), row(MawuValue::Float(0.5))]);
+    assert_eq!(clean.csv_column_f64("value", true).unwrap(), vec![1.0, -2.0, 0.5]);
+    assert_eq!(clean.csv_column_f64("value", false).unwrap(), vec![1.0, -2.0, 0.5]);
+
+    let stray = MawuValue::CSVObject(vec![row(MawuValue::Uint(1)), row(MawuValue::String("two".to_string())), row(MawuValue::Float(3.0))]);
+    assert_eq!(stray.csv_column_f64("value", true), None);
+    let lenient = stray.csv_column_f64("value", false).unwrap();
+    assert_eq!(lenient.len(), 3);
+    assert_eq!(lenient[0], 1.0);
+    assert!(lenient[1].is_nan());
+    assert_eq!(lenient[2], 3.0);
+
+    assert_eq!(clean.csv_column_f64("missing", false), None);
+}
+
+#[test]
+fn csv_grouping() {
+    let row = |team: Option<&str>, score: u64| {
+        let mut row = HashMap::from([("score".to_string(), MawuValue::Uint(score))]);
+        if let Some(team) = team {
+            row.insert("team".to_string(), MawuValue::String(team.to_string()));
+        }
+        row
+    };
+    let csv = MawuValue::CSVObject(vec![row(Some("red"), 1), row(Some("blue"), 2), row(Some("red"), 3), row(Some("blue"), 4)]);
+    let groups = csv.csv_group_by("team").unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups["red"], vec![row(Some("red"), 1), row(Some("red"), 3)]);
+    assert_eq!(groups["blue"], vec![row(Some("blue"), 2), row(Some("blue"), 4)]);
+
+    let by_score = csv.csv_group_by("score").unwrap();
+    assert_eq!(by_score["3"], vec![row(Some("red"), 3)]);
+
+    let mut with_missing = csv.clone();
+    if let MawuValue::CSVObject(rows) = &mut with_missing {
+        rows.push(row(None, 5));
+    }
+    assert_eq!(with_missing.csv_group_by("team").unwrap()[""], vec![row(None, 5)]);
+    assert_eq!(csv.csv_group_by("missing"), None);
+    assert_eq!(MawuValue::new_array().csv_group_by("team"), None);
+}
+
+#[test]
+fn builders() {
+    let built = MawuObjectBuilder::new()
+        .set("name", MawuValue::String("mawu".to_string()))
+        .set("version", MawuValue::Uint(1))
+        .set(
+            "formats",
+            MawuArrayBuilder::new()
+                .add(MawuObjectBuilder::new().set("name", MawuValue::String("csv".to_string())))
+                .add(MawuObjectBuilder::new().set("name", MawuValue::String("json".to_string())).build()),
+        )
+        .set("nested", MawuObjectBuilder::new().set("empty", MawuObjectBuilder::new()).set("list", MawuArrayBuilder::new()))
+        .set("version", MawuValue::Uint(2))
+        .build();
+
+    let mut csv = MawuObject::new();
+    csv.insert("name".to_string(), MawuValue::String("csv".to_string()));
+    let mut json = MawuObject::new();
+    json.insert("name".to_string(), MawuValue::String("json".to_string()));
+    let mut nested = MawuObject::new();
+    nested.insert("empty".to_string(), MawuValue::new_object());
+    nested.insert("list".to_string(), MawuValue::new_array());
+    let mut expected = MawuObject::new();
+    expected.insert("name".to_string(), MawuValue::String("mawu".to_string()));
+    expected.insert("version".to_string(), MawuValue::Uint(2));
+    expected.insert("formats".to_string(), MawuValue::Array(vec![MawuValue::Object(csv), MawuValue::Object(json)]));
+    expected.insert("nested".to_string(), MawuValue::Object(nested));
+    assert_eq!(built, MawuValue::Object(expected));
+
+    assert_eq!(MawuObjectBuilder::new().build(), MawuValue::new_object());
+    assert_eq!(MawuArrayBuilder::new().build(), MawuValue::new_array());
+}
+
+#[test]
+fn from_pairs() {
+    let mut expected = MawuObject::new();
+    expected.insert("a".to_string(), MawuValue::Int(1));
+    expected.insert("b".to_string(), MawuValue::Int(2));
+    expected.insert("c".to_string(), MawuValue::Int(3));
+    let expected = MawuValue::Object(expected);
+
+    assert_eq!(MawuValue::from([("a", 1), ("b", 2), ("c", 3)]), expected);
+    let pairs = vec![("a", 1), ("b", 2), ("c", 3)];
+    assert_eq!(MawuValue::from(pairs.as_slice()), expected);
+    assert_eq!(MawuValue::from([("a", 1), ("a", 2)]).get("a").unwrap(), &MawuValue::Int(2));
+    assert_eq!(MawuValue::from([("empty", MawuValue::None); 0]), MawuValue::new_object());
+}
+
+#[test]
+fn from_single_pairs() {
+    let single = MawuValue::from([("k", 5)]);
+    assert_eq!(single.len(), 1);
+    assert_eq!(single.get("k").unwrap(), &MawuValue::Int(5));
+
+    let nested = MawuValue::from([("outer", MawuValue::from([("inner", MawuValue::from([("k", true)]))]))]);
+    assert_eq!(
+        nested.get("outer").unwrap().get("inner").unwrap().get("k").unwrap(),
+        &MawuValue::Bool(true)
+    );
+}
@@ -0,0 +1,166 @@
+use crate::prelude::*;
+use alloc::borrow::Cow;
+
+use crate::mawu_value::{MawuObject, MawuValue};
+
+#[derive(Clone, Debug, PartialEq)]
+/// A borrowed counterpart to `MawuValue`, returned by `json::from_str_borrowed`.
+///
+/// Strings and object keys without escape sequences borrow directly from the parsed input,
+/// only strings that had to be unescaped allocate.
+/// Objects keep their key-value-pairs in the order of the input, if a key is used more than once
+/// the last value wins, just like in a `MawuValue::Object`.
+/// Use `to_owned` or `into_owned` to convert into a `MawuValue`.
+pub enum MawuValueRef<'a> {
+    /// Represents a JSON Object, with string keys and values made of `MawuValueRef`'s
+    Object(Vec<(Cow<'a, str>, MawuValueRef<'a>)>),
+    /// Represents a JSON Array, made of `MawuValueRef`'s
+    Array(Vec<MawuValueRef<'a>>),
+    /// Represents a positive integer
+    Uint(u64),
+    /// Represents a negative integer
+    Int(i64),
+    /// Represents a floating point number
+    Float(f64),
+    /// Represents a string, borrowed from the input if it contained no escape sequences
+    String(Cow<'a, str>),
+    /// Represents a boolean
+    Bool(bool),
+    /// Represents `null`
+    None,
+}
+
+impl<'a> MawuValueRef<'a> {
+    /// Returns the value of the given key, or `None` if the value is not an object or has no such key.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::json::from_str_borrowed;
+    ///
+    /// let value = from_str_borrowed(r#"{"key": "value", "key": "last"}"#).unwrap();
+    /// assert_eq!(value.get("key").unwrap().as_str(), Some("last"));
+    /// assert!(value.get("other").is_none());
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&MawuValueRef<'a>> {
+        match self {
+            MawuValueRef::Object(pairs) => pairs.iter().rev().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the key-value-pairs of an object in the order of the input, or `None` if the value is not an object
+    pub fn as_object(&self) -> Option<&Vec<(Cow<'a, str>, MawuValueRef<'a>)>> {
+        match self {
+            MawuValueRef::Object(pairs) => Some(pairs),
+            _ => None,
+        }
+    }
+
+    /// Returns the values of an array, or `None` if the value is not an array
+    pub fn as_array(&self) -> Option<&Vec<MawuValueRef<'a>>> {
+        match self {
+            MawuValueRef::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the string, or `None` if the value is not a string
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            MawuValueRef::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the positive integer, or `None` if the value is not a `Uint`
+    pub fn as_uint(&self) -> Option<u64> {
+        match self {
+            MawuValueRef::Uint(u) => Some(*u),
+            _ => None,
+        }
+    }
+
+    /// Returns the negative integer, or `None` if the value is not an `Int`
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            MawuValueRef::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the floating point number, or `None` if the value is not a `Float`
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            MawuValueRef::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+
+    /// Returns the boolean, or `None` if the value is not a `Bool`
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            MawuValueRef::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the value is `null`
+    pub fn is_none(&self) -> bool {
+        matches!(self, MawuValueRef::None)
+    }
+
+    /// Returns `true` if the value is a string borrowed from the input
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, MawuValueRef::String(Cow::Borrowed(_)))
+    }
+
+    /// Converts into an owned `MawuValue`, copying all borrowed strings.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::{json::{from_str, from_str_borrowed}, mawu};
+    ///
+    /// let input = r#"{"name": "mawu", "tags": ["json", "csv\n"], "stars": 5}"#;
+    /// let borrowed = from_str_borrowed(input).unwrap();
+    /// assert_eq!(borrowed.to_owned(), from_str(input).unwrap());
+    /// assert_eq!(borrowed.to_owned(), mawu!({"name": "mawu", "tags": ["json", "csv\n"], "stars": 5}));
+    /// ```
+    pub fn to_owned(&self) -> MawuValue {
+        self.clone().into_owned()
+    }
+
+    /// Converts into an owned `MawuValue`, reusing the strings that were already unescaped
+    pub fn into_owned(self) -> MawuValue {
+        match self {
+            MawuValueRef::Object(pairs) => MawuValue::Object(
+                pairs
+                    .into_iter()
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect::<MawuObject>(),
+            ),
+            MawuValueRef::Array(values) => MawuValue::Array(values.into_iter().map(|v| v.into_owned()).collect()),
+            MawuValueRef::Uint(u) => MawuValue::Uint(u),
+            MawuValueRef::Int(i) => MawuValue::Int(i),
+            MawuValueRef::Float(f) => MawuValue::Float(f),
+            MawuValueRef::String(s) => MawuValue::String(s.into_owned()),
+            MawuValueRef::Bool(b) => MawuValue::Bool(b),
+            MawuValueRef::None => MawuValue::None,
+        }
+    }
+}
+
+impl<'a> From<MawuValueRef<'a>> for MawuValue {
+    fn from(value: MawuValueRef<'a>) -> Self {
+        value.into_owned()
+    }
+}
+
+#[test]
+fn into_owned_keeps_last_duplicate_key() {
+    let value = MawuValueRef::Object(vec![
+        (Cow::Borrowed("a"), MawuValueRef::Uint(1)),
+        (Cow::Owned("a".to_string()), MawuValueRef::Uint(2)),
+    ]);
+    assert_eq!(value.get("a"), Some(&MawuValueRef::Uint(2)));
+    assert_eq!(value.into_owned().get("a").unwrap(), &MawuValue::Uint(2));
+}
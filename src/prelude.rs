@@ -0,0 +1,9 @@
+//! The parts of the standard prelude that live in `alloc`, so that every module compiles with
+//! and without the `std` feature.
+
+pub use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
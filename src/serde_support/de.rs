@@ -0,0 +1,269 @@
+use crate::prelude::*;
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer, StringDeserializer},
+        DeserializeSeed, EnumAccess, IntoDeserializer, Unexpected, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+
+use crate::{mawu_value::MawuValue, serde_support::SerdeError};
+
+/// Deserializes a `T` out of the value, consuming it
+pub fn from_mawu_value<T: de::DeserializeOwned>(value: MawuValue) -> Result<T, SerdeError> {
+    T::deserialize(value)
+}
+
+/// Deserializes a `T` out of a CSV row read without type inference, see `CsvField`
+pub fn from_csv_row<T: de::DeserializeOwned, I: IntoIterator<Item = (String, MawuValue)>>(row: I) -> Result<T, SerdeError> {
+    T::deserialize(MapDeserializer::new(row.into_iter().map(|(key, value)| (key, CsvField(value)))))
+}
+
+fn unexpected(value: &MawuValue) -> Unexpected<'_> {
+    match value {
+        MawuValue::CSVObject(_) | MawuValue::CSVArray(_) | MawuValue::Array(_) => Unexpected::Seq,
+        MawuValue::Object(_) => Unexpected::Map,
+        MawuValue::Uint(u) => Unexpected::Unsigned(*u),
+        MawuValue::Int(i) => Unexpected::Signed(*i),
+        MawuValue::Float(f) => Unexpected::Float(*f),
+        MawuValue::String(s) => Unexpected::Str(s),
+        MawuValue::Bool(b) => Unexpected::Bool(*b),
+        MawuValue::None => Unexpected::Unit,
+    }
+}
+
+/// Visits all values of an array, failing if the visitor does not take all of them
+fn visit_seq<'de, V: Visitor<'de>, I: Iterator<Item = MawuValue>>(values: I, visitor: V) -> Result<V::Value, SerdeError> {
+    let mut seq = SeqDeserializer::new(values);
+    let out = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(out)
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for MawuValue {
+    type Deserializer = MawuValue;
+
+    fn into_deserializer(self) -> MawuValue {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for MawuValue {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            // every row of a headed CSV file is deserialized like a JSON object
+            MawuValue::CSVObject(rows) => {
+                visit_seq(rows.into_iter().map(|row| MawuValue::Object(row.into_iter().collect())), visitor)
+            }
+            MawuValue::CSVArray(rows) => visit_seq(rows.into_iter().map(MawuValue::Array), visitor),
+            MawuValue::Object(object) => {
+                let mut map = MapDeserializer::new(object.into_iter());
+                let out = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(out)
+            }
+            MawuValue::Array(values) => visit_seq(values.into_iter(), visitor),
+            MawuValue::Uint(u) => visitor.visit_u64(u),
+            MawuValue::Int(i) => visitor.visit_i64(i),
+            MawuValue::Float(f) => visitor.visit_f64(f),
+            MawuValue::String(s) => visitor.visit_string(s),
+            MawuValue::Bool(b) => visitor.visit_bool(b),
+            MawuValue::None => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self {
+            MawuValue::None => visitor.visit_none(),
+            other => visitor.visit_some(other),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Unit variants are strings, all other variants objects with the variant as their only key
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match self {
+            MawuValue::String(variant) => {
+                let variant: StringDeserializer<SerdeError> = variant.into_deserializer();
+                visitor.visit_enum(variant)
+            }
+            MawuValue::Object(object) if object.len() == 1 => {
+                let (variant, value) = object.into_iter().next().expect("object has exactly one key");
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(de::Error::invalid_type(unexpected(&other), &"a string or an object with a single key")),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// A CSV value read without type inference, so a `MawuValue::String` or `MawuValue::None`.
+/// Numbers and bools are parsed out of the string only if the field asks for them,
+/// so a zip code of `01234` stays `01234` in a `String` field.
+struct CsvField(MawuValue);
+
+impl CsvField {
+    fn parse<'de, T: core::str::FromStr, V: Visitor<'de>>(self, visitor: &V) -> Result<T, SerdeError> {
+        match self.0 {
+            MawuValue::String(s) => s.parse().map_err(|_| de::Error::invalid_type(Unexpected::Str(&s), visitor)),
+            other => Err(de::Error::invalid_type(unexpected(&other), visitor)),
+        }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for CsvField {
+    type Deserializer = CsvField;
+
+    fn into_deserializer(self) -> CsvField {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $ty:ty, $visit:ident;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+                let parsed: $ty = self.parse(&visitor)?;
+                visitor.$visit(parsed)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for CsvField {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.0 {
+            MawuValue::None => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => bool, visit_bool;
+        deserialize_i8 => i8, visit_i8;
+        deserialize_i16 => i16, visit_i16;
+        deserialize_i32 => i32, visit_i32;
+        deserialize_i64 => i64, visit_i64;
+        deserialize_i128 => i128, visit_i128;
+        deserialize_u8 => u8, visit_u8;
+        deserialize_u16 => u16, visit_u16;
+        deserialize_u32 => u32, visit_u32;
+        deserialize_u64 => u64, visit_u64;
+        deserialize_u128 => u128, visit_u128;
+        deserialize_f32 => f32, visit_f32;
+        deserialize_f64 => f64, visit_f64;
+        deserialize_char => char, visit_char;
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumDeserializer {
+    variant: String,
+    value: MawuValue,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer {
+    type Error = SerdeError;
+    type Variant = MawuValue;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, MawuValue), SerdeError> {
+        let variant: StringDeserializer<SerdeError> = self.variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for MawuValue {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), SerdeError> {
+        match self {
+            MawuValue::None => Ok(()),
+            other => Err(de::Error::invalid_type(unexpected(&other), &"a unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, SerdeError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, SerdeError> {
+        self.deserialize_map(visitor)
+    }
+}
+
+#[test]
+fn deserialize_nested() {
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    enum Shape {
+        Dot,
+        Circle(f64),
+        Rect { w: u8, h: u8 },
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Drawing {
+        name: String,
+        shapes: Vec<Shape>,
+        layer: Option<i32>,
+        origin: (i64, i64),
+    }
+
+    let value = crate::mawu!({
+        "name": "nested",
+        "shapes": ["Dot", {"Circle": 1.5}, {"Rect": {"w": 2, "h": 3}}],
+        "layer": null,
+        "origin": [-1, 1]
+    });
+    assert_eq!(from_mawu_value::<Drawing>(value).unwrap(), Drawing {
+        name: "nested".to_string(),
+        shapes: vec![Shape::Dot, Shape::Circle(1.5), Shape::Rect { w: 2, h: 3 }],
+        layer: None,
+        origin: (-1, 1),
+    });
+    assert!(from_mawu_value::<u8>(MawuValue::Uint(256)).is_err());
+    assert!(from_mawu_value::<String>(MawuValue::Uint(42)).is_err());
+    assert!(from_mawu_value::<Vec<u8>>(crate::mawu!({"a": 1})).is_err());
+}
@@ -0,0 +1,31 @@
+use crate::prelude::*;
+use core::fmt;
+
+/// `Deserializer` for `MawuValue`
+pub mod de;
+/// `Serializer` into `MawuValue`
+pub mod ser;
+
+/// The error of the serde glue, only used internally and then mapped into the `MawuError` of the calling module
+#[derive(Debug)]
+pub struct SerdeError(pub String);
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl core::error::Error for SerdeError {}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError(msg.to_string())
+    }
+}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        SerdeError(msg.to_string())
+    }
+}
@@ -0,0 +1,522 @@
+use crate::prelude::*;
+use serde::ser::{self, Impossible, Serialize};
+
+use crate::{
+    mawu_value::{MawuObject, MawuValue},
+    serde_support::SerdeError,
+};
+
+/// Serializes any `T` into a `MawuValue`.
+///
+/// Non-negative integers become a `MawuValue::Uint` and negative ones a `MawuValue::Int`, just like parsing the resulting JSON would.
+pub fn to_mawu_value<T: Serialize + ?Sized>(value: &T) -> Result<MawuValue, SerdeError> {
+    value.serialize(MawuValueSerializer)
+}
+
+fn key_error() -> SerdeError {
+    SerdeError("object keys must be strings, chars, numbers or bools".to_string())
+}
+
+/// Wraps `value` in an object with the variant as its only key, the way externally tagged enums are written
+fn variant_object(variant: &str, value: MawuValue) -> MawuValue {
+    let mut object = MawuObject::new();
+    object.insert(variant.to_string(), value);
+    MawuValue::Object(object)
+}
+
+struct MawuValueSerializer;
+
+impl ser::Serializer for MawuValueSerializer {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    type SerializeSeq = SerializeArray;
+    type SerializeTuple = SerializeArray;
+    type SerializeTupleStruct = SerializeArray;
+    type SerializeTupleVariant = SerializeArray;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeObject;
+
+    fn serialize_bool(self, v: bool) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<MawuValue, SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<MawuValue, SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<MawuValue, SerdeError> {
+        self.serialize_i64(v as i64)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<MawuValue, SerdeError> {
+        if v < 0 {
+            Ok(MawuValue::Int(v))
+        } else {
+            Ok(MawuValue::Uint(v as u64))
+        }
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<MawuValue, SerdeError> {
+        match (i64::try_from(v), u64::try_from(v)) {
+            (Ok(i), _) => self.serialize_i64(i),
+            (_, Ok(u)) => Ok(MawuValue::Uint(u)),
+            _ => Err(SerdeError(format!("{} is too large to be represented", v))),
+        }
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Uint(v as u64))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Uint(v as u64))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Uint(v as u64))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Uint(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<MawuValue, SerdeError> {
+        u64::try_from(v)
+            .map(MawuValue::Uint)
+            .map_err(|_| SerdeError(format!("{} is too large to be represented", v)))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Float(v as f64))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::Array(v.iter().map(|b| MawuValue::Uint(*b as u64)).collect()))
+    }
+
+    fn serialize_none(self) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::None)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<MawuValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::None)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::None)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<MawuValue, SerdeError> {
+        Ok(MawuValue::String(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<MawuValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<MawuValue, SerdeError> {
+        Ok(variant_object(variant, to_mawu_value(value)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray { variant: None, values: Vec::with_capacity(len.unwrap_or_default()) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SerializeArray, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SerializeArray, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SerializeArray, SerdeError> {
+        Ok(SerializeArray { variant: Some(variant), values: Vec::with_capacity(len) })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<SerializeObject, SerdeError> {
+        Ok(SerializeObject { variant: None, object: MawuObject::new(), next_key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeObject, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<SerializeObject, SerdeError> {
+        Ok(SerializeObject { variant: Some(variant), object: MawuObject::new(), next_key: None })
+    }
+}
+
+/// Collects sequences, tuples and tuple variants, which are wrapped in an object if `variant` is set
+struct SerializeArray {
+    variant: Option<&'static str>,
+    values: Vec<MawuValue>,
+}
+
+impl SerializeArray {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.values.push(to_mawu_value(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<MawuValue, SerdeError> {
+        let array = MawuValue::Array(self.values);
+        match self.variant {
+            Some(variant) => Ok(variant_object(variant, array)),
+            None => Ok(array),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SerializeArray {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+/// Collects maps, structs and struct variants, which are wrapped in an object if `variant` is set
+struct SerializeObject {
+    variant: Option<&'static str>,
+    object: MawuObject,
+    next_key: Option<String>,
+}
+
+impl SerializeObject {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), SerdeError> {
+        self.object.insert(key.to_string(), to_mawu_value(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<MawuValue, SerdeError> {
+        let object = MawuValue::Object(self.object);
+        match self.variant {
+            Some(variant) => Ok(variant_object(variant, object)),
+            None => Ok(object),
+        }
+    }
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.next_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| SerdeError("serialize_value called before serialize_key".to_string()))?;
+        self.insert(&key, value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeObject {
+    type Ok = MawuValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<MawuValue, SerdeError> {
+        self.finish()
+    }
+}
+
+/// Turns map keys into strings, as JSON only allows string keys
+struct MapKeySerializer;
+
+impl ser::Serializer for MapKeySerializer {
+    type Ok = String;
+    type Error = SerdeError;
+
+    type SerializeSeq = Impossible<String, SerdeError>;
+    type SerializeTuple = Impossible<String, SerdeError>;
+    type SerializeTupleStruct = Impossible<String, SerdeError>;
+    type SerializeTupleVariant = Impossible<String, SerdeError>;
+    type SerializeMap = Impossible<String, SerdeError>;
+    type SerializeStruct = Impossible<String, SerdeError>;
+    type SerializeStructVariant = Impossible<String, SerdeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_none(self) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<String, SerdeError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<String, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(key_error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(key_error())
+    }
+}
+
+#[test]
+fn serialize_round_trip() {
+    use serde::{Deserialize, Serialize};
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Shape {
+        Dot,
+        Circle(f64),
+        Line(i8, i8),
+        Rect { w: u8, h: u8 },
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Drawing {
+        name: String,
+        shapes: Vec<Shape>,
+        layer: Option<i32>,
+    }
+
+    let drawing = Drawing {
+        name: "sketch".to_string(),
+        shapes: vec![Shape::Dot, Shape::Circle(1.5), Shape::Line(-1, 1), Shape::Rect { w: 2, h: 3 }],
+        layer: None,
+    };
+    let value = to_mawu_value(&drawing).unwrap();
+    assert_eq!(value, crate::mawu!({
+        "name": "sketch",
+        "shapes": ["Dot", {"Circle": 1.5}, {"Line": [-1, 1u64]}, {"Rect": {"w": 2u64, "h": 3u64}}],
+        "layer": null
+    }));
+    assert_eq!(crate::serde_support::de::from_mawu_value::<Drawing>(value).unwrap(), drawing);
+    assert_eq!(to_mawu_value(&BTreeMap::from([(1u32, 'a'), (20, 'b')])).unwrap(), crate::mawu!({"1": "a", "20": "b"}));
+    assert!(to_mawu_value(&BTreeMap::from([(vec![1], 1)])).is_err());
+    assert!(to_mawu_value(&u128::MAX).is_err());
+}
@@ -1,46 +1,76 @@
-use crate::{errors::{csv_error::{CsvError, CsvWriteError}, MawuError}, mawu_value::MawuValue, utils::make_whitespace};
+use crate::prelude::*;
+use crate::{
+    errors::{csv_error::{CsvError, CsvWriteError}, MawuError},
+    mawu_value::MawuValue,
+    serializers::csv_write_options::{CsvWriteOptions, QuoteStyle},
+    utils::make_whitespace,
+};
 
-fn serialize_csv_string(value: String, spaces: u8) -> Result<String, MawuError> {
-            let mut out = format!("{}\"", make_whitespace(spaces));
-            let tmp = value.replace("\"", "\"\"");
-            out.push_str(&tmp);
-            out.push('"');
-            Ok(out)
+/// Values containing the delimiter, the quote character or a newline have to be quoted to be read back
+fn needs_quoting(text: &str, delimiter: char) -> bool {
+    text.contains([delimiter, '"', '\n', '\r'])
 }
 
-fn serialize_csv_value<T: Into<MawuValue>>(value: T, spaces: u8) -> Result<String, MawuError> {
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// Applies the `QuoteStyle` to the text of a value, `QuoteStyle::Strings` only quotes it if `is_string` is `true`
+fn quote_field(text: String, is_string: bool, delimiter: char, options: &CsvWriteOptions) -> Result<String, MawuError> {
+    match options.quote_style {
+        QuoteStyle::Strings if is_string => Ok(quote(&text)),
+        QuoteStyle::Always => Ok(quote(&text)),
+        QuoteStyle::Minimal if needs_quoting(&text, delimiter) => Ok(quote(&text)),
+        QuoteStyle::Never if needs_quoting(&text, delimiter) => {
+            Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NeedsQuoting(text))))
+        }
+        _ => Ok(text),
+    }
+}
+
+fn serialize_csv_value<T: Into<MawuValue>>(value: T, spaces: u8, delimiter: char, options: &CsvWriteOptions) -> Result<String, MawuError> {
     let value = value.into();
     match value {
-        MawuValue::String(s) => serialize_csv_string(s, spaces),
-        MawuValue::Uint(u) => Ok(format!("{}{}", make_whitespace(spaces), u)),
-        MawuValue::Int(i) => Ok(format!("{}{}", make_whitespace(spaces), i)),
+        MawuValue::String(s) => Ok(format!("{}{}", make_whitespace(spaces), quote_field(s, true, delimiter, options)?)),
+        MawuValue::Uint(u) => Ok(format!("{}{}", make_whitespace(spaces), quote_field(u.to_string(), false, delimiter, options)?)),
+        MawuValue::Int(i) => Ok(format!("{}{}", make_whitespace(spaces), quote_field(i.to_string(), false, delimiter, options)?)),
         MawuValue::Float(f) => {
-            if f.fract() == 0.0 {
-                Ok(format!("{}{}.0", make_whitespace(spaces), f.to_string()))
+            let text = if f % 1.0 == 0.0 {
+                format!("{}.0", f)
             } else {
-               Ok(format!("{}{}", make_whitespace(spaces), f)) 
-            }
-        }, 
-        MawuValue::Bool(b) => Ok(format!("{}{}", make_whitespace(spaces), b)),
+                f.to_string()
+            };
+            Ok(format!("{}{}", make_whitespace(spaces), quote_field(text, false, delimiter, options)?))
+        },
+        MawuValue::Bool(b) => Ok(format!("{}{}", make_whitespace(spaces), quote_field(b.to_string(), false, delimiter, options)?)),
         MawuValue::Array(a) => {
-            let mut out = format!("{}[", make_whitespace(spaces));
+            // the values inside of the brackets always look the same, the quote style applies to the whole array
+            let mut out = String::from("[");
             for v in a {
-                out.push_str(&serialize_csv_value(v, spaces)?);
+                out.push_str(&serialize_csv_value(v, spaces, delimiter, &CsvWriteOptions::default())?);
                 out.push(',');
             }
             out = out.trim_end_matches(',').to_string();
             out.push(']');
-            Ok(out)
+            Ok(format!("{}{}", make_whitespace(spaces), quote_field(out, false, delimiter, options)?))
         }
+        MawuValue::None if options.quote_style == QuoteStyle::Always => Ok(format!("{}\"\"", make_whitespace(spaces))),
         MawuValue::None => Ok(String::new()),
         // All other types are not allowed
-        MawuValue::Object(_) => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType("Object".to_string())))).unwrap(),
-        MawuValue::CSVArray(_) => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType("CSV-Array inside CSV-Value".to_string())))).unwrap(),
-        MawuValue::CSVObject(_) => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType("CSV-Object inside CSV-Value".to_string())))).unwrap(),
+        MawuValue::Object(_) => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType("Object".to_string())))),
+        MawuValue::CSVArray(_) => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType("CSV-Array inside CSV-Value".to_string())))),
+        MawuValue::CSVObject(_) => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType("CSV-Object inside CSV-Value".to_string())))),
     }
 }
 
-pub fn serialize_csv_headed(value: MawuValue, spaces: u8) -> Result<String, MawuError> {
+fn inconsistent_columns(index: usize) -> MawuError {
+    MawuError::CsvError(CsvError::WriteError(CsvWriteError::InconsistentColumns(format!(
+        "row {} does not have the same columns as the first row",
+        index
+    ))))
+}
+
+pub fn serialize_csv_headed(value: MawuValue, spaces: u8, options: &CsvWriteOptions) -> Result<String, MawuError> {
     // Headed: Vec<HashMap<String, MawuValue>>
 
     let mut head_created = false;
@@ -48,36 +78,25 @@ pub fn serialize_csv_headed(value: MawuValue, spaces: u8) -> Result<String, Mawu
     let mut body: Vec<String> = Default::default();
     let mut keys: Vec<String> = Default::default();
     if !value.is_csv_object() {
-        let val_type = {
-            match value {
-                MawuValue::CSVArray(_) => "CSV-Array",
-                MawuValue::Array(_) => "Array",
-                MawuValue::Object(_) => "Object",
-                MawuValue::None => "None",
-                MawuValue::Bool(_) => "Bool",
-                MawuValue::Uint(_) => "Uint",
-                MawuValue::Int(_) => "Int",
-                MawuValue::Float(_) => "Float",
-                MawuValue::String(_) => "String",
-                _ => "Unknown",
-            }
-        };
-        return Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType(format!("{} is not a MawuValue::CsvObject!", val_type)))));
+        return Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(value.type_name().to_string()))));
     }
-    for map in value.to_csv_object().unwrap() {
+    for (index, map) in value.to_csv_object().unwrap().into_iter().enumerate() {
         let mut row: String = Default::default();
         for (key, _) in &map {
             if !head_created {
                 keys.push(key.clone());
                 head.push_str(make_whitespace(spaces).as_str());
-                head.push_str(&key);
+                head.push_str(&quote_field(key.clone(), false, ',', options)?);
                 head.push(',');
             }
         }
         head_created = true;
+        if map.len() != keys.len() || keys.iter().any(|key| !map.contains_key(key)) {
+            return Err(inconsistent_columns(index));
+        }
         for key in keys.clone() {
             let get_val = map.get(&key).unwrap();
-            row.push_str(&serialize_csv_value(get_val, spaces)?);
+            row.push_str(&serialize_csv_value(get_val, spaces, ',', options)?);
             row.push(',');
         }
         row = row.trim_end_matches(',').to_string();
@@ -90,32 +109,18 @@ pub fn serialize_csv_headed(value: MawuValue, spaces: u8) -> Result<String, Mawu
     Ok(out)
 }
 
-pub fn serialize_csv_unheaded(value: MawuValue, spaces: u8) -> Result<String, MawuError> {
+pub fn serialize_csv_unheaded(value: MawuValue, spaces: u8, options: &CsvWriteOptions) -> Result<String, MawuError> {
     // Input == Vec<Vec<MawuValue>>
     // First vec holds rows, second vec holds data in each row
     // output == String, with each row on a new line, values separated by commas
     let mut out = format!("{}", make_whitespace(spaces));
     if !value.is_csv_array() {
-        let val_type = {
-            match value {
-                MawuValue::CSVObject(_) => "CSV-Object",
-                MawuValue::Array(_) => "Array",
-                MawuValue::Object(_) => "Object",
-                MawuValue::None => "None",
-                MawuValue::Bool(_) => "Bool",
-                MawuValue::Uint(_) => "Uint",
-                MawuValue::Int(_) => "Int",
-                MawuValue::Float(_) => "Float",
-                MawuValue::String(_) => "String",
-                _ => "Unknown",
-            }
-        };
-        return Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType(format!("{} is not a MawuValue::CsvArray!", val_type)))));
+        return Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(value.type_name().to_string()))));
     }
     for v in value.to_csv_array().unwrap() {
         let mut row = String::new();
         for i in v {
-            row.push_str(&serialize_csv_value(i, spaces)?);
+            row.push_str(&serialize_csv_value(i, spaces, ',', options)?);
             row.push(',');
         }
         row = row.trim_end_matches(',').to_string();
@@ -124,3 +129,41 @@ pub fn serialize_csv_unheaded(value: MawuValue, spaces: u8) -> Result<String, Ma
     }
     Ok(out)
 }
+
+/// Writes a `MawuValue::CSVObject` or `MawuValue::CSVArray` to `writer` one record at a time, separating the values with `delimiter`.
+///
+/// Every record is formatted like `serialize_csv_headed` and `serialize_csv_unheaded` format it, without padding,
+/// and ends with a newline. The header, taken from the first row, is only written for a `MawuValue::CSVObject` if `headed` is `true`.
+/// Records written before an error are not taken back.
+#[cfg(feature = "std")]
+pub fn serialize_csv_to_writer<W: std::io::Write>(mut writer: W, value: &MawuValue, delimiter: char, headed: bool) -> Result<(), MawuError> {
+    let options = CsvWriteOptions::default();
+    let separator = delimiter.to_string();
+    let mut write_record = |fields: Vec<String>| {
+        writer
+            .write_all(format!("{}\n", fields.join(&separator)).as_bytes())
+            .map_err(|e| MawuError::CsvError(CsvError::WriteError(CsvWriteError::IoError(e))))
+    };
+    match value {
+        MawuValue::CSVObject(rows) => {
+            let keys: Vec<&String> = rows.first().map(|row| row.keys().collect()).unwrap_or_default();
+            if headed && !keys.is_empty() {
+                write_record(keys.iter().map(|key| quote_field(key.to_string(), false, delimiter, &options)).collect::<Result<_, _>>()?)?;
+            }
+            for (index, row) in rows.iter().enumerate() {
+                if row.len() != keys.len() || keys.iter().any(|key| !row.contains_key(*key)) {
+                    return Err(inconsistent_columns(index));
+                }
+                write_record(keys.iter().map(|key| serialize_csv_value(&row[*key], 0, delimiter, &options)).collect::<Result<_, _>>()?)?;
+            }
+            Ok(())
+        }
+        MawuValue::CSVArray(rows) => {
+            for row in rows {
+                write_record(row.iter().map(|value| serialize_csv_value(value, 0, delimiter, &options)).collect::<Result<_, _>>()?)?;
+            }
+            Ok(())
+        }
+        _ => Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(value.type_name().to_string())))),
+    }
+}
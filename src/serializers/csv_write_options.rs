@@ -0,0 +1,46 @@
+/// Which values are written inside of quotes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum QuoteStyle {
+    /// Quote every string value, but no numbers, booleans or header names
+    #[default]
+    Strings,
+    /// Quote only the values containing the delimiter, the quote character or a newline
+    Minimal,
+    /// Quote every value and header name, empty values included
+    Always,
+    /// Never quote, and return a `CsvWriteError::NeedsQuoting` for values that would need it
+    Never,
+}
+
+/// Options changing how CSV is written.
+///
+/// `CsvWriteOptions::default()` and `new()` return the options `csv::to_string` uses,
+/// every option can then be changed with the method of the same name.
+///
+/// ## Example
+/// ```rust
+/// use mawu::csv::{CsvWriteOptions, QuoteStyle};
+///
+/// let options = CsvWriteOptions::new().quote_style(QuoteStyle::Minimal);
+/// assert_ne!(options, CsvWriteOptions::default());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CsvWriteOptions {
+    pub(crate) quote_style: QuoteStyle,
+}
+
+impl CsvWriteOptions {
+    /// Creates new `CsvWriteOptions`, identical to `CsvWriteOptions::default()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Which values are written inside of quotes.
+    /// A quote inside of a quoted value is always escaped by doubling it.
+    ///
+    /// Default: `QuoteStyle::Strings`
+    pub fn quote_style(mut self, quote_style: QuoteStyle) -> Self {
+        self.quote_style = quote_style;
+        self
+    }
+}
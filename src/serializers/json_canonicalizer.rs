@@ -0,0 +1,158 @@
+use crate::prelude::*;
+use crate::{
+    errors::{
+        json_error::{JsonError, JsonWriteError},
+        MawuError,
+    },
+    mawu_value::MawuValue,
+};
+
+/// Serializes a `MawuValue` following the JSON Canonicalization Scheme (RFC 8785).
+///
+/// Keys are sorted by their UTF-16 code units, there is no whitespace, strings only escape what
+/// they have to and floats are written like ECMAScript's `Number.prototype.toString` would.
+/// `Uint` and `Int` are written as the exact integer they hold, even if it is too large to be
+/// represented by a `f64` without loss.
+pub fn canonicalize_json(value: &MawuValue) -> Result<String, MawuError> {
+    let mut out = String::new();
+    canonicalize_into(value, &mut out)?;
+    Ok(out)
+}
+
+fn canonicalize_into(value: &MawuValue, out: &mut String) -> Result<(), MawuError> {
+    match value {
+        MawuValue::Object(o) => {
+            let mut pairs = o.iter().collect::<Vec<_>>();
+            pairs.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+            out.push('{');
+            for (index, (key, value)) in pairs.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                canonicalize_string(key, out);
+                out.push(':');
+                canonicalize_into(value, out)?;
+            }
+            out.push('}');
+        }
+        MawuValue::Array(a) => {
+            out.push('[');
+            for (index, value) in a.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                canonicalize_into(value, out)?;
+            }
+            out.push(']');
+        }
+        MawuValue::None => out.push_str("null"),
+        MawuValue::Bool(b) => out.push_str(&b.to_string()),
+        MawuValue::Uint(u) => out.push_str(&u.to_string()),
+        MawuValue::Int(i) => out.push_str(&i.to_string()),
+        MawuValue::Float(f) => out.push_str(&canonicalize_float(*f)?),
+        MawuValue::String(s) => canonicalize_string(s, out),
+        MawuValue::CSVObject(_) => {
+            return Err(MawuError::JsonError(JsonError::WriteError(
+                JsonWriteError::NotJSONType("CSVObject".to_string()),
+            )))
+        }
+        MawuValue::CSVArray(_) => {
+            return Err(MawuError::JsonError(JsonError::WriteError(
+                JsonWriteError::NotJSONType("CSVArray".to_string()),
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn canonicalize_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Rust already formats floats with the shortest digits that round-trip, just like ECMAScript.
+/// The only differences are the switch to exponential notation outside of `1e-6 <= |f| < 1e21`
+/// and the explicit `+` of positive exponents.
+fn canonicalize_float(value: f64) -> Result<String, MawuError> {
+    if !value.is_finite() {
+        return Err(MawuError::JsonError(JsonError::WriteError(
+            JsonWriteError::InvalidNumber(value.to_string()),
+        )));
+    }
+    if value == 0.0 {
+        // also covers `-0.0`
+        return Ok("0".to_string());
+    }
+    let abs = value.abs();
+    if (1e-6..1e21).contains(&abs) {
+        Ok(format!("{}", value))
+    } else {
+        let exponential = format!("{:e}", value);
+        if exponential.contains("e-") {
+            Ok(exponential)
+        } else {
+            Ok(exponential.replace('e', "e+"))
+        }
+    }
+}
+
+#[test]
+fn canonical_floats() {
+    // the number examples from RFC 8785, appendix B
+    let cases = [
+        (0.0, "0"),
+        (-0.0, "0"),
+        (5e-324, "5e-324"),
+        (-5e-324, "-5e-324"),
+        (1.7976931348623157e308, "1.7976931348623157e+308"),
+        (-1.7976931348623157e308, "-1.7976931348623157e+308"),
+        (9007199254740992.0, "9007199254740992"),
+        (-9007199254740992.0, "-9007199254740992"),
+        (295147905179352830000.0, "295147905179352830000"),
+        (9.999999999999997e22, "9.999999999999997e+22"),
+        (1e23, "1e+23"),
+        (999999999999999700000.0, "999999999999999700000"),
+        (999999999999999900000.0, "999999999999999900000"),
+        (1e21, "1e+21"),
+        (9.999999999999997e-7, "9.999999999999997e-7"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (333333333.3333332, "333333333.3333332"),
+        (4.5, "4.5"),
+        (2e-3, "0.002"),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(canonicalize_float(value).unwrap(), expected);
+    }
+    assert!(canonicalize_float(f64::NAN).is_err());
+    assert!(canonicalize_float(f64::INFINITY).is_err());
+}
+
+#[test]
+fn canonical_output() {
+    let value = crate::mawu!({
+        "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+        "string": "\u{20ac}$\u{f}\nA'B\"\\\\\"/",
+        "literals": [null, true, false]
+    });
+    assert_eq!(
+        canonicalize_json(&value).unwrap(),
+        "{\"literals\":[null,true,false],\"numbers\":[333333333.3333333,1e+30,4.5,0.002,1e-27],\"string\":\"\u{20ac}$\\u000f\\nA'B\\\"\\\\\\\\\\\"/\"}"
+    );
+    // sorted by UTF-16 code units, so the surrogate pair of the emoji sorts before U+FB33
+    let value = crate::mawu!({"\u{fb33}": 1, "\u{1f600}": 2, "a": 3, "B": 4});
+    assert_eq!(canonicalize_json(&value).unwrap(), "{\"B\":4,\"a\":3,\"\u{1f600}\":2,\"\u{fb33}\":1}");
+}
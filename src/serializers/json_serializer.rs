@@ -1,99 +1,120 @@
-use crate::{errors::{json_error::{JsonError, JsonWriteError}, MawuError}, mawu_value::MawuValue, utils::make_whitespace};
+use core::fmt::Write;
 
-pub fn serialize_json(value: MawuValue, spaces: u8, depth: u16) -> Result<String, MawuError> {
+use crate::prelude::*;
+use crate::{
+    errors::{json_error::{JsonError, JsonWriteError}, MawuError},
+    mawu_value::MawuValue,
+    serializers::json_write_options::{JsonWriteOptions, NonFiniteFloats},
+    utils::push_whitespace,
+};
+
+pub fn serialize_json(value: &MawuValue, spaces: u8) -> Result<String, MawuError> {
+    serialize_json_with(value, spaces, &JsonWriteOptions::default())
+}
+
+pub fn serialize_json_with(value: &MawuValue, spaces: u8, options: &JsonWriteOptions) -> Result<String, MawuError> {
     let mut out: String = Default::default();
+    write_json(&mut out, value, spaces, 0, true, options)?;
+    Ok(out)
+}
+
+/// Writes `value` onto the end of `out`.
+///
+/// Every value starts with its own indentation, which is left out if `trim_start` is set,
+/// as it is for the top-level value and the values of an object, that follow their key directly.
+fn write_json(out: &mut String, value: &MawuValue, spaces: u8, depth: u16, trim_start: bool, options: &JsonWriteOptions) -> Result<(), MawuError> {
     let current_whitespace = (spaces as usize).saturating_mul(depth as usize);
     let next_depth = depth.saturating_add(1);
     let next_whitespace = (spaces as usize).saturating_mul(next_depth as usize);
     let is_pretty = spaces > 0;
     match value {
         MawuValue::Object(o) => {
-            if is_pretty {
+            if is_pretty && !trim_start {
                 out.push('\n');
+                push_whitespace(out, current_whitespace);
             }
-            out.push_str(format!("{}{{", make_whitespace(current_whitespace)).as_str());
+            out.push('{');
             if is_pretty {
                 out.push('\n');
             }
-            for (key, value) in o {
-                out.push_str(format!("{}\"{}\":", make_whitespace(next_whitespace), key).as_str());
-                if is_pretty {
-                    out.push(' ');
+            for (n, (key, value)) in o.iter().enumerate() {
+                if n > 0 {
+                    out.push(',');
+                    if is_pretty {
+                        out.push('\n');
+                    }
                 }
-                out.push_str(&serialize_json(value, spaces, next_depth)?.trim_start());
-                out.push(',');
                 if is_pretty {
-                    out.push('\n');
+                    push_whitespace(out, next_whitespace);
                 }
-            }
-            out = {
+                write_json_string(out, key);
+                out.push(':');
                 if is_pretty {
-                    out.trim_end_matches(",\n").to_string()
-                } else {
-                    out.trim_end_matches(',').to_string()
+                    out.push(' ');
                 }
-            };
+                write_json(out, value, spaces, next_depth, true, options)?;
+            }
             if is_pretty {
                 out.push('\n');
-                out.push_str(format!("{}}}", make_whitespace(current_whitespace)).as_str());
-            } else {
-                out.push('}');
+                push_whitespace(out, current_whitespace);
             }
-            
+            out.push('}');
         },
         MawuValue::Array(a) => {
-            if is_pretty {
+            if is_pretty && !trim_start {
                 out.push('\n');
+                push_whitespace(out, current_whitespace);
             }
-            out.push_str(format!("{}[", make_whitespace(current_whitespace)).as_str());
+            out.push('[');
             if is_pretty {
                 out.push('\n');
-                out.push_str(format!("{} ", make_whitespace(next_whitespace)).as_str());
+                push_whitespace(out, next_whitespace);
+                out.push(' ');
             }
-            for v in a {
-                out.push_str(&serialize_json(v, spaces, next_depth)?);
-                out.push(',');
-                if is_pretty {
-                    out.push(' ');
+            for (n, v) in a.iter().enumerate() {
+                if n > 0 {
+                    out.push(',');
+                    if is_pretty {
+                        out.push(' ');
+                    }
                 }
+                write_json(out, v, spaces, next_depth, false, options)?;
             }
-            out = {
-                if is_pretty {
-                    out.trim_end_matches(", ").to_string()
-                } else {
-                    out.trim_end_matches(',').to_string()
-                }
-            };
             if is_pretty {
                 out.push('\n');
-                out.push_str(format!("{}]", make_whitespace(current_whitespace)).as_str());
-            } else {
-                out.push(']');
+                push_whitespace(out, current_whitespace);
             }
+            out.push(']');
         },
         MawuValue::None => {
             out.push_str("null");
         },
         MawuValue::Bool(b) => {
-            out.push_str(format!("{}", b).as_str());
+            out.push_str(if *b { "true" } else { "false" });
         },
         MawuValue::Uint(u) => {
-            out.push_str(format!("{}", u).as_str());
+            // writing into a `String` can not fail
+            let _ = write!(out, "{}", u);
         },
         MawuValue::Int(i) => {
-            out.push_str(format!("{}", i).as_str());
+            let _ = write!(out, "{}", i);
         },
         MawuValue::Float(f) => {
-            // I don't know if this is correct, never worked or heard of fract() until googling
-            // right now
-            if f.fract() == 0.0 || f.fract() == -0.0 {
-                out.push_str(&format!("{}{}.0", make_whitespace(spaces), f));
+            if !trim_start {
+                push_whitespace(out, spaces as usize);
+            }
+            if f.is_finite() {
+                // `Debug` writes the shortest digits that round-trip, always with a fraction or an exponent,
+                // so `1.0` stays `1.0` and is read back as a float, and `1e21` is not written out digit by digit
+                let _ = write!(out, "{:?}", f);
+            } else if options.non_finite_floats == NonFiniteFloats::Null {
+                out.push_str("null");
             } else {
-               out.push_str(&format!("{}{}", make_whitespace(spaces), f));
+                Err(MawuError::JsonError(JsonError::WriteError(JsonWriteError::InvalidNumber(f.to_string()))))?
             }
         },
         MawuValue::String(s) => {
-            out.push_str(serialize_string_to_json(&s).as_str());
+            write_json_string(out, s);
         },
         MawuValue::CSVObject(_) => {
             Err(MawuError::JsonError(JsonError::WriteError(JsonWriteError::NotJSONType("CSVObject".to_string()))))?
@@ -102,34 +123,60 @@ pub fn serialize_json(value: MawuValue, spaces: u8, depth: u16) -> Result<String
             Err(MawuError::JsonError(JsonError::WriteError(JsonWriteError::NotJSONType("CSVArray".to_string()))))?
         },
     };
-    if depth == 0 {
-        out = out.trim_start().to_string();
-    }
-    Ok(out)
+    Ok(())
 }
 
-fn serialize_string_to_json(value: &str) -> String {
-    let mut tmp_bind: String = Default::default();
-    for (index, c) in value.chars().enumerate() {
-        if c == '"' {
-            tmp_bind.push_str("\\\"");
-        } else if c == '\\' {
-            tmp_bind.push_str("\\");
-            if index + 1 == value.len() {
-                tmp_bind.push_str("\\");
+/// Writes `value` as a quoted JSON string onto the end of `out`.
+/// Escapes `"`, `\\`, `/` and all control characters, everything else is written as is
+fn write_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '/' => out.push_str("\\/"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{0008}' => out.push_str("\\b"),
+            '\u{000C}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
             }
-        } else if c == '/' {
-            tmp_bind.push('\\');
-            tmp_bind.push('/');
-        } else if c == '\n' {
-            tmp_bind.push_str("\\n");
-        } else if c == '\r' {
-            tmp_bind.push_str("\\r");
-        } else if c == '\t' {
-            tmp_bind.push_str("\\t");
-        } else {
-            tmp_bind.push(c);
+            c => out.push(c),
         }
     }
-    format!("\"{}\"", tmp_bind)
+    out.push('"');
+}
+
+#[test]
+fn string_escaping() {
+    fn serialize_string_to_json(value: &str) -> String {
+        let mut out = String::new();
+        write_json_string(&mut out, value);
+        out
+    }
+    assert_eq!(serialize_string_to_json("plain"), "\"plain\"");
+    assert_eq!(serialize_string_to_json("a\"b\\c/d"), r#""a\"b\\c\/d""#);
+    assert_eq!(serialize_string_to_json("\\n"), r#""\\n""#);
+    assert_eq!(serialize_string_to_json("\n\r\t\u{0008}\u{000C}"), r#""\n\r\t\b\f""#);
+    assert_eq!(serialize_string_to_json("\u{0000}\u{001f}\u{007f}"), "\"\\u0000\\u001f\u{007f}\"");
+    assert_eq!(serialize_string_to_json("\u{30af}\u{1F600}"), "\"\u{30af}\u{1F600}\"");
+}
+
+#[test]
+fn float_round_trip() {
+    let cases = [(1.0, "1.0"), (1e21, "1e21"), (0.1, "0.1"), (-0.0, "-0.0"), (2.5e-7, "2.5e-7"), (1234.5, "1234.5")];
+    for (float, json) in cases {
+        let serialized = serialize_json(&MawuValue::Float(float), 0).unwrap();
+        assert_eq!(serialized, json);
+        let parsed = crate::lexers::json_lexer::json_lexer(serialized.chars().collect()).unwrap();
+        assert_eq!(parsed, MawuValue::Float(float));
+    }
+    for float in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        assert!(matches!(
+            serialize_json(&crate::mawu!([1, (float)]), 0),
+            Err(MawuError::JsonError(JsonError::WriteError(JsonWriteError::InvalidNumber(_))))
+        ));
+    }
 }
@@ -0,0 +1,41 @@
+/// What to do with floats that are `NaN` or infinite, as JSON has no way to write them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NonFiniteFloats {
+    /// Return a `JsonWriteError::InvalidNumber`
+    #[default]
+    Error,
+    /// Write them as `null`, reading the JSON back returns a `MawuValue::None` in their place
+    Null,
+}
+
+/// Options changing how JSON is written.
+///
+/// `JsonWriteOptions::default()` and `new()` return the options `json::to_string` uses,
+/// every option can then be changed with the method of the same name.
+///
+/// ## Example
+/// ```rust
+/// use mawu::json::{JsonWriteOptions, NonFiniteFloats};
+///
+/// let options = JsonWriteOptions::new().non_finite_floats(NonFiniteFloats::Null);
+/// assert_ne!(options, JsonWriteOptions::default());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct JsonWriteOptions {
+    pub(crate) non_finite_floats: NonFiniteFloats,
+}
+
+impl JsonWriteOptions {
+    /// Creates new `JsonWriteOptions`, identical to `JsonWriteOptions::default()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// What to do with floats that are `NaN` or infinite.
+    ///
+    /// Default: `NonFiniteFloats::Error`, so no invalid JSON is ever written
+    pub fn non_finite_floats(mut self, non_finite_floats: NonFiniteFloats) -> Self {
+        self.non_finite_floats = non_finite_floats;
+        self
+    }
+}
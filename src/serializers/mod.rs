@@ -1,2 +1,5 @@
 pub mod csv_serializer;
+pub mod csv_write_options;
+pub mod json_canonicalizer;
 pub mod json_serializer;
+pub mod json_write_options;
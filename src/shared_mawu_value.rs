@@ -0,0 +1,82 @@
+use alloc::sync::Arc;
+use core::{fmt, ops::Deref};
+
+use crate::mawu_value::MawuValue;
+
+#[derive(Clone, PartialEq)]
+/// A read-only `MawuValue` behind an `Arc`, returned by `MawuValue::into_shared`.
+///
+/// Cloning only increments a reference count instead of copying the whole tree, so the same
+/// parsed document can be handed to many threads or tasks cheaply.
+/// All reading methods of `MawuValue`, like `get`, `pointer` or `as_str`, are available through `Deref`.
+/// Use `into_inner` to get a `MawuValue` back that can be changed again.
+pub struct SharedMawuValue(Arc<MawuValue>);
+
+impl SharedMawuValue {
+    /// Wraps the value, same as `MawuValue::into_shared`
+    pub fn new(value: MawuValue) -> Self {
+        SharedMawuValue(Arc::new(value))
+    }
+
+    /// Returns the `MawuValue`, only copying it if other clones of this `SharedMawuValue` still exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// use mawu::mawu;
+    ///
+    /// let shared = mawu!([1, 2, 3]).into_shared();
+    /// let other = shared.clone();
+    /// assert_eq!(other.into_inner(), mawu!([1, 2, 3]));
+    /// assert_eq!(shared.into_inner(), mawu!([1, 2, 3]));
+    /// ```
+    pub fn into_inner(self) -> MawuValue {
+        Arc::try_unwrap(self.0).unwrap_or_else(|shared| (*shared).clone())
+    }
+
+    /// Returns `true` if both point to the same value, i.e. one is a clone of the other
+    pub fn ptr_eq(&self, other: &SharedMawuValue) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Deref for SharedMawuValue {
+    type Target = MawuValue;
+
+    fn deref(&self) -> &MawuValue {
+        &self.0
+    }
+}
+
+impl AsRef<MawuValue> for SharedMawuValue {
+    fn as_ref(&self) -> &MawuValue {
+        &self.0
+    }
+}
+
+impl From<MawuValue> for SharedMawuValue {
+    fn from(value: MawuValue) -> Self {
+        SharedMawuValue::new(value)
+    }
+}
+
+impl fmt::Debug for SharedMawuValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for SharedMawuValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[test]
+fn clones_share_the_value() {
+    let shared = crate::mawu!({"a": [1, 2, {"b": null}]}).into_shared();
+    let clone = shared.clone();
+    assert!(shared.ptr_eq(&clone));
+    assert!(!shared.ptr_eq(&SharedMawuValue::new(crate::mawu!({"a": [1, 2, {"b": null}]}))));
+    assert_eq!(clone.pointer("/a/1"), Some(&MawuValue::Uint(2)));
+    assert_eq!(*shared, crate::mawu!({"a": [1, 2, {"b": null}]}));
+}
@@ -1,14 +1,96 @@
-use std::{collections::VecDeque, fs::read_to_string, path::Path};
+use std::{char::decode_utf16, collections::VecDeque, fs::read, io::{ErrorKind, Read}, path::Path};
 
 use crate::errors::MawuError;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The text encodings Mawu can read files in
+pub enum Encoding {
+    /// UTF-8, the default for all reading functions
+    Utf8,
+    /// UTF-16 little endian, as written by Excel's "Unicode Text" export
+    Utf16Le,
+    /// UTF-16 big endian
+    Utf16Be,
+    /// ISO-8859-1, every byte is read as the unicode codepoint of the same value.
+    /// Windows-1252 files only differ in the range `0x80` to `0x9F`, so they are read correctly
+    /// as long as they contain none of the characters in that range (e.g. `€`).
+    Latin1,
+}
+
 /// This function reads the contents of a file, and converts the bytes from a Vec<u8> to a VecDeque<char>.
 /// It only accepts valid UTF-8 encoded files, returning an error otherwise.
+/// A leading UTF-8 BOM is stripped.
 pub fn read_file<T: AsRef<Path>>(path: T) -> Result<VecDeque<char>, MawuError> {
-    let out = read_to_string(path.as_ref()).map_err(|e| MawuError::IoError(e))?.chars().collect::<VecDeque<char>>();
+    read_file_with_encoding(path, Encoding::Utf8)
+}
+
+/// This function reads the raw bytes of a file.
+/// With the `flate2` feature enabled, gzip compressed files are recognised by their magic bytes and decompressed.
+fn read_bytes<T: AsRef<Path>>(path: T) -> Result<Vec<u8>, MawuError> {
+    decompress(read(path.as_ref()).map_err(MawuError::IoError)?)
+}
+
+/// This function reads everything the reader returns, and decodes it like `read_file` decodes a file.
+pub fn read_reader<R: Read>(mut reader: R) -> Result<VecDeque<char>, MawuError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(MawuError::IoError)?;
+    decode(decompress(bytes)?, Encoding::Utf8)
+}
+
+/// With the `flate2` feature enabled, gzip compressed bytes are recognised by their magic bytes and decompressed.
+fn decompress(bytes: Vec<u8>) -> Result<Vec<u8>, MawuError> {
+    #[cfg(feature = "flate2")]
+    if bytes.starts_with(&[0x1f, 0x8b]) {
+        let mut out = Vec::new();
+        flate2::read::MultiGzDecoder::new(bytes.as_slice())
+            .read_to_end(&mut out)
+            .map_err(MawuError::IoError)?;
+        return Ok(out);
+    }
+    Ok(bytes)
+}
+
+/// This function reads the contents of a file in the given encoding, and decodes the bytes into a VecDeque<char>.
+/// Returns an `IoError` of kind `InvalidData` if the file is not valid in the given encoding.
+/// A leading BOM is stripped.
+pub fn read_file_with_encoding<T: AsRef<Path>>(path: T, encoding: Encoding) -> Result<VecDeque<char>, MawuError> {
+    decode(read_bytes(path)?, encoding)
+}
+
+/// Decodes the bytes in the given encoding, stripping a leading BOM.
+fn decode(bytes: Vec<u8>, encoding: Encoding) -> Result<VecDeque<char>, MawuError> {
+    let mut out: VecDeque<char> = match encoding {
+        Encoding::Utf8 => String::from_utf8(bytes)
+            .map_err(|_| invalid_data("file did not contain valid UTF-8"))?
+            .chars()
+            .collect(),
+        Encoding::Utf16Le | Encoding::Utf16Be => {
+            if !bytes.len().is_multiple_of(2) {
+                return Err(invalid_data("file did not contain valid UTF-16"));
+            }
+            let units = bytes.chunks_exact(2).map(|pair| {
+                if encoding == Encoding::Utf16Le {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                }
+            });
+            decode_utf16(units)
+                .collect::<Result<VecDeque<char>, _>>()
+                .map_err(|_| invalid_data("file did not contain valid UTF-16"))?
+        }
+        Encoding::Latin1 => bytes.iter().map(|b| char::from(*b)).collect(),
+    };
+    if out.front() == Some(&'\u{FEFF}') {
+        let _ = out.pop_front();
+    }
     Ok(out)
 }
 
+fn invalid_data(message: &str) -> MawuError {
+    MawuError::IoError(std::io::Error::new(ErrorKind::InvalidData, message))
+}
+
 /// This function writes a file with the given contents.
 pub fn write_file<T: AsRef<Path>, C: AsRef<[u8]>>(path: T, contents: C) -> Result<(), MawuError> {
     std::fs::write(path.as_ref(), contents).map_err(|e| MawuError::IoError(e))
@@ -0,0 +1,437 @@
+use crate::prelude::*;
+use crate::{
+    errors::{
+        json_error::{JsonError, JsonPatchError, JsonPointerError},
+        MawuError,
+    },
+    mawu_value::{MawuObject, MawuValue},
+    utils::json_pointer::{array_index, join_tokens, parse_pointer, resolve, resolve_mut},
+};
+
+/// Applies a JSON Patch (RFC 6902) to the document.
+///
+/// The operations are applied to a copy of the document, which only replaces the document once
+/// every operation succeeded. If any operation fails, the document is left untouched.
+pub fn apply_patch(document: &mut MawuValue, patch: &MawuValue) -> Result<(), MawuError> {
+    let operations = match patch {
+        MawuValue::Array(a) => a,
+        _ => return Err(patch_error(JsonPatchError::NotAPatch)),
+    };
+    let mut patched = document.clone();
+    for operation in operations {
+        apply_operation(&mut patched, operation)?;
+    }
+    *document = patched;
+    Ok(())
+}
+
+fn apply_operation(document: &mut MawuValue, operation: &MawuValue) -> Result<(), MawuError> {
+    if !operation.is_object() {
+        return Err(patch_error(JsonPatchError::NotAPatch));
+    }
+    let op = match operation.get("op") {
+        Some(MawuValue::String(op)) => op.as_str(),
+        Some(other) => return Err(patch_error(JsonPatchError::InvalidOperation(other.to_string()))),
+        None => return Err(patch_error(JsonPatchError::MissingMember("op".to_string()))),
+    };
+    let path = pointer_member(operation, "path")?;
+    match op {
+        "add" => add(document, &path, value_member(operation)?.clone()),
+        "remove" => remove(document, &path).map(|_| ()),
+        "replace" => {
+            let value = value_member(operation)?.clone();
+            let target = resolve_mut(document, &path).ok_or_else(|| not_found(&path))?;
+            *target = value;
+            Ok(())
+        }
+        "move" => {
+            let from = pointer_member(operation, "from")?;
+            if from == path {
+                // resolving `from` is still required, moving a value onto itself changes nothing
+                return resolve(document, &from).map(|_| ()).ok_or_else(|| not_found(&from));
+            }
+            if path.starts_with(&from) {
+                return Err(patch_error(JsonPatchError::MoveIntoChild(join_tokens(&path))));
+            }
+            let value = remove(document, &from)?;
+            add(document, &path, value)
+        }
+        "copy" => {
+            let from = pointer_member(operation, "from")?;
+            let value = resolve(document, &from).ok_or_else(|| not_found(&from))?.clone();
+            add(document, &path, value)
+        }
+        "test" => {
+            let expected = value_member(operation)?;
+            let actual = resolve(document, &path).ok_or_else(|| not_found(&path))?;
+            if json_equal(actual, expected) {
+                Ok(())
+            } else {
+                Err(patch_error(JsonPatchError::TestFailed(join_tokens(&path))))
+            }
+        }
+        other => Err(patch_error(JsonPatchError::InvalidOperation(other.to_string()))),
+    }
+}
+
+fn add(document: &mut MawuValue, path: &[String], value: MawuValue) -> Result<(), MawuError> {
+    let (last, parent_path) = match path.split_last() {
+        Some(split) => split,
+        None => {
+            *document = value;
+            return Ok(());
+        }
+    };
+    match resolve_mut(document, parent_path).ok_or_else(|| not_found(parent_path))? {
+        MawuValue::Object(o) => {
+            o.insert(last.to_string(), value);
+            Ok(())
+        }
+        MawuValue::Array(a) => {
+            let index = if last == "-" {
+                a.len()
+            } else {
+                array_index(last)
+                    .filter(|index| *index <= a.len())
+                    .ok_or_else(|| invalid_index(path))?
+            };
+            a.insert(index, value);
+            Ok(())
+        }
+        _ => Err(not_found(path)),
+    }
+}
+
+/// Sets the value the JSON Pointer points to, used by `MawuValue::pointer_set`.
+///
+/// Unlike `add`, an existing array element is replaced instead of shifted, and only `-` appends.
+/// Every value on the way has to exist already.
+pub fn set(document: &mut MawuValue, pointer: &str, value: MawuValue) -> Result<(), MawuError> {
+    let path = parse_pointer(pointer).map_err(pointer_error)?;
+    let (last, parent_path) = match path.split_last() {
+        Some(split) => split,
+        None => {
+            *document = value;
+            return Ok(());
+        }
+    };
+    match resolve_mut(document, parent_path).ok_or_else(|| not_found(parent_path))? {
+        MawuValue::Object(o) => {
+            o.insert(last.to_string(), value);
+            Ok(())
+        }
+        MawuValue::Array(a) if last == "-" => {
+            a.push(value);
+            Ok(())
+        }
+        MawuValue::Array(a) => {
+            let target = array_index(last)
+                .and_then(|index| a.get_mut(index))
+                .ok_or_else(|| invalid_index(&path))?;
+            *target = value;
+            Ok(())
+        }
+        _ => Err(not_found(&path)),
+    }
+}
+
+/// Removes and returns the value the path points to, shifting later array elements down.
+/// The empty path takes the whole document, leaving `MawuValue::None` behind.
+pub fn remove(document: &mut MawuValue, path: &[String]) -> Result<MawuValue, MawuError> {
+    let (last, parent_path) = match path.split_last() {
+        Some(split) => split,
+        None => return Ok(document.take()),
+    };
+    match resolve_mut(document, parent_path).ok_or_else(|| not_found(parent_path))? {
+        MawuValue::Object(o) => o.remove(last).ok_or_else(|| not_found(path)),
+        MawuValue::Array(a) => {
+            let index = array_index(last)
+                .filter(|index| *index < a.len())
+                .ok_or_else(|| invalid_index(path))?;
+            Ok(a.remove(index))
+        }
+        _ => Err(not_found(path)),
+    }
+}
+
+/// Computes a JSON Patch (RFC 6902) that turns `from` into `to`.
+///
+/// Objects are compared key by key, emitting `add` and `remove` for keys only present on one side.
+/// Arrays are compared index by index up to the shorter length, then trailing elements are removed
+/// from the back or appended with `-`. Everything else that differs is `replace`d as a whole.
+pub fn diff(from: &MawuValue, to: &MawuValue) -> MawuValue {
+    let mut operations = Vec::new();
+    diff_into(from, to, &mut Vec::new(), &mut operations);
+    MawuValue::Array(operations)
+}
+
+fn diff_into(from: &MawuValue, to: &MawuValue, path: &mut Vec<String>, operations: &mut Vec<MawuValue>) {
+    match (from, to) {
+        (MawuValue::Object(a), MawuValue::Object(b)) => {
+            // sorted, so the same inputs always produce the same patch
+            let mut removed = a.keys().filter(|key| !b.contains_key(*key)).collect::<Vec<_>>();
+            removed.sort();
+            for key in removed {
+                path.push(key.to_string());
+                operations.push(operation("remove", path, None));
+                path.pop();
+            }
+            let mut keys = b.keys().collect::<Vec<_>>();
+            keys.sort();
+            for key in keys {
+                path.push(key.to_string());
+                match a.get(key) {
+                    Some(old) => diff_into(old, &b[key], path, operations),
+                    None => operations.push(operation("add", path, Some(&b[key]))),
+                }
+                path.pop();
+            }
+        }
+        (MawuValue::Array(a), MawuValue::Array(b)) => {
+            for (index, (old, new)) in a.iter().zip(b).enumerate() {
+                path.push(index.to_string());
+                diff_into(old, new, path, operations);
+                path.pop();
+            }
+            for index in (b.len()..a.len()).rev() {
+                path.push(index.to_string());
+                operations.push(operation("remove", path, None));
+                path.pop();
+            }
+            for new in b.iter().skip(a.len()) {
+                path.push("-".to_string());
+                operations.push(operation("add", path, Some(new)));
+                path.pop();
+            }
+        }
+        _ => {
+            if from != to {
+                operations.push(operation("replace", path, Some(to)));
+            }
+        }
+    }
+}
+
+fn operation(op: &str, path: &[String], value: Option<&MawuValue>) -> MawuValue {
+    let mut out = MawuObject::from([
+        ("op".to_string(), MawuValue::String(op.to_string())),
+        ("path".to_string(), MawuValue::String(join_tokens(path))),
+    ]);
+    if let Some(value) = value {
+        out.insert("value".to_string(), value.clone());
+    }
+    MawuValue::Object(out)
+}
+
+/// Compares two values as RFC 6902 requires for `test`, numbers are equal if their values are,
+/// regardless of them being a `Uint`, `Int` or `Float`.
+fn json_equal(a: &MawuValue, b: &MawuValue) -> bool {
+    match (a, b) {
+        (MawuValue::Array(a), MawuValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_equal(a, b))
+        }
+        (MawuValue::Object(a), MawuValue::Object(b)) => {
+            a.len() == b.len()
+                && a.iter().all(|(key, a)| b.get(key).is_some_and(|b| json_equal(a, b)))
+        }
+        (MawuValue::Uint(_) | MawuValue::Int(_), MawuValue::Uint(_) | MawuValue::Int(_)) => {
+            as_i128(a) == as_i128(b)
+        }
+        (MawuValue::Float(_), _) | (_, MawuValue::Float(_)) if a.is_number() && b.is_number() => {
+            a.to_float() == b.to_float()
+        }
+        _ => a == b,
+    }
+}
+
+fn as_i128(value: &MawuValue) -> Option<i128> {
+    match value {
+        MawuValue::Uint(u) => Some(*u as i128),
+        MawuValue::Int(i) => Some(*i as i128),
+        _ => None,
+    }
+}
+
+fn pointer_member(operation: &MawuValue, member: &str) -> Result<Vec<String>, MawuError> {
+    match operation.get(member) {
+        Some(MawuValue::String(pointer)) => parse_pointer(pointer).map_err(pointer_error),
+        _ => Err(patch_error(JsonPatchError::MissingMember(member.to_string()))),
+    }
+}
+
+fn value_member(operation: &MawuValue) -> Result<&MawuValue, MawuError> {
+    operation
+        .get("value")
+        .ok_or_else(|| patch_error(JsonPatchError::MissingMember("value".to_string())))
+}
+
+fn not_found(path: &[String]) -> MawuError {
+    pointer_error(JsonPointerError::PathNotFound(join_tokens(path)))
+}
+
+fn invalid_index(path: &[String]) -> MawuError {
+    pointer_error(JsonPointerError::InvalidIndex(join_tokens(path)))
+}
+
+fn pointer_error(error: JsonPointerError) -> MawuError {
+    MawuError::JsonError(JsonError::PointerError(error))
+}
+
+fn patch_error(error: JsonPatchError) -> MawuError {
+    MawuError::JsonError(JsonError::PatchError(error))
+}
+
+#[cfg(test)]
+fn parse(json: &str) -> MawuValue {
+    crate::lexers::json_lexer::json_lexer(json.chars().collect()).unwrap()
+}
+
+#[test]
+fn rfc6902_examples() {
+    // (document, patch, expected result), from RFC 6902 appendix A
+    let valid = [
+        // A.1 adding an object member
+        (r#"{"foo": "bar"}"#, r#"[{"op": "add", "path": "/baz", "value": "qux"}]"#, r#"{"baz": "qux", "foo": "bar"}"#),
+        // A.2 adding an array element
+        (r#"{"foo": ["bar", "baz"]}"#, r#"[{"op": "add", "path": "/foo/1", "value": "qux"}]"#, r#"{"foo": ["bar", "qux", "baz"]}"#),
+        // A.3 removing an object member
+        (r#"{"baz": "qux", "foo": "bar"}"#, r#"[{"op": "remove", "path": "/baz"}]"#, r#"{"foo": "bar"}"#),
+        // A.4 removing an array element
+        (r#"{"foo": ["bar", "qux", "baz"]}"#, r#"[{"op": "remove", "path": "/foo/1"}]"#, r#"{"foo": ["bar", "baz"]}"#),
+        // A.5 replacing a value
+        (r#"{"baz": "qux", "foo": "bar"}"#, r#"[{"op": "replace", "path": "/baz", "value": "boo"}]"#, r#"{"baz": "boo", "foo": "bar"}"#),
+        // A.6 moving a value
+        (
+            r#"{"foo": {"bar": "baz", "waldo": "fred"}, "qux": {"corge": "grault"}}"#,
+            r#"[{"op": "move", "from": "/foo/waldo", "path": "/qux/thud"}]"#,
+            r#"{"foo": {"bar": "baz"}, "qux": {"corge": "grault", "thud": "fred"}}"#,
+        ),
+        // A.7 moving an array element
+        (r#"{"foo": ["all", "grass", "cows", "eat"]}"#, r#"[{"op": "move", "from": "/foo/1", "path": "/foo/3"}]"#, r#"{"foo": ["all", "cows", "eat", "grass"]}"#),
+        // A.8 testing a value: success
+        (
+            r#"{"baz": "qux", "foo": ["a", 2, "c"]}"#,
+            r#"[{"op": "test", "path": "/baz", "value": "qux"}, {"op": "test", "path": "/foo/1", "value": 2}]"#,
+            r#"{"baz": "qux", "foo": ["a", 2, "c"]}"#,
+        ),
+        // A.10 adding a nested member object
+        (r#"{"foo": "bar"}"#, r#"[{"op": "add", "path": "/child", "value": {"grandchild": {}}}]"#, r#"{"foo": "bar", "child": {"grandchild": {}}}"#),
+        // A.11 ignoring unrecognized elements
+        (r#"{"foo": "bar"}"#, r#"[{"op": "add", "path": "/baz", "value": "qux", "xyz": 123}]"#, r#"{"foo": "bar", "baz": "qux"}"#),
+        // A.14 ~ escape ordering
+        (r#"{"/": 9, "~1": 10}"#, r#"[{"op": "test", "path": "/~01", "value": 10}]"#, r#"{"/": 9, "~1": 10}"#),
+        // A.16 adding an array value
+        (r#"{"foo": ["bar"]}"#, r#"[{"op": "add", "path": "/foo/-", "value": ["abc", "def"]}]"#, r#"{"foo": ["bar", ["abc", "def"]]}"#),
+    ];
+    for (document, patch, expected) in valid {
+        let mut document = parse(document);
+        apply_patch(&mut document, &parse(patch)).unwrap();
+        assert_eq!(document, parse(expected));
+    }
+
+    // A.9 testing a value: error
+    let mut document = parse(r#"{"baz": "qux"}"#);
+    let error = apply_patch(&mut document, &parse(r#"[{"op": "test", "path": "/baz", "value": "bar"}]"#));
+    assert!(matches!(error, Err(MawuError::JsonError(JsonError::PatchError(JsonPatchError::TestFailed(_))))));
+    // A.12 adding to a nonexistent target
+    let mut document = parse(r#"{"foo": "bar"}"#);
+    let error = apply_patch(&mut document, &parse(r#"[{"op": "add", "path": "/baz/bat", "value": "qux"}]"#));
+    assert!(matches!(error, Err(MawuError::JsonError(JsonError::PointerError(JsonPointerError::PathNotFound(_))))));
+    // A.15 comparing strings and numbers
+    let mut document = parse(r#"{"/": 9, "~1": 10}"#);
+    assert!(apply_patch(&mut document, &parse(r#"[{"op": "test", "path": "/~01", "value": "10"}]"#)).is_err());
+}
+
+#[test]
+fn patch_errors_leave_document_untouched() {
+    let original = parse(r#"{"a": [1, 2], "b": {"c": 1.0}}"#);
+    let mut document = original.clone();
+    let patch = parse(r#"[{"op": "remove", "path": "/a/0"}, {"op": "remove", "path": "/a/5"}]"#);
+    assert!(apply_patch(&mut document, &patch).is_err());
+    assert_eq!(document, original);
+
+    for patch in [
+        r#"{"op": "remove", "path": "/a"}"#,
+        r#"[{"op": "jump", "path": "/a"}]"#,
+        r#"[{"op": "add", "path": "/a"}]"#,
+        r#"[{"path": "/a"}]"#,
+        r#"[{"op": "move", "from": "/b", "path": "/b/c/d"}]"#,
+        r#"[{"op": "add", "path": "/a/01", "value": 0}]"#,
+        r#"[{"op": "replace", "path": "a", "value": 0}]"#,
+    ] {
+        assert!(apply_patch(&mut document, &parse(patch)).is_err(), "{}", patch);
+    }
+    assert_eq!(document, original);
+
+    // numbers are compared by value and copy duplicates
+    let patch = parse(r#"[{"op": "test", "path": "/b/c", "value": 1}, {"op": "copy", "from": "/a", "path": "/b/a"}, {"op": "replace", "path": "", "value": [null]}]"#);
+    let mut copy = document.clone();
+    apply_patch(&mut copy, &patch).unwrap();
+    assert_eq!(copy, parse("[null]"));
+    // the root pointer built with `mawu!` is the empty string as well
+    apply_patch(&mut copy, &crate::mawu!([{"op": "replace", "path": "", "value": {"a": "01"}}])).unwrap();
+    assert_eq!(copy, parse(r#"{"a": "01"}"#));
+}
+
+#[test]
+fn set_replaces_and_appends() {
+    let mut document = crate::mawu!({"users": [{"name": "Ada"}], "list": [1]});
+    set(&mut document, "/users/0/name", MawuValue::from("Grace")).unwrap();
+    set(&mut document, "/users/0/admin", MawuValue::from(true)).unwrap();
+    set(&mut document, "/list/-", MawuValue::Uint(2)).unwrap();
+    set(&mut document, "/list/0", MawuValue::Uint(0)).unwrap();
+    assert_eq!(document, crate::mawu!({"users": [{"name": "Grace", "admin": true}], "list": [0, 2]}));
+
+    let invalid_index = |r: Result<(), MawuError>| {
+        matches!(r, Err(MawuError::JsonError(JsonError::PointerError(JsonPointerError::InvalidIndex(_)))))
+    };
+    let not_found = |r: Result<(), MawuError>| {
+        matches!(r, Err(MawuError::JsonError(JsonError::PointerError(JsonPointerError::PathNotFound(_)))))
+    };
+    assert!(invalid_index(set(&mut document, "/list/2", MawuValue::None)));
+    assert!(invalid_index(set(&mut document, "/list/a", MawuValue::None)));
+    assert!(not_found(set(&mut document, "/users/0/name/first", MawuValue::None)));
+    assert!(not_found(set(&mut document, "/missing/key", MawuValue::None)));
+    assert!(matches!(
+        set(&mut document, "users", MawuValue::None),
+        Err(MawuError::JsonError(JsonError::PointerError(JsonPointerError::InvalidPointer(_))))
+    ));
+    set(&mut document, "", MawuValue::None).unwrap();
+    assert_eq!(document, MawuValue::None);
+}
+
+#[test]
+fn pointer_remove_shifts_arrays() {
+    let mut document = crate::mawu!({"users": [{"name": "Ada", "admin": true}, {"name": "Grace"}]});
+    assert_eq!(document.pointer_remove("/users/0/admin"), Some(MawuValue::Bool(true)));
+    assert_eq!(document.pointer_remove("/users/0"), Some(crate::mawu!({"name": "Ada"})));
+    assert_eq!(document, crate::mawu!({"users": [{"name": "Grace"}]}));
+    for missing in ["/users/1", "/users/-", "/users/0/age", "/users/0/name/0", "users"] {
+        assert_eq!(document.pointer_remove(missing), None, "{}", missing);
+    }
+    assert_eq!(document.pointer_remove(""), Some(crate::mawu!({"users": [{"name": "Grace"}]})));
+    assert_eq!(document, MawuValue::None);
+}
+
+#[test]
+fn diff_then_patch() {
+    let pairs = [
+        (r#"{"a": 1, "b": [1, 2, 3], "c": {"d": "e"}}"#, r#"{"a": 2, "b": [1, 5], "c": {"f": null}, "g": true}"#),
+        (r#"[1, [2, 3]]"#, r#"[1, [2, 3, 4], {"x": []}, "y"]"#),
+        (r#"{"a/b": {"~": 1}}"#, r#"{"a/b": {"~": 1.5}}"#),
+        (r#"{"same": [1, {"x": null}]}"#, r#"{"same": [1, {"x": null}]}"#),
+        (r#"[1, 2]"#, r#"{"now": "an object"}"#),
+        ("1", "-1"),
+    ];
+    for (a, b) in pairs {
+        let (mut a, b) = (parse(a), parse(b));
+        let patch = diff(&a, &b);
+        apply_patch(&mut a, &patch).unwrap();
+        assert_eq!(a, b);
+    }
+    assert_eq!(diff(&parse(r#"{"a": [1]}"#), &parse(r#"{"a": [1]}"#)), MawuValue::Array(vec![]));
+    assert_eq!(
+        diff(&parse(r#"{"a": 1, "b": 2}"#), &parse(r#"{"b": 3, "c": 4}"#)),
+        parse(r#"[{"op": "remove", "path": "/a"}, {"op": "replace", "path": "/b", "value": 3}, {"op": "add", "path": "/c", "value": 4}]"#)
+    );
+}
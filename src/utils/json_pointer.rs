@@ -0,0 +1,190 @@
+use crate::prelude::*;
+use crate::{errors::json_error::JsonPointerError, mawu_value::MawuValue};
+
+/// Splits a JSON Pointer (RFC 6901) into its unescaped reference tokens.
+/// The empty pointer `""` points to the whole document and results in no tokens.
+///
+/// ## Errors
+/// `JsonPointerError::InvalidPointer` if the pointer does not start with `/`, or a `~` is not
+/// followed by `0` or `1`
+pub fn parse_pointer(pointer: &str) -> Result<Vec<String>, JsonPointerError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !pointer.starts_with('/') {
+        return Err(JsonPointerError::InvalidPointer(pointer.to_string()));
+    }
+    pointer[1..]
+        .split('/')
+        .map(|token| unescape_token(token).ok_or_else(|| JsonPointerError::InvalidPointer(pointer.to_string())))
+        .collect()
+}
+
+/// Splits a path into reference tokens like `parse_pointer`, but accepts every path:
+/// the leading `/` is optional and a `~` not followed by `0` or `1` is kept as is.
+pub fn parse_path(path: &str) -> Vec<String> {
+    if path.is_empty() {
+        return Vec::new();
+    }
+    path.strip_prefix('/')
+        .unwrap_or(path)
+        .split('/')
+        .map(|token| unescape_token(token).unwrap_or_else(|| token.to_string()))
+        .collect()
+}
+
+fn unescape_token(token: &str) -> Option<String> {
+    let mut out = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c == '~' {
+            // `~1` has to be unescaped before `~0`, so `~01` becomes `~1` and not `/`
+            match chars.next() {
+                Some('0') => out.push('~'),
+                Some('1') => out.push('/'),
+                _ => return None,
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    Some(out)
+}
+
+/// Escapes a single reference token, so it can be appended to a JSON Pointer after a `/`
+pub fn escape_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Joins reference tokens into a JSON Pointer, used for error messages
+pub fn join_tokens(tokens: &[String]) -> String {
+    tokens.iter().map(|t| format!("/{}", escape_token(t))).collect()
+}
+
+/// Parses a reference token as an array index.
+/// Only plain decimal numbers without leading zeros are valid, `-` is not handled here.
+pub fn array_index(token: &str) -> Option<usize> {
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) || !token.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Follows the reference tokens through objects and arrays, returning the value they point to
+pub fn resolve<'a>(value: &'a MawuValue, tokens: &[String]) -> Option<&'a MawuValue> {
+    tokens.iter().try_fold(value, |current, token| match current {
+        MawuValue::Object(o) => o.get(token),
+        MawuValue::Array(a) => a.get(array_index(token)?),
+        _ => None,
+    })
+}
+
+/// Follows the reference tokens through objects and arrays, returning a mutable reference to the value they point to
+pub fn resolve_mut<'a>(value: &'a mut MawuValue, tokens: &[String]) -> Option<&'a mut MawuValue> {
+    tokens.iter().try_fold(value, |current, token| match current {
+        MawuValue::Object(o) => o.get_mut(token),
+        MawuValue::Array(a) => a.get_mut(array_index(token)?),
+        _ => None,
+    })
+}
+
+/// Follows the reference tokens through objects, creating them where needed, and returns a mutable
+/// reference to the value the tokens point to, inserting `MawuValue::None` if it is missing.
+/// Every value along the way that is not an object, arrays included, is replaced by an empty object.
+pub fn ensure<'a>(value: &'a mut MawuValue, tokens: &[String]) -> &'a mut MawuValue {
+    tokens.iter().fold(value, |current, token| {
+        if !current.is_object() {
+            *current = MawuValue::new_object();
+        }
+        match current {
+            MawuValue::Object(o) => o.entry(token.clone()).or_insert(MawuValue::None),
+            _ => unreachable!("replaced by an object above"),
+        }
+    })
+}
+
+/// Visits the value and everything inside of it depth first, yielding each value with the JSON Pointer to it.
+/// Object keys are visited in sorted order, so the same value always results in the same sequence.
+pub fn walk(value: &MawuValue) -> impl Iterator<Item = (String, &MawuValue)> {
+    let mut stack = vec![(String::new(), value)];
+    core::iter::from_fn(move || {
+        let (pointer, current) = stack.pop()?;
+        match current {
+            MawuValue::Object(o) => {
+                let mut entries = o.iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                // pushed in reverse, so they are popped in order
+                stack.extend(entries.into_iter().rev().map(|(key, child)| (format!("{}/{}", pointer, escape_token(key)), child)));
+            }
+            MawuValue::Array(a) => {
+                stack.extend(a.iter().enumerate().rev().map(|(index, child)| (format!("{}/{}", pointer, index), child)));
+            }
+            _ => {}
+        }
+        Some((pointer, current))
+    })
+}
+
+#[test]
+fn rfc6901_examples() {
+    let document = crate::mawu!({
+        "foo": [(MawuValue::String("bar".to_string())), (MawuValue::String("baz".to_string()))],
+        "": 0,
+        "a/b": 1,
+        "c%d": 2,
+        "e^f": 3,
+        "g|h": 4,
+        "i\\j": 5,
+        "k\"l": 6,
+        " ": 7,
+        "m~n": 8
+    });
+    let get = |pointer: &str| resolve(&document, &parse_pointer(pointer).unwrap());
+    assert_eq!(get(""), Some(&document));
+    assert_eq!(get("/foo").unwrap().len(), 2);
+    assert_eq!(get("/foo/0"), Some(&MawuValue::String("bar".to_string())));
+    assert_eq!(get("/"), Some(&MawuValue::Uint(0)));
+    assert_eq!(get("/a~1b"), Some(&MawuValue::Uint(1)));
+    assert_eq!(get("/c%d"), Some(&MawuValue::Uint(2)));
+    assert_eq!(get("/e^f"), Some(&MawuValue::Uint(3)));
+    assert_eq!(get("/g|h"), Some(&MawuValue::Uint(4)));
+    assert_eq!(get("/i\\j"), Some(&MawuValue::Uint(5)));
+    assert_eq!(get("/k\"l"), Some(&MawuValue::Uint(6)));
+    assert_eq!(get("/ "), Some(&MawuValue::Uint(7)));
+    assert_eq!(get("/m~0n"), Some(&MawuValue::Uint(8)));
+
+    assert_eq!(get("/foo/2"), None);
+    assert_eq!(get("/foo/01"), None);
+    assert_eq!(get("/foo/-"), None);
+    assert_eq!(get("/a~1b/c"), None);
+    assert!(parse_pointer("foo").is_err());
+    assert!(parse_pointer("/m~2n").is_err());
+    assert_eq!(parse_pointer("/~01").unwrap(), vec!["~1".to_string()]);
+    assert_eq!(join_tokens(&parse_pointer("/a~1b/m~0n/0").unwrap()), "/a~1b/m~0n/0");
+}
+
+#[test]
+fn ensure_creates_objects() {
+    let mut value = MawuValue::new_object();
+    *ensure(&mut value, &parse_path("/a/b/c")) = MawuValue::Uint(1);
+    assert_eq!(value, crate::mawu!({"a": {"b": {"c": 1}}}));
+
+    // existing values are kept, scalars along the path are replaced
+    *ensure(&mut value, &parse_path("a/d")) = MawuValue::from(true);
+    assert_eq!(ensure(&mut value, &parse_path("/a/b/c/x")), &MawuValue::None);
+    assert_eq!(value, crate::mawu!({"a": {"b": {"c": {"x": null}}, "d": true}}));
+
+    assert_eq!(parse_path("/m~0n/a~1b/~2"), vec!["m~n".to_string(), "a/b".to_string(), "~2".to_string()]);
+    assert!(parse_path("").is_empty());
+}
+
+#[test]
+fn walk_depth_first() {
+    let value = crate::mawu!({"b": [1, {"c": null}], "a/~": true, "d": {}});
+    let pointers = walk(&value).map(|(pointer, _)| pointer).collect::<Vec<_>>();
+    assert_eq!(pointers, vec!["", "/a~1~0", "/b", "/b/0", "/b/1", "/b/1/c", "/d"]);
+    for (pointer, found) in walk(&value) {
+        assert_eq!(resolve(&value, &parse_pointer(&pointer).unwrap()), Some(found));
+    }
+    assert_eq!(walk(&MawuValue::from(5)).collect::<Vec<_>>(), vec![(String::new(), &MawuValue::Int(5))]);
+}
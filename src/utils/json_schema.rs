@@ -0,0 +1,335 @@
+use crate::prelude::*;
+use crate::{
+    errors::json_error::{ValidationError, ValidationErrorKind},
+    mawu_value::{MawuObject, MawuValue},
+    utils::json_pointer::join_tokens,
+};
+
+/// Validates `value` against a minimal subset of JSON Schema, collecting every error.
+///
+/// Supported keywords are `type`, `required`, `properties`, `items`, `enum`, `minimum`, `maximum`,
+/// `minLength` and `maxLength`, all other keywords are ignored.
+pub fn validate(value: &MawuValue, schema: &MawuValue) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+    validate_value(value, schema, &mut Vec::new(), &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn validate_value(value: &MawuValue, schema: &MawuValue, path: &mut Vec<String>, errors: &mut Vec<ValidationError>) {
+    let mut error = |kind: ValidationErrorKind| {
+        errors.push(ValidationError { path: join_tokens(path), kind });
+    };
+    if !schema.is_object() {
+        error(ValidationErrorKind::InvalidSchema("schema".to_string()));
+        return;
+    }
+    if let Some(expected) = schema.get("type") {
+        match type_names(expected) {
+            Some(names) => {
+                if !names.iter().any(|name| is_of_type(value, name)) {
+                    error(ValidationErrorKind::WrongType(names.join(", ")));
+                }
+            }
+            None => error(ValidationErrorKind::InvalidSchema("type".to_string())),
+        }
+    }
+    if let Some(allowed) = schema.get("enum") {
+        match allowed.as_array() {
+            Some(allowed) => {
+                if !allowed.iter().any(|a| json_equal(a, value)) {
+                    error(ValidationErrorKind::NotInEnum);
+                }
+            }
+            None => error(ValidationErrorKind::InvalidSchema("enum".to_string())),
+        }
+    }
+    if let Some(n) = value.as_number().map(|n| n.as_f64()) {
+        if let Some(minimum) = schema.get("minimum") {
+            match minimum.as_number() {
+                Some(minimum) if n < minimum.as_f64() => {
+                    error(ValidationErrorKind::BelowMinimum(minimum.as_f64()))
+                }
+                Some(_) => {}
+                None => error(ValidationErrorKind::InvalidSchema("minimum".to_string())),
+            }
+        }
+        if let Some(maximum) = schema.get("maximum") {
+            match maximum.as_number() {
+                Some(maximum) if n > maximum.as_f64() => {
+                    error(ValidationErrorKind::AboveMaximum(maximum.as_f64()))
+                }
+                Some(_) => {}
+                None => error(ValidationErrorKind::InvalidSchema("maximum".to_string())),
+            }
+        }
+    }
+    if let Some(s) = value.as_str() {
+        // JSON Schema counts characters, not bytes
+        let len = s.chars().count();
+        if let Some(min_length) = schema.get("minLength") {
+            match length(min_length) {
+                Some(min_length) if len < min_length => error(ValidationErrorKind::TooShort(min_length)),
+                Some(_) => {}
+                None => error(ValidationErrorKind::InvalidSchema("minLength".to_string())),
+            }
+        }
+        if let Some(max_length) = schema.get("maxLength") {
+            match length(max_length) {
+                Some(max_length) if len > max_length => error(ValidationErrorKind::TooLong(max_length)),
+                Some(_) => {}
+                None => error(ValidationErrorKind::InvalidSchema("maxLength".to_string())),
+            }
+        }
+    }
+    if let Some(object) = value.as_object() {
+        if let Some(required) = schema.get("required") {
+            match required.as_array() {
+                Some(required) if required.iter().all(|r| r.is_string()) => {
+                    for name in required.iter().filter_map(|r| r.as_str()) {
+                        if !object.contains_key(name) {
+                            error(ValidationErrorKind::MissingRequired(name.to_string()));
+                        }
+                    }
+                }
+                _ => error(ValidationErrorKind::InvalidSchema("required".to_string())),
+            }
+        }
+        if let Some(properties) = schema.get("properties") {
+            match properties.as_object() {
+                Some(properties) => {
+                    // sorted, so the errors come out in the same order every time
+                    let mut names: Vec<&String> = properties.keys().filter(|k| object.contains_key(*k)).collect();
+                    names.sort();
+                    for name in names {
+                        path.push(name.clone());
+                        validate_value(&object[name], &properties[name], path, errors);
+                        path.pop();
+                    }
+                }
+                None => error(ValidationErrorKind::InvalidSchema("properties".to_string())),
+            }
+        }
+    }
+    if let (Some(array), Some(items)) = (value.as_array(), schema.get("items")) {
+        for (index, item) in array.iter().enumerate() {
+            path.push(index.to_string());
+            validate_value(item, items, path, errors);
+            path.pop();
+        }
+    }
+}
+
+/// Returns the type names of a `type` keyword, which is either a single name or an array of names
+fn type_names(expected: &MawuValue) -> Option<Vec<&str>> {
+    const KNOWN: [&str; 7] = ["null", "boolean", "object", "array", "number", "integer", "string"];
+    let names: Vec<&str> = match expected {
+        MawuValue::String(s) => vec![s.as_str()],
+        MawuValue::Array(a) => a.iter().map(|v| v.as_str()).collect::<Option<_>>()?,
+        _ => return None,
+    };
+    if names.is_empty() || !names.iter().all(|name| KNOWN.contains(name)) {
+        return None;
+    }
+    Some(names)
+}
+
+fn is_of_type(value: &MawuValue, name: &str) -> bool {
+    match name {
+        "null" => value.is_none(),
+        "boolean" => value.is_bool(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "number" => value.is_number(),
+        // JSON Schema treats numbers with a zero fractional part, like `1.0`, as integers
+        "integer" => match value {
+            MawuValue::Uint(_) | MawuValue::Int(_) => true,
+            MawuValue::Float(f) => f.is_finite() && f % 1.0 == 0.0,
+            _ => false,
+        },
+        "string" => value.is_string(),
+        _ => false,
+    }
+}
+
+/// Parses `minLength` and `maxLength`, which have to be non-negative integers
+fn length(value: &MawuValue) -> Option<usize> {
+    match value {
+        MawuValue::Uint(u) => usize::try_from(*u).ok(),
+        MawuValue::Int(i) => usize::try_from(*i).ok(),
+        _ => None,
+    }
+}
+
+/// Compares like JSON Schema does, where numbers are equal by value no matter how they are stored
+fn json_equal(a: &MawuValue, b: &MawuValue) -> bool {
+    match (a, b) {
+        (MawuValue::Array(a), MawuValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_equal(a, b))
+        }
+        (MawuValue::Object(a), MawuValue::Object(b)) => {
+            a.len() == b.len() && a.iter().all(|(k, v)| b.get(k).is_some_and(|w| json_equal(v, w)))
+        }
+        _ => match (a.as_number(), b.as_number()) {
+            (Some(a), Some(b)) => a.as_f64() == b.as_f64(),
+            _ => a == b,
+        },
+    }
+}
+
+/// Describes `value` with the keywords `type`, `required`, `properties` and `items`, so that it is valid against the result.
+///
+/// Every key of an object is required and described in `properties`. The elements of an array
+/// are described by a single `items` schema, merging their schemas if they differ.
+/// CSV values are described like an array of objects or an array of arrays.
+pub fn infer(value: &MawuValue) -> MawuValue {
+    match value {
+        MawuValue::None => type_schema("null"),
+        MawuValue::Bool(_) => type_schema("boolean"),
+        MawuValue::Uint(_) | MawuValue::Int(_) => type_schema("integer"),
+        MawuValue::Float(_) => type_schema("number"),
+        MawuValue::String(_) => type_schema("string"),
+        MawuValue::Object(o) => object_schema(o.iter()),
+        MawuValue::Array(a) => array_schema(a.iter().map(infer)),
+        MawuValue::CSVObject(rows) => array_schema(rows.iter().map(|row| object_schema(row.iter()))),
+        MawuValue::CSVArray(rows) => array_schema(rows.iter().map(|row| array_schema(row.iter().map(infer)))),
+    }
+}
+
+fn type_schema(name: &str) -> MawuValue {
+    MawuValue::Object(MawuObject::from([("type".to_string(), MawuValue::String(name.to_string()))]))
+}
+
+fn object_schema<'a>(object: impl Iterator<Item = (&'a String, &'a MawuValue)>) -> MawuValue {
+    let mut schema = type_schema("object");
+    let properties: MawuObject = object.map(|(key, value)| (key.clone(), infer(value))).collect();
+    let mut required: Vec<&String> = properties.keys().collect();
+    required.sort();
+    let required = required.into_iter().map(|key| MawuValue::String(key.clone())).collect();
+    schema.object_insert("required", MawuValue::Array(required));
+    schema.object_insert("properties", MawuValue::Object(properties));
+    schema
+}
+
+fn array_schema(items: impl Iterator<Item = MawuValue>) -> MawuValue {
+    let mut schema = type_schema("array");
+    if let Some(items) = items.reduce(merge) {
+        schema.object_insert("items", items);
+    }
+    schema
+}
+
+/// Merges two inferred schemas into one both values are valid against.
+/// The types are united, keys are only required if they are required by both and
+/// the schemas of properties and items found in both are merged.
+fn merge(a: MawuValue, b: MawuValue) -> MawuValue {
+    const ORDER: [&str; 7] = ["null", "boolean", "object", "array", "number", "integer", "string"];
+    let (MawuValue::Object(mut a), MawuValue::Object(mut b)) = (a, b) else {
+        unreachable!("inferred schemas are objects")
+    };
+    let types_of = |schema: &MawuObject| schema.get("type").and_then(type_names).unwrap_or_default().iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    let types = [types_of(&a), types_of(&b)].concat();
+    let mut names: Vec<&str> = ORDER.into_iter().filter(|name| types.iter().any(|t| t == name)).collect();
+    // every integer is a number too
+    if names.contains(&"number") {
+        names.retain(|name| *name != "integer");
+    }
+    let mut out = MawuObject::new();
+    out.insert(
+        "type".to_string(),
+        match names.as_slice() {
+            [name] => MawuValue::String(name.to_string()),
+            _ => MawuValue::Array(names.iter().map(|name| MawuValue::String(name.to_string())).collect()),
+        },
+    );
+    match (a.remove("required"), b.remove("required")) {
+        (Some(MawuValue::Array(mut a)), Some(b)) => {
+            a.retain(|key| b.contains(key));
+            out.insert("required".to_string(), MawuValue::Array(a));
+        }
+        (Some(required), None) | (None, Some(required)) => {
+            out.insert("required".to_string(), required);
+        }
+        _ => {}
+    }
+    match (a.remove("properties"), b.remove("properties")) {
+        (Some(MawuValue::Object(mut a)), Some(MawuValue::Object(b))) => {
+            for (key, schema) in b {
+                let merged = match a.remove(&key) {
+                    Some(existing) => merge(existing, schema),
+                    None => schema,
+                };
+                a.insert(key, merged);
+            }
+            out.insert("properties".to_string(), MawuValue::Object(a));
+        }
+        (Some(properties), None) | (None, Some(properties)) => {
+            out.insert("properties".to_string(), properties);
+        }
+        _ => {}
+    }
+    match (a.remove("items"), b.remove("items")) {
+        (Some(a), Some(b)) => {
+            out.insert("items".to_string(), merge(a, b));
+        }
+        (Some(items), None) | (None, Some(items)) => {
+            out.insert("items".to_string(), items);
+        }
+        _ => {}
+    }
+    MawuValue::Object(out)
+}
+
+#[test]
+fn collects_all_errors() {
+    let schema = crate::mawu!({
+        "type": "object",
+        "required": ["name", "port"],
+        "properties": {
+            "name": {"type": "string", "minLength": 1, "maxLength": 3},
+            "port": {"type": "integer", "minimum": 1, "maximum": 65535},
+            "mode": {"enum": ["fast", "safe", 1]},
+            "tags": {"type": "array", "items": {"type": ["string", "null"]}}
+        }
+    });
+    assert_eq!(validate(&crate::mawu!({"name": "abc", "port": 80, "mode": 1.0, "tags": ["a", null]}), &schema), Ok(()));
+
+    let errors = validate(&crate::mawu!({"name": "mawu", "mode": "slow", "tags": ["a", 2]}), &schema).unwrap_err();
+    assert_eq!(errors, vec![
+        ValidationError { path: "".to_string(), kind: ValidationErrorKind::MissingRequired("port".to_string()) },
+        ValidationError { path: "/mode".to_string(), kind: ValidationErrorKind::NotInEnum },
+        ValidationError { path: "/name".to_string(), kind: ValidationErrorKind::TooLong(3) },
+        ValidationError { path: "/tags/1".to_string(), kind: ValidationErrorKind::WrongType("string, null".to_string()) },
+    ]);
+
+    assert_eq!(validate(&MawuValue::None, &crate::mawu!({"type": "float"})).unwrap_err()[0].kind, ValidationErrorKind::InvalidSchema("type".to_string()));
+    assert!(validate(&MawuValue::Float(2.0), &crate::mawu!({"type": "integer"})).is_ok());
+}
+
+#[test]
+fn infer_nested_schema() {
+    let value = crate::mawu!({
+        "name": "mawu",
+        "version": 1,
+        "tags": ["json", "csv"],
+        "mixed": [1, 2.5, "three", null],
+        "users": [{"name": "ada", "admin": true}, {"name": "grace", "email": "g@example.com"}]
+    });
+    let schema = infer(&value);
+    assert_eq!(schema.get("type").unwrap(), &MawuValue::from("object"));
+    assert_eq!(schema.get("required").unwrap(), &crate::mawu!(["mixed", "name", "tags", "users", "version"]));
+    let properties = schema.get("properties").unwrap();
+    assert_eq!(properties.get("version").unwrap(), &crate::mawu!({"type": "integer"}));
+    assert_eq!(properties.get("tags").unwrap(), &crate::mawu!({"type": "array", "items": {"type": "string"}}));
+    assert_eq!(properties.get("mixed").unwrap().get("items").unwrap(), &crate::mawu!({"type": ["null", "number", "string"]}));
+    let users = properties.get("users").unwrap().get("items").unwrap();
+    assert_eq!(users.get("required").unwrap(), &crate::mawu!(["name"]));
+    assert_eq!(users.get("properties").unwrap().len(), 3);
+    assert_eq!(validate(&value, &schema), Ok(()));
+
+    assert_eq!(infer(&crate::mawu!([])), crate::mawu!({"type": "array"}));
+    assert!(validate(&crate::mawu!({"name": "x"}), &schema).is_err());
+}
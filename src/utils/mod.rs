@@ -1,8 +1,29 @@
-use std::char::{self, decode_utf16};
+use crate::prelude::*;
+use core::char::{self, decode_utf16};
 
-use crate::errors::{MawuError, MawuInternalError};
+use crate::errors::{
+    json_error::{JsonError, JsonParseError},
+    MawuError, MawuInternalError,
+};
 
+#[cfg(feature = "std")]
 pub mod file_handling;
+pub mod json_patch;
+pub mod json_pointer;
+pub mod json_schema;
+
+/// Enough spaces for most indentation levels, longer runs are pushed in multiple chunks
+const SPACES: &str = "                                                                ";
+
+/// Pushes `n` spaces onto the end of `out`, without allocating a temporary `String` like `make_whitespace`
+pub fn push_whitespace(out: &mut String, n: usize) {
+    let mut remaining = n;
+    while remaining > 0 {
+        let chunk = remaining.min(SPACES.len());
+        out.push_str(&SPACES[..chunk]);
+        remaining -= chunk;
+    }
+}
 
 /// Takes in a `usize` and returns a `String` that is `n` spaces long filled with whitespace
 pub fn make_whitespace<N: Into<usize> + Copy>(n: N) -> String {
@@ -33,7 +54,34 @@ pub fn is_newline(s: &char) -> bool {
 /// ## Returns
 /// `Ok((String, bool))` if the string is successfully unescaped, `Err(MawuError)` otherwise
 /// the boolean is `true` if the next_codepoint was used, `false` otherwise
+///
+/// ## Errors
+/// `JsonParseError::UnpairedSurrogate` if `s` is a low surrogate, or a high surrogate not followed
+/// by a low surrogate in `next_codepoint`
 pub fn unescape_unicode(s: &str, next_codepoint: &str) -> Result<(String, bool), MawuError> {
+    // surrogates are only valid as a high surrogate directly followed by a low surrogate
+    if let Ok(codepoint) = u16::from_str_radix(s, 16) {
+        if (0xDC00..=0xDFFF).contains(&codepoint) {
+            return Err(MawuError::JsonError(JsonError::ParseError(
+                JsonParseError::UnpairedSurrogate(s.to_string()),
+            )));
+        } else if (0xD800..=0xDBFF).contains(&codepoint) {
+            return match u16::from_str_radix(next_codepoint, 16) {
+                Ok(low) if (0xDC00..=0xDFFF).contains(&low) => {
+                    let out = decode_utf16([codepoint, low]).next();
+                    match out {
+                        Some(Ok(c)) => Ok((c.to_string(), true)),
+                        _ => Err(MawuError::InternalError(
+                            MawuInternalError::UnableToUnescapeUnicode(s.to_string()),
+                        )),
+                    }
+                }
+                _ => Err(MawuError::JsonError(JsonError::ParseError(
+                    JsonParseError::UnpairedSurrogate(s.to_string()),
+                ))),
+            };
+        }
+    }
     let out = my_unescape_unicode_handler(s.to_string());
     if out.is_err() {
         if next_codepoint.is_empty() {
@@ -131,3 +179,12 @@ pub fn is_json_string_terminator_token(c: Option<&char>) -> bool {
     let c = c.unwrap();
     *c == ':' || *c == ',' || *c == '}' || *c == ']'
 }
+
+#[test]
+fn push_whitespace_matches_make_whitespace() {
+    for n in [0usize, 1, 4, 63, 64, 65, 200] {
+        let mut out = String::from("x");
+        push_whitespace(&mut out, n);
+        assert_eq!(out, format!("x{}", make_whitespace(n)));
+    }
+}
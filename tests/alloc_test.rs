@@ -0,0 +1,157 @@
+// Counts the allocations made while parsing, run with
+// `cargo test --release --test alloc_test -- --ignored --nocapture`
+mod alloc_tests {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+    use std::time::Instant;
+
+    struct CountingAllocator;
+
+    thread_local! {
+        // per thread, so tests running in parallel do not count each others allocations
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+        // bytes allocated and not yet freed, and the most there have been since the last `measure`
+        static LIVE: Cell<isize> = const { Cell::new(0) };
+        static PEAK: Cell<isize> = const { Cell::new(0) };
+    }
+
+    fn count() {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+    }
+
+    fn track(bytes: isize) {
+        let _ = LIVE.try_with(|live| {
+            live.set(live.get() + bytes);
+            let _ = PEAK.try_with(|peak| peak.set(peak.get().max(live.get())));
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            count();
+            track(layout.size() as isize);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            track(-(layout.size() as isize));
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            count();
+            track(new_size as isize - layout.size() as isize);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    /// Runs `f` and prints the number of allocations and reallocations it made,
+    /// the most memory it used at once and how much of it is still held by the returned value
+    fn measure<T>(name: &str, f: impl FnOnce() -> T) -> T {
+        let before = ALLOCATIONS.with(|a| a.get());
+        let live_before = LIVE.with(|live| live.get());
+        PEAK.with(|peak| peak.set(live_before));
+        let start = Instant::now();
+        let out = f();
+        let elapsed = start.elapsed();
+        let allocations = ALLOCATIONS.with(|a| a.get()) - before;
+        let peak = PEAK.with(|peak| peak.get()) - live_before;
+        let retained = LIVE.with(|live| live.get()) - live_before;
+        println!("{}: {} allocations, {} bytes peak, {} bytes retained in {:?}", name, allocations, peak, retained, elapsed);
+        out
+    }
+
+    #[test]
+    #[ignore]
+    fn json_allocations() {
+        let path = "data/json/json-test-data/microsoftEdge-json-test-data/json-dummy-data/5MB.json";
+        let value = measure("json 5MB", || mawu::read::json(path).unwrap());
+        assert!(value.is_array());
+        let numbers = format!("[{}]", (0..1_000_000).map(|n| n.to_string()).collect::<Vec<_>>().join(","));
+        let value = measure("json 1M numbers", || mawu::json::from_str(&numbers).unwrap());
+        assert_eq!(value.as_array().unwrap().len(), 1_000_000);
+    }
+
+    #[test]
+    #[ignore]
+    fn json_repeated_keys_memory() {
+        let objects = (0..100_000u64)
+            .map(|n| format!(r#"{{"id":{},"name":"row {}","score":{}.5,"active":{},"note":null}}"#, n, n, n, n % 2 == 0))
+            .collect::<Vec<_>>();
+        let input = format!("[{}]", objects.join(","));
+        let value = measure("json 100k objects, 5 repeated keys", || mawu::json::from_str(&input).unwrap());
+        let keys = value.iter_array().unwrap().flat_map(|object| object.keys().unwrap()).map(|key| key.len()).sum::<usize>();
+        println!("{} bytes of key text", keys);
+        // keys borrowed from the input are not allocated at all
+        let borrowed = measure("json 100k objects, 5 repeated keys, borrowed", || mawu::json::from_str_borrowed(&input).unwrap());
+        assert_eq!(borrowed.to_owned(), value);
+        // every key is allocated once and shared by all objects
+        let interned = measure("json 100k objects, 5 repeated keys, interned", || mawu::json::from_str_interned(&input).unwrap());
+        assert_eq!(interned.to_owned(), value);
+    }
+
+    #[test]
+    #[ignore]
+    fn json_serialize_allocations() {
+        let rows = (0..100_000u64)
+            .map(|n| mawu::mawu!({"id": n, "name": (format!("row \"{}\"", n)), "score": (n as f64 / 4.0), "tags": ["a", "b/c"], "active": (n % 2 == 0), "note": null}))
+            .collect::<Vec<_>>();
+        let value = mawu::mawu_value::MawuValue::from(rows);
+        let compact = measure("json serialize 100k rows", || mawu::json::to_string(&value, 0).unwrap());
+        println!("{} bytes", compact.len());
+        let pretty = measure("json serialize 100k rows pretty", || mawu::json::to_string(&value, 4).unwrap());
+        println!("{} bytes", pretty.len());
+    }
+
+    #[test]
+    #[ignore]
+    fn json_serialize_nested_allocations() {
+        let mut value = mawu::mawu!(null);
+        for n in 0..500u64 {
+            value = mawu::mawu!({"level": n, "items": [1, 2.5, "three"], "child": value});
+        }
+        let pretty = measure("json serialize 500 levels pretty", || mawu::json::to_string(&value, 4).unwrap());
+        println!("{} bytes", pretty.len());
+    }
+
+    #[test]
+    #[ignore]
+    fn csv_wide_table_memory() {
+        let columns = 200;
+        let mut csv = (0..columns).map(|n| format!("column_{}", n)).collect::<Vec<_>>().join(",");
+        for row in 0..5_000 {
+            csv.push('\n');
+            csv.push_str(&(0..columns).map(|n| (row * n).to_string()).collect::<Vec<_>>().join(","));
+        }
+        let options = mawu::csv::CsvParseOptions::default();
+        let object = measure("csv 200 columns CSVObject", || mawu::csv::from_str_with(&csv, &options).unwrap());
+        let table = measure("csv 200 columns CsvTable", || mawu::csv::from_str_table_with(&csv, &options).unwrap());
+        assert_eq!(mawu::mawu_value::MawuValue::from(table), object);
+    }
+
+    #[test]
+    #[ignore]
+    fn csv_numeric_inference() {
+        let mut csv = String::from("a,b,c,d,e,f,g,h");
+        for row in 0..200_000i64 {
+            csv.push_str(&format!("\n{},{},{},{}.25,{}e3,-{}.5,{},name {}", row, -row, row * 1_000_003, row, row, row, row % 2 == 0, row));
+        }
+        let options = mawu::csv::CsvParseOptions::default().has_headers(false);
+        let value = measure("csv 200k numeric rows", || mawu::csv::from_str_with(&csv, &options).unwrap());
+        assert_eq!(value.as_csv_array().unwrap().len(), 200_001);
+        let cells = csv.lines().flat_map(|line| line.split(',')).collect::<Vec<_>>();
+        let values = measure("MawuValue::from 1.6M cells", || cells.iter().map(|cell| mawu::mawu_value::MawuValue::from(*cell)).collect::<Vec<_>>());
+        assert_eq!(values.len(), cells.len());
+    }
+
+    #[test]
+    #[ignore]
+    fn csv_allocations() {
+        let path = "data/csv/csv-test-data/headed/my-own-random-data/test_data_310k.csv";
+        let value = measure("csv 310k rows", || mawu::read::csv_headed(path).unwrap());
+        assert_eq!(value.as_csv_object().unwrap().len(), 310000);
+    }
+}
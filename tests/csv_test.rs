@@ -215,6 +215,462 @@ mod csv_tests {
             std::fs::remove_file("test_file_delete_me.csv").unwrap();
         }
 
+        #[test]
+        fn empty_files() {
+            for path in ["data/csv/csv-test-data/empty/empty.csv", "data/csv/csv-test-data/empty/whitespace-only.csv"] {
+                let mawu = mawu::read::csv_headed(path).unwrap();
+                assert_eq!(mawu, MawuValue::CSVObject(vec![]));
+                assert!(mawu.is_empty());
+            }
+        }
+
+        #[test]
+        fn tab_separated() {
+            let tsv = mawu::csv::read_tsv_headed("data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv").unwrap();
+            let rows = tsv.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 3);
+            assert_eq!(rows[0].get("name").unwrap(), &MawuValue::from("Ada"));
+            assert_eq!(rows[0].get("age").unwrap(), &MawuValue::Uint(36));
+            assert_eq!(rows[0].get("note").unwrap(), &MawuValue::String("likes\ttabs".to_string()));
+            assert_eq!(rows[1].get("note").unwrap(), &MawuValue::String("said \"hi\"".to_string()));
+            assert_eq!(rows[2].get("age").unwrap(), &MawuValue::None);
+            assert_eq!(rows[2].get("note").unwrap(), &MawuValue::from("plain"));
+
+            let all_types = "data/csv/csv-test-data/headed/my-own-random-data/all-types.csv";
+            assert_eq!(mawu::csv::read_headed_with_delimiter(all_types, ',').unwrap(), mawu::read::csv_headed(all_types).unwrap());
+        }
+
+        #[test]
+        fn detect_delimiter() {
+            let dir = "data/csv/csv-test-data/headed/my-own-random-data/";
+            let comma = mawu::csv::read_csv_auto(format!("{}auto-comma.csv", dir)).unwrap();
+            assert_eq!(comma.as_csv_object().unwrap()[0].get("city").unwrap(), &MawuValue::from("London, UK"));
+            let semicolon = mawu::csv::read_csv_auto(format!("{}auto-semicolon.csv", dir)).unwrap();
+            assert_eq!(semicolon.as_csv_object().unwrap()[1].get("price").unwrap(), &MawuValue::from("2,25"));
+            let tab = mawu::csv::read_csv_auto(format!("{}tab-headed.tsv", dir)).unwrap();
+            assert_eq!(tab.as_csv_object().unwrap()[0].get("note").unwrap(), &MawuValue::String("likes\ttabs".to_string()));
+            let pipe = mawu::csv::read_csv_auto(format!("{}auto-pipe.csv", dir)).unwrap();
+            assert_eq!(pipe.as_csv_object().unwrap()[1].get("name").unwrap(), &MawuValue::from("Bob"));
+
+            let ambiguous = mawu::csv::read_csv_auto(format!("{}auto-ambiguous.csv", dir));
+            assert!(matches!(
+                ambiguous,
+                Err(mawu::errors::MawuError::CsvError(mawu::errors::csv_error::CsvError::ParseError(
+                    mawu::errors::csv_error::CsvParseError::UndetectableDelimiter
+                )))
+            ));
+        }
+
+        #[test]
+        fn detect_header() {
+            let headed = "data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv";
+            assert!(mawu::csv::has_header(&std::fs::read_to_string(headed).unwrap(), ','));
+            let value = mawu::csv::read_csv_auto_headed(headed).unwrap();
+            assert_eq!(value, mawu::read::csv_headed(headed).unwrap());
+
+            let headless = "data/csv/csv-test-data/headless/my-own-random-data/numeric-only.csv";
+            assert!(!mawu::csv::has_header(&std::fs::read_to_string(headless).unwrap(), ';'));
+            let value = mawu::csv::read_csv_auto_headed(headless).unwrap();
+            assert_eq!(value.as_csv_array().unwrap().len(), 3);
+            assert_eq!(value.as_csv_array().unwrap()[0][1], MawuValue::Float(2.5));
+        }
+
+        #[test]
+        fn parse_options() {
+            use mawu::csv::{read_with, CsvParseOptions, RaggedRows};
+            let path = "data/csv/csv-test-data/headed/my-own-random-data/options.csv";
+            let options = CsvParseOptions::new().delimiter(';').quote('\'').comment(Some('#'));
+            assert!(read_with(path, &options).is_err());
+
+            let fitted = read_with(path, &options.clone().ragged_rows(RaggedRows::Fit)).unwrap();
+            let rows = fitted.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 3);
+            assert_eq!(rows[0].get("nick;name").unwrap(), &MawuValue::String("it's ada".to_string()));
+            assert_eq!(rows[1].get("nick;name").unwrap(), &MawuValue::None);
+            assert_eq!(rows[2].get("age").unwrap(), &MawuValue::Uint(7));
+            assert_eq!(rows[2].len(), 3);
+
+            let strings = read_with(path, &options.clone().ragged_rows(RaggedRows::Fit).infer_types(false)).unwrap();
+            assert_eq!(strings.as_csv_object().unwrap()[2].get("age").unwrap(), &MawuValue::String("007".to_string()));
+            assert_eq!(strings.as_csv_object().unwrap()[1].get("nick;name").unwrap(), &MawuValue::None);
+
+            let headless = read_with(path, &options.has_headers(false)).unwrap();
+            let rows = headless.as_csv_array().unwrap();
+            assert_eq!(rows.len(), 4);
+            assert_eq!(rows[0][2], MawuValue::String("nick;name".to_string()));
+            assert_eq!(rows[3].len(), 4);
+        }
+
+        #[test]
+        fn leading_empty_fields() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let csv = from_str_with("a,b\n,1\n2,3\n,4\n", &CsvParseOptions::new()).unwrap();
+            let rows = csv.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 3);
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::None);
+            assert_eq!(rows[0].get("b").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(rows[2].get("a").unwrap(), &MawuValue::None);
+            assert_eq!(rows[2].get("b").unwrap(), &MawuValue::Uint(4));
+        }
+
+        #[test]
+        fn null_tokens() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let input = "a,b,c\nNA,NULL,1\nna,-,\n";
+            let plain = from_str_with(input, &CsvParseOptions::new()).unwrap();
+            let rows = plain.as_csv_object().unwrap();
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::String("NA".to_string()));
+            assert_eq!(rows[0].get("b").unwrap(), &MawuValue::String("NULL".to_string()));
+            assert_eq!(rows[1].get("c").unwrap(), &MawuValue::None);
+
+            let options = CsvParseOptions::new().null_tokens(vec!["NA".to_string(), "NULL".to_string(), "-".to_string()]);
+            let nulls = from_str_with(input, &options).unwrap();
+            let rows = nulls.as_csv_object().unwrap();
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::None);
+            assert_eq!(rows[0].get("b").unwrap(), &MawuValue::None);
+            assert_eq!(rows[0].get("c").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(rows[1].get("a").unwrap(), &MawuValue::String("na".to_string()));
+            assert_eq!(rows[1].get("b").unwrap(), &MawuValue::None);
+
+            let ignore_case = from_str_with(input, &options.null_tokens_ignore_case(true).infer_types(false)).unwrap();
+            let rows = ignore_case.as_csv_object().unwrap();
+            assert_eq!(rows[1].get("a").unwrap(), &MawuValue::None);
+            assert_eq!(rows[0].get("c").unwrap(), &MawuValue::String("1".to_string()));
+        }
+
+        #[test]
+        fn bool_tokens() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let input = "a,b,c,d\nTRUE,yes,1,N\nfalse,No,0,True\n";
+            let plain = from_str_with(input, &CsvParseOptions::new()).unwrap();
+            let rows = plain.as_csv_object().unwrap();
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::String("TRUE".to_string()));
+            assert_eq!(rows[0].get("c").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(rows[1].get("a").unwrap(), &MawuValue::Bool(false));
+
+            let options = CsvParseOptions::new()
+                .true_tokens(vec!["yes".to_string(), "1".to_string(), "Y".to_string()])
+                .false_tokens(vec!["no".to_string(), "0".to_string(), "N".to_string()]);
+            let tokens = from_str_with(input, &options).unwrap();
+            let rows = tokens.as_csv_object().unwrap();
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::String("TRUE".to_string()));
+            assert_eq!(rows[0].get("b").unwrap(), &MawuValue::Bool(true));
+            assert_eq!(rows[0].get("c").unwrap(), &MawuValue::Bool(true));
+            assert_eq!(rows[0].get("d").unwrap(), &MawuValue::Bool(false));
+            assert_eq!(rows[1].get("b").unwrap(), &MawuValue::String("No".to_string()));
+            assert_eq!(rows[1].get("c").unwrap(), &MawuValue::Bool(false));
+
+            let ignore_case = from_str_with(input, &options.clone().bool_tokens_ignore_case(true)).unwrap();
+            let rows = ignore_case.as_csv_object().unwrap();
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::Bool(true));
+            assert_eq!(rows[1].get("b").unwrap(), &MawuValue::Bool(false));
+            assert_eq!(rows[1].get("d").unwrap(), &MawuValue::Bool(true));
+
+            // null tokens win, and nothing is inferred without `infer_types`
+            let nulls = from_str_with(input, &options.clone().null_tokens(vec!["yes".to_string()])).unwrap();
+            assert_eq!(nulls.as_csv_object().unwrap()[0].get("b").unwrap(), &MawuValue::None);
+            let strings = from_str_with(input, &options.infer_types(false)).unwrap();
+            assert_eq!(strings.as_csv_object().unwrap()[0].get("c").unwrap(), &MawuValue::String("1".to_string()));
+        }
+
+        #[test]
+        fn empty_as_none() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let json_null = mawu::json::from_str("null").unwrap();
+            let input = "a,b,c\n,NULL,\"\"\n1\n";
+            let options = CsvParseOptions::new().ragged_rows(mawu::csv::RaggedRows::Fit);
+            let default = from_str_with(input, &options).unwrap();
+            assert_eq!(default.as_csv_object().unwrap()[0].get("a").unwrap(), &json_null);
+
+            let options = options.empty_as_none(false).null_tokens(vec!["NULL".to_string()]);
+            let kept = from_str_with(input, &options).unwrap();
+            let rows = kept.as_csv_object().unwrap();
+            assert_eq!(rows[0].get("a").unwrap(), &MawuValue::String("".to_string()));
+            assert_ne!(rows[0].get("a").unwrap(), &json_null);
+            assert_eq!(rows[0].get("b").unwrap(), &json_null);
+            assert_eq!(rows[0].get("c").unwrap(), &MawuValue::String("".to_string()));
+            // absent values of a short row are still `None`
+            assert_eq!(rows[1].get("b").unwrap(), &MawuValue::None);
+            let strings = from_str_with(input, &options.infer_types(false)).unwrap();
+            assert_eq!(strings.as_csv_object().unwrap()[0].get("a").unwrap(), &MawuValue::String("".to_string()));
+        }
+
+        #[test]
+        fn from_str_and_reader() {
+            use mawu::csv::{from_reader_headed, from_reader_headless, from_str_headed, from_str_headless};
+            let input = "id,name,\"note, quoted\"\r\n1,Ada,\"multi\nline\"\r\n2,Grace,\r\n";
+            let value = from_str_headed(input).unwrap();
+            let rows = value.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].get("id").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(rows[0].get("note, quoted").unwrap(), &MawuValue::from("multi\nline"));
+            assert_eq!(rows[1].get("note, quoted").unwrap(), &MawuValue::None);
+            assert_eq!(from_reader_headed(input.as_bytes()).unwrap(), value);
+
+            let headless = from_str_headless(input).unwrap();
+            assert_eq!(headless.as_csv_array().unwrap().len(), 3);
+            assert_eq!(from_reader_headless(input.as_bytes()).unwrap(), headless);
+            // the reader is decoded like a file, a BOM is stripped and invalid UTF-8 is an error
+            assert_eq!(from_reader_headed("\u{FEFF}id\n1\n".as_bytes()).unwrap(), from_str_headed("id\n1\n").unwrap());
+            assert!(matches!(from_reader_headed(&[b'a', b'\n', 0xff][..]), Err(mawu::errors::MawuError::IoError(_))));
+        }
+
+        #[test]
+        fn preserve_leading_zeros() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let input = "id,zip,ratio,zero,signed\n007,00123,0.5,0,-01\n";
+            let inferred = from_str_with(input, &CsvParseOptions::new()).unwrap();
+            assert_eq!(inferred.as_csv_object().unwrap()[0].get("id").unwrap(), &MawuValue::Uint(7));
+
+            let preserved = from_str_with(input, &CsvParseOptions::new().preserve_leading_zeros(true)).unwrap();
+            let row = &preserved.as_csv_object().unwrap()[0];
+            assert_eq!(row.get("id").unwrap(), &MawuValue::String("007".to_string()));
+            assert_eq!(row.get("zip").unwrap(), &MawuValue::String("00123".to_string()));
+            assert_eq!(row.get("signed").unwrap(), &MawuValue::String("-01".to_string()));
+            assert_eq!(row.get("ratio").unwrap(), &MawuValue::Float(0.5));
+            assert_eq!(row.get("zero").unwrap(), &MawuValue::Uint(0));
+        }
+
+        #[test]
+        fn trim_fields() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let input = "a,b,c\n 42 , \" quoted \" ,\t\n";
+            let exact = from_str_with(input, &CsvParseOptions::new()).unwrap();
+            let row = &exact.as_csv_object().unwrap()[0];
+            assert_eq!(row.get("a").unwrap(), &MawuValue::String(" 42 ".to_string()));
+            assert_eq!(row.get("b").unwrap(), &MawuValue::String(" quoted ".to_string()));
+            assert_eq!(row.get("c").unwrap(), &MawuValue::String("\t".to_string()));
+
+            let trimmed = from_str_with(input, &CsvParseOptions::new().trim_fields(true)).unwrap();
+            let row = &trimmed.as_csv_object().unwrap()[0];
+            assert_eq!(row.get("a").unwrap(), &MawuValue::Uint(42));
+            assert_eq!(row.get("b").unwrap(), &MawuValue::String(" quoted ".to_string()));
+            assert_eq!(row.get("c").unwrap(), &MawuValue::None);
+        }
+
+        #[test]
+        fn tsv_empty_field_before_quoted() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            let input = "a\tb\tc\n1\t\t\"x\"\n";
+            for trim in [false, true] {
+                let tsv = from_str_with(input, &CsvParseOptions::new().delimiter('\t').trim_fields(trim)).unwrap();
+                let row = &tsv.as_csv_object().unwrap()[0];
+                assert_eq!(row.get("a").unwrap(), &MawuValue::Uint(1));
+                assert_eq!(row.get("b").unwrap(), &MawuValue::None);
+                assert_eq!(row.get("c").unwrap(), &MawuValue::from("x"));
+            }
+        }
+
+        #[test]
+        fn duplicate_headers() {
+            use mawu::csv::{from_str_with, CsvParseOptions, DuplicateHeaders};
+            use mawu::errors::{csv_error::{CsvError, CsvParseError}, MawuError};
+            let input = "a,b,a\n1,2,3\n";
+            let suffixed = from_str_with(input, &CsvParseOptions::new()).unwrap();
+            let row = &suffixed.as_csv_object().unwrap()[0];
+            assert_eq!(row.len(), 3);
+            assert_eq!(row.get("a").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(row.get("b").unwrap(), &MawuValue::Uint(2));
+            assert_eq!(row.get("a_2").unwrap(), &MawuValue::Uint(3));
+
+            let taken = from_str_with("a,a_2,a\n1,2,3\n", &CsvParseOptions::new()).unwrap();
+            assert_eq!(taken.as_csv_object().unwrap()[0].get("a_3").unwrap(), &MawuValue::Uint(3));
+
+            let error = from_str_with(input, &CsvParseOptions::new().duplicate_headers(DuplicateHeaders::Error));
+            assert!(matches!(
+                error,
+                Err(MawuError::CsvError(CsvError::ParseError(CsvParseError::DuplicateHeader(ref name)))) if name == "a"
+            ));
+        }
+
+        #[test]
+        fn unterminated_quote() {
+            use mawu::csv::{from_str_with, CsvParseOptions};
+            use mawu::errors::{csv_error::{CsvError, CsvParseError}, MawuError};
+            // used to loop forever waiting for the closing quote
+            for input in ["a,b\n1,\"x", "a,b\n1,\"x\"\"", "\"a,b\n1,2\n"] {
+                for has_headers in [true, false] {
+                    let parsed = from_str_with(input, &CsvParseOptions::new().has_headers(has_headers));
+                    assert!(
+                        matches!(parsed, Err(MawuError::CsvError(CsvError::ParseError(CsvParseError::UnterminatedQuote)))),
+                        "{:?}",
+                        input
+                    );
+                }
+            }
+        }
+
+        #[test]
+        #[cfg(feature = "serde")]
+        fn typed_rows() {
+            use mawu::errors::{csv_error::CsvError, MawuError};
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize, PartialEq)]
+            struct Person {
+                name: String,
+                age: u8,
+                city: Option<String>,
+            }
+
+            let people: Vec<Person> = mawu::csv::from_path_typed("data/csv/csv-test-data/headed/my-own-random-data/auto-comma.csv").unwrap();
+            assert_eq!(people, vec![
+                Person { name: "Ada".to_string(), age: 36, city: Some("London, UK".to_string()) },
+                Person { name: "Bob".to_string(), age: 42, city: Some("Paris".to_string()) },
+            ]);
+
+            #[derive(Debug, Deserialize)]
+            #[allow(dead_code)]
+            struct Row {
+                #[serde(rename = "Id")]
+                id: u32,
+            }
+            // the first id is padded with whitespace, and thus a string
+            let error = mawu::csv::from_path_typed::<Row, _>("data/csv/csv-test-data/headed/my-own-random-data/all-types.csv");
+            assert!(matches!(error, Err(MawuError::CsvError(CsvError::DeserializeError(ref s))) if s.starts_with("row 1:")));
+        }
+
+        #[test]
+        #[cfg(feature = "serde")]
+        fn typed_rows_keep_leading_zeros() {
+            use serde::Deserialize;
+
+            #[derive(Debug, Deserialize, PartialEq)]
+            struct Place {
+                city: String,
+                zip: String,
+                population: u32,
+            }
+
+            let places: Vec<Place> = mawu::csv::from_path_typed("data/csv/csv-test-data/headed/my-own-random-data/zip-codes.csv").unwrap();
+            assert_eq!(places, vec![
+                Place { city: "Cambridge".to_string(), zip: "02139".to_string(), population: 118403 },
+                Place { city: "Boston".to_string(), zip: "02108".to_string(), population: 675647 },
+            ]);
+        }
+
+        #[test]
+        fn table_matches_csv_object() {
+            use mawu::{csv::{read_table, CsvTable}, read::csv_headed};
+            for entry in std::fs::read_dir("data/csv/csv-test-data/headed/my-own-random-data").unwrap() {
+                let path = entry.unwrap().path();
+                if path.metadata().unwrap().len() > 1_000_000 {
+                    continue;
+                }
+                match (csv_headed(&path), read_table(&path)) {
+                    (Ok(object), Ok(table)) => assert_eq!(MawuValue::from(table), object, "{:?}", path),
+                    (Err(_), Err(_)) => {}
+                    (object, table) => panic!("{:?}: {:?} != {:?}", path, object.is_ok(), table.is_ok()),
+                }
+            }
+            let table = read_table("data/csv/csv-test-data/headed/my-own-random-data/all-types.csv").unwrap();
+            let first = table.headers[0].clone();
+            assert_eq!(table.get(0, &first), table.rows[0].first());
+            assert_eq!(MawuValue::from(CsvTable::default()), MawuValue::CSVObject(Vec::new()));
+        }
+
+        // every headed file up to 1mb, and one large enough to be split up with quoted newlines in
+        // every record, has to return the same value or an error both ways
+        #[test]
+        #[cfg(feature = "rayon")]
+        fn parallel_matches_serial() {
+            use mawu::{csv::read_csv_headed_parallel, read::csv_headed};
+            let mut files = Vec::new();
+            for dir in ["data/csv/csv-test-data/headed/my-own-random-data", "data/csv/csv-test-data/headed/random-data-no-license"] {
+                for entry in std::fs::read_dir(dir).unwrap() {
+                    let path = entry.unwrap().path();
+                    if path.metadata().unwrap().len() <= 1_000_000 {
+                        files.push(path);
+                    }
+                }
+            }
+            let large = std::env::temp_dir().join(format!("mawu-parallel-{}.csv", std::process::id()));
+            let mut contents = String::from("id,\"multi\nline\",text\n");
+            for id in 0..20_000 {
+                contents.push_str(&format!("{},\"{}\n\"\"{}\"\"\",plain\r\n", id, id % 7, id));
+            }
+            std::fs::write(&large, contents).unwrap();
+            files.push(large.clone());
+            for file in &files {
+                match (csv_headed(file), read_csv_headed_parallel(file)) {
+                    (Ok(serial), Ok(parallel)) => assert_eq!(serial, parallel, "{:?}", file),
+                    (Err(_), Err(_)) => {}
+                    (serial, parallel) => panic!("{:?}: {:?} != {:?}", file, serial.is_ok(), parallel.is_ok()),
+                }
+            }
+            let parallel = read_csv_headed_parallel(&large).unwrap();
+            std::fs::remove_file(&large).unwrap();
+            let rows = parallel.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 20_000);
+            assert_eq!(rows[19_999].get("multi\nline").unwrap(), &MawuValue::from("0\n\"19999\""));
+        }
+
+        // run with `cargo test --release --features rayon -- --ignored --nocapture parallel_speed_test`
+        #[test]
+        #[ignore]
+        #[cfg(feature = "rayon")]
+        fn parallel_speed_test() {
+            use std::time::Instant;
+            let path = "data/csv/csv-test-data/headed/my-own-random-data/test_data_310k.csv";
+            let start = Instant::now();
+            let serial = mawu::read::csv_headed(path).unwrap();
+            let serial_time = start.elapsed();
+            let start = Instant::now();
+            let parallel = mawu::csv::read_csv_headed_parallel(path).unwrap();
+            let parallel_time = start.elapsed();
+            println!("serial:   {:?}", serial_time);
+            println!("parallel: {:?} on {} threads", parallel_time, std::thread::available_parallelism().unwrap());
+            assert_eq!(serial, parallel);
+        }
+
+        #[test]
+        fn leading_bom_is_stripped() {
+            let mawu_result = mawu::read::csv_headed(
+                "data/csv/csv-test-data/headed/my-own-random-data/bom-headed.csv",
+            ).unwrap();
+            let rows = mawu_result.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].get("Id").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(rows[1].get("Name").unwrap(), &MawuValue::from("two"));
+        }
+
+        #[test]
+        #[cfg(feature = "flate2")]
+        fn gzip_compressed() {
+            let mawu_result = mawu::read::csv_headed(
+                "data/csv/csv-test-data/headed/my-own-random-data/gzip-headed.csv.gz",
+            ).unwrap();
+            let rows = mawu_result.as_csv_object().unwrap();
+            assert_eq!(rows.len(), 2);
+            assert_eq!(rows[0].get("Id").unwrap(), &MawuValue::Uint(1));
+            assert_eq!(rows[1].get("Name").unwrap(), &MawuValue::from("two"));
+        }
+
+        #[test]
+        fn other_encodings() {
+            use mawu::read::{csv_headed_with_encoding, Encoding};
+            for (file, encoding) in [
+                ("utf16le-headed.csv", Encoding::Utf16Le),
+                ("utf16be-headed.csv", Encoding::Utf16Be),
+                ("latin1-headed.csv", Encoding::Latin1),
+            ] {
+                let mawu_result = csv_headed_with_encoding(
+                    format!("data/csv/csv-test-data/headed/my-own-random-data/{}", file),
+                    encoding,
+                ).unwrap();
+                let rows = mawu_result.as_csv_object().unwrap();
+                assert_eq!(rows.len(), 2);
+                assert_eq!(rows[0].get("Id").unwrap(), &MawuValue::Uint(1));
+                assert_eq!(rows[0].get("Name").unwrap(), &MawuValue::from("J\u{fc}rgen"));
+                assert_eq!(rows[1].get("Name").unwrap(), &MawuValue::from("Zo\u{eb}"));
+            }
+            let wrong_encoding = csv_headed_with_encoding(
+                "data/csv/csv-test-data/headed/my-own-random-data/latin1-headed.csv",
+                Encoding::Utf8,
+            );
+            assert!(wrong_encoding.is_err());
+        }
+
         #[test]
         fn my_own_random_data_all_types() {
             let mawu_result = mawu::read::csv_headed(
@@ -612,6 +1068,138 @@ mod csv_tests {
         use mawu::mawu_value::MawuValue;
         use pretty_assertions::assert_eq;
 
+        #[test]
+        fn split_record() {
+            use mawu::{csv::split_record, errors::{csv_error::{CsvError, CsvParseError}, MawuError}};
+            assert_eq!(split_record("a,\"b,c\",d", ',', '"').unwrap(), vec!["a", "b,c", "d"]);
+            assert_eq!(split_record(" \"x \"\"y\"\"\" ;'z';;\"multi\nline\"\n", ';', '"').unwrap(), vec!["x \"y\"", "'z'", "", "multi\nline"]);
+            assert_eq!(split_record("'a;b';c", ';', '\'').unwrap(), vec!["a;b", "c"]);
+            assert_eq!(split_record("", ',', '"').unwrap(), Vec::<String>::new());
+            assert_eq!(split_record(",", ',', '"').unwrap(), vec!["", ""]);
+            assert_eq!(split_record("1\t\t\"x\"", '\t', '"').unwrap(), vec!["1", "", "x"]);
+            assert_eq!(split_record("\t\"x\"", '\t', '"').unwrap(), vec!["", "x"]);
+            let error = |line: &str| match split_record(line, ',', '"') {
+                Err(MawuError::CsvError(CsvError::ParseError(e))) => e,
+                other => panic!("{:?}", other),
+            };
+            assert!(matches!(error("a,\"b"), CsvParseError::UnterminatedQuote));
+            assert!(matches!(error("\"a\"b,c"), CsvParseError::UnescapedCharacter('b')));
+            assert!(matches!(error("a\nb"), CsvParseError::UnexpectedNewline));
+        }
+
+        #[test]
+        fn empty_files() {
+            for path in ["data/csv/csv-test-data/empty/empty.csv", "data/csv/csv-test-data/empty/whitespace-only.csv"] {
+                let mawu = mawu::read::csv_headless(path).unwrap();
+                assert_eq!(mawu, MawuValue::CSVArray(vec![]));
+                assert!(mawu.is_empty());
+            }
+        }
+
+        #[test]
+        fn tab_separated() {
+            let tsv = mawu::csv::read_tsv_headless("data/csv/csv-test-data/headed/my-own-random-data/tab-headed.tsv").unwrap();
+            let rows = tsv.as_csv_array().unwrap();
+            assert_eq!(rows.len(), 4);
+            assert_eq!(rows[0], vec![MawuValue::from("name"), MawuValue::from("age"), MawuValue::from("note")]);
+            assert_eq!(rows[1][2], MawuValue::String("likes\ttabs".to_string()));
+            assert_eq!(rows[3], vec![MawuValue::from("Eve"), MawuValue::None, MawuValue::from("plain")]);
+        }
+
+        #[test]
+        fn quote_styles() {
+            use mawu::csv::{from_str_with, to_string, to_string_with, CsvParseOptions, CsvWriteOptions, QuoteStyle};
+            use mawu::errors::{csv_error::{CsvError, CsvWriteError}, MawuError};
+            let value = MawuValue::CSVArray(vec![
+                vec![MawuValue::from("plain"), MawuValue::from("Doe, \"Jane\""), MawuValue::Uint(1)],
+                vec![MawuValue::from("next"), MawuValue::None, MawuValue::Uint(2)],
+            ]);
+            let write = |style| to_string_with(&value, 0, &CsvWriteOptions::new().quote_style(style));
+
+            assert_eq!(write(QuoteStyle::Strings).unwrap(), to_string(&value, 0).unwrap());
+            assert_eq!(write(QuoteStyle::Strings).unwrap(), "\"plain\",\"Doe, \"\"Jane\"\"\",1\n\"next\",,2\n");
+            assert_eq!(write(QuoteStyle::Minimal).unwrap(), "plain,\"Doe, \"\"Jane\"\"\",1\nnext,,2\n");
+            assert_eq!(write(QuoteStyle::Always).unwrap(), "\"plain\",\"Doe, \"\"Jane\"\"\",\"1\"\n\"next\",\"\",\"2\"\n");
+            assert!(matches!(
+                write(QuoteStyle::Never),
+                Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NeedsQuoting(ref field)))) if field == "Doe, \"Jane\""
+            ));
+
+            let options = CsvParseOptions::new().has_headers(false);
+            for style in [QuoteStyle::Strings, QuoteStyle::Minimal, QuoteStyle::Always] {
+                assert_eq!(from_str_with(&write(style).unwrap(), &options).unwrap(), value);
+            }
+            let no_comma = MawuValue::CSVArray(vec![vec![MawuValue::from("plain"), MawuValue::Uint(1)]]);
+            assert_eq!(to_string_with(&no_comma, 0, &CsvWriteOptions::new().quote_style(QuoteStyle::Never)).unwrap(), "plain,1\n");
+        }
+
+        #[test]
+        fn write_errors() {
+            use mawu::csv::to_string;
+            use mawu::errors::{csv_error::{CsvError, CsvWriteError}, MawuError};
+            let object = mawu::mawu!({"a": 1});
+            let error = to_string(&object, 0).unwrap_err();
+            assert!(matches!(error, MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(ref found))) if found == "Object"));
+            assert_eq!(error.to_string(), "Not CSV type: Object");
+            assert!(matches!(
+                to_string(&MawuValue::None, 0),
+                Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(ref found)))) if found == "None"
+            ));
+
+            // an object inside of a row is an error instead of a panic
+            let nested = MawuValue::CSVArray(vec![vec![MawuValue::Uint(1), object]]);
+            assert!(matches!(to_string(&nested, 0), Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::UnallowedType(_))))));
+
+            let row = |keys: &[&str]| keys.iter().map(|key| (key.to_string(), MawuValue::Uint(1))).collect();
+            let missing = MawuValue::CSVObject(vec![row(&["a", "b"]), row(&["a"])]);
+            let extra = MawuValue::CSVObject(vec![row(&["a"]), row(&["a", "b"])]);
+            for value in [missing, extra] {
+                assert!(matches!(
+                    to_string(&value, 0),
+                    Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::InconsistentColumns(_))))
+                ));
+            }
+        }
+
+        #[test]
+        fn write_to_writer() {
+            use mawu::csv::{from_str_headed, write_csv_headed_to};
+            use mawu::errors::{csv_error::{CsvError, CsvWriteError}, MawuError};
+            let rows = (0..1_000u64)
+                .map(|n| {
+                    [
+                        ("id".to_string(), MawuValue::Uint(n)),
+                        ("text".to_string(), MawuValue::String(format!("a, \"quoted\"\nline {}", n))),
+                        ("score".to_string(), if n % 3 == 0 { MawuValue::None } else { MawuValue::Float(n as f64 / 2.0) }),
+                    ]
+                    .into_iter()
+                    .collect()
+                })
+                .collect();
+            let value = MawuValue::CSVObject(rows);
+            let mut out = Vec::new();
+            write_csv_headed_to(&mut out, &value).unwrap();
+            let written = String::from_utf8(out).unwrap();
+            assert!(written.ends_with('\n'));
+            assert_eq!(from_str_headed(&written).unwrap(), value);
+
+            assert!(matches!(
+                write_csv_headed_to(Vec::new(), &MawuValue::CSVArray(vec![vec![MawuValue::Uint(1)]])),
+                Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::NotCsvType(_))))
+            ));
+            // a writer that fails is reported, not a partial success
+            struct Full;
+            impl std::io::Write for Full {
+                fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                    Err(std::io::Error::new(std::io::ErrorKind::WriteZero, "full"))
+                }
+                fn flush(&mut self) -> std::io::Result<()> {
+                    Ok(())
+                }
+            }
+            assert!(matches!(write_csv_headed_to(Full, &value), Err(MawuError::CsvError(CsvError::WriteError(CsvWriteError::IoError(_))))));
+        }
+
         #[test]
         fn read_and_write_data() {
             let mawu_result = mawu::read::csv_headless(
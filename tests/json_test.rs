@@ -11,18 +11,316 @@ mod json_tests {
         assert!(infinity.is_err());
     }
 
+    #[test]
+    fn json5_extensions_are_opt_in() {
+        use mawu::json::{from_str, from_str_borrowed, from_str_with, read_with, JsonParseOptions};
+
+        let leading_plus = "data/json/json-test-data/n_leading_plus.json";
+        assert!(json(leading_plus).is_err());
+        assert!(from_str("+5").is_err());
+        assert!(from_str_borrowed("[+5]").is_err());
+        assert_eq!(read_with(leading_plus, &JsonParseOptions::lenient()).unwrap(), MawuValue::from(vec![MawuValue::Uint(5)]));
+
+        for radix in ["[0xFF]", "-0o10", "0b11"] {
+            assert!(from_str(radix).is_err(), "{}", radix);
+            assert!(from_str_borrowed(radix).is_err(), "{}", radix);
+        }
+        assert_eq!(from_str_with("[0xFF]", &JsonParseOptions::lenient()).unwrap(), MawuValue::from(vec![MawuValue::Uint(255)]));
+
+        assert!(from_str("{'a': 'b'}").is_err());
+        assert!(from_str_borrowed("['a']").is_err());
+        assert_eq!(from_str_with("{'a': 'b'}", &JsonParseOptions::lenient()).unwrap().get("a").unwrap(), &MawuValue::from("b"));
+
+        assert!(from_str("{a: 1}").is_err());
+        assert!(from_str_borrowed("{a: 1}").is_err());
+        assert_eq!(from_str_with("{a: 1}", &JsonParseOptions::lenient()).unwrap().get("a").unwrap(), &MawuValue::Uint(1));
+
+        // the extensions are the only difference between the default and the lenient options
+        assert_ne!(JsonParseOptions::default(), JsonParseOptions::lenient());
+        assert_eq!(
+            JsonParseOptions::default()
+                .allow_leading_plus(true)
+                .allow_radix_prefixes(true)
+                .allow_single_quotes(true)
+                .allow_unquoted_keys(true),
+            JsonParseOptions::lenient()
+        );
+    }
+
+    #[test]
+    fn top_level_container() {
+        use mawu::errors::{json_error::{JsonError, JsonParseError}, MawuError};
+        use mawu::json::{read_as_array, read_as_object};
+
+        let number = "data/json/json-test-data/rfc8259-test-data/small-text2.json";
+        assert_eq!(json(number).unwrap(), MawuValue::Uint(42));
+        let error = read_as_object(number).unwrap_err();
+        assert!(matches!(error, MawuError::JsonError(JsonError::ParseError(JsonParseError::ExpectedTopLevelObject(ref found))) if found == "Uint"));
+        assert_eq!(error.to_string(), "Expected an object at the top level, found MawuValue::Uint");
+        assert!(matches!(
+            read_as_array(number),
+            Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::ExpectedTopLevelArray(_))))
+        ));
+
+        assert_eq!(read_as_object("data/json/json-test-data/rfc8259-test-data/object.json").unwrap().len(), 1);
+        assert_eq!(read_as_array("data/json/json-test-data/rfc8259-test-data/array.json").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn concatenated_documents() {
+        use mawu::json::from_str_partial;
+
+        let input = "{}{}";
+        let (first, offset) = from_str_partial(input).unwrap();
+        assert_eq!(first, MawuValue::new_object());
+        assert_eq!(offset, 2);
+        let (second, rest) = from_str_partial(&input[offset..]).unwrap();
+        assert_eq!(second, MawuValue::new_object());
+        assert_eq!(offset + rest, input.len());
+
+        // numbers, strings and literals end right where the next document starts
+        let mut input = " 1 \"ü\"[true]null\n";
+        let mut values = Vec::new();
+        while !input.trim().is_empty() {
+            let (value, offset) = from_str_partial(input).unwrap();
+            values.push(value);
+            input = &input[offset..];
+        }
+        assert_eq!(input, "\n");
+        assert_eq!(values, vec![
+            MawuValue::Uint(1),
+            MawuValue::from("ü"),
+            MawuValue::from(vec![MawuValue::Bool(true)]),
+            MawuValue::None,
+        ]);
+        assert!(from_str_partial("{\"a\": }{}").is_err());
+    }
+
+    #[test]
+    fn many_documents() {
+        use mawu::json::from_str_many;
+
+        let values = from_str_many("{} [] 5 \"x\"").unwrap();
+        assert_eq!(values, vec![MawuValue::new_object(), MawuValue::new_array(), MawuValue::Uint(5), MawuValue::from("x")]);
+        // documents spanning lines, separated by nothing or by several kinds of whitespace
+        let values = from_str_many("\r\n{\n  \"a\": [\n    1,\n    2\n  ]\n}{\"b\": null}\t\n\n[true]  ").unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].get("a").unwrap().len(), 2);
+        assert_eq!(values[2], MawuValue::from(vec![MawuValue::Bool(true)]));
+        assert_eq!(from_str_many(" \n ").unwrap(), Vec::new());
+        assert!(from_str_many("{} [1 2] 2").is_err());
+        // extra commas are skipped, like by every parser with the default options
+        assert_eq!(from_str_many("[1,] {\"a\": 1,}").unwrap(), vec![MawuValue::from(vec![MawuValue::Uint(1)]), mawu::mawu!({"a": 1})]);
+        // a scalar has to be followed by whitespace or the start of an object or array
+        for touching in ["truefalse", "nulltrue", "5\"x\"", "\"x\"\"y\"", "\"x\"5", "1 2true"] {
+            assert!(from_str_many(touching).is_err(), "{}", touching);
+        }
+        assert_eq!(from_str_many("true false\nnull").unwrap(), vec![MawuValue::Bool(true), MawuValue::Bool(false), MawuValue::None]);
+        assert_eq!(from_str_many("5[1]\"x\"{}").unwrap(), vec![MawuValue::Uint(5), MawuValue::from(vec![MawuValue::Uint(1)]), MawuValue::from("x"), MawuValue::new_object()]);
+    }
+
+    // every test file that is valid UTF-8, the larger ones split up, has to return the same value or an error both ways
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn parallel_matches_serial() {
+        use mawu::json::{from_str, from_str_parallel};
+        let mut files = Vec::new();
+        for dir in ["data/json/json-test-data/jsonTestSuite-data/test_parsing", "data/json/json-test-data/microsoftEdge-json-test-data/json-dummy-data"] {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                files.push(entry.unwrap().path());
+            }
+        }
+        for file in &files {
+            let Ok(input) = std::fs::read_to_string(file) else { continue };
+            match (from_str(&input), from_str_parallel(&input)) {
+                (Ok(serial), Ok(parallel)) => assert_eq!(serial, parallel, "{:?}", file),
+                (Err(_), Err(_)) => {}
+                (serial, parallel) => panic!("{:?}: {:?} != {:?}", file, serial.is_ok(), parallel.is_ok()),
+            }
+        }
+        let large = format!("[{}]", (0..50_000).map(|n| format!(r#"{{"id": {}, "text": "a, \\\"[b]\\\"", "nested": [[{}], {{}}]}}"#, n, n)).collect::<Vec<_>>().join(",\n"));
+        let parallel = from_str_parallel(&large).unwrap();
+        assert_eq!(parallel.len(), 50_000);
+        assert_eq!(parallel.as_array().unwrap()[49_999].get("text").unwrap(), &MawuValue::from("a, \\\"[b]\\\""));
+        assert_eq!(parallel, from_str(&large).unwrap());
+    }
+
+    // run with `cargo test --release --features rayon -- --ignored --nocapture parallel_speed_test`
+    #[test]
+    #[ignore]
+    #[cfg(feature = "rayon")]
+    fn parallel_speed_test() {
+        use std::time::Instant;
+        let input = format!("[{}]", (0..1_000_000).map(|n| format!(r#"{{"id": {}, "name": "row {}", "score": {}.25, "tags": ["a", "b,c"], "active": {}, "note": null}}"#, n, n, n, n % 2 == 0)).collect::<Vec<_>>().join(",\n"));
+        let start = Instant::now();
+        let serial = mawu::json::from_str(&input).unwrap();
+        let serial_time = start.elapsed();
+        let start = Instant::now();
+        let parallel = mawu::json::from_str_parallel(&input).unwrap();
+        let parallel_time = start.elapsed();
+        println!("{} bytes", input.len());
+        println!("serial:   {:?}", serial_time);
+        println!("parallel: {:?} on {} threads", parallel_time, std::thread::available_parallelism().unwrap());
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn leading_bom_is_stripped() {
+        let bom = json("data/json/json-test-data/bom-object.json").unwrap();
+        assert_eq!(bom.len(), 2);
+        assert_eq!(bom.get("key1").unwrap(), &MawuValue::from("value1"));
+        assert_eq!(bom.get("key2").unwrap(), &MawuValue::Uint(1));
+    }
+
+    #[test]
+    fn strict_rejects_control_characters() {
+        use mawu::json::{read_with, JsonParseOptions};
+        for file in ["n_string_unescaped_tab.json", "n_string_unescaped_newline.json", "n_string_unescaped_ctrl_char.json"] {
+            let path = format!("data/json/json-test-data/jsonTestSuite-data/test_parsing/{}", file);
+            assert!(read_with(&path, &JsonParseOptions::strict()).is_err(), "{}", file);
+        }
+        let tab = "data/json/json-test-data/jsonTestSuite-data/test_parsing/n_string_unescaped_tab.json";
+        assert_eq!(read_with(tab, &JsonParseOptions::lenient()).unwrap(), MawuValue::Array(vec![MawuValue::String("\t".to_string())]));
+        assert!(read_with("data/json/json-test-data/simple-object.json", &JsonParseOptions::strict()).is_ok());
+    }
+
+    #[test]
+    fn unpaired_surrogates() {
+        use mawu::errors::{json_error::{JsonError, JsonParseError}, MawuError};
+        for file in ["i_string_1st_surrogate_but_2nd_missing.json", "i_string_invalid_lonely_surrogate.json", "i_string_lone_second_surrogate.json", "i_object_key_lone_2nd_surrogate.json"] {
+            let path = format!("data/json/json-test-data/jsonTestSuite-data/test_parsing/{}", file);
+            assert!(
+                matches!(json(&path), Err(MawuError::JsonError(JsonError::ParseError(JsonParseError::UnpairedSurrogate(_))))),
+                "{}",
+                file
+            );
+        }
+    }
+
+    #[test]
+    fn empty_files() {
+        use mawu::json::{read_with, JsonParseOptions};
+        for path in ["data/json/json-test-data/empty.json", "data/json/json-test-data/whitespace-only.json"] {
+            assert_eq!(json(path).unwrap(), MawuValue::None);
+            assert_eq!(read_with(path, &JsonParseOptions::new()).unwrap(), MawuValue::None);
+            assert!(read_with(path, &JsonParseOptions::strict()).is_err());
+        }
+    }
+
+    #[test]
+    fn write_errors() {
+        use mawu::errors::{json_error::{JsonError, JsonWriteError}, MawuError};
+        use mawu::json::{to_string, to_writer};
+
+        let csv_object = MawuValue::CSVObject(vec![std::collections::HashMap::from([("a".to_string(), MawuValue::Uint(1))]).into_iter().collect()]);
+        let error = to_string(&csv_object, 0).unwrap_err();
+        assert!(matches!(error, MawuError::JsonError(JsonError::WriteError(JsonWriteError::NotJSONType(ref found))) if found == "CSVObject"));
+        assert_eq!(error.to_string(), "Not JSON type: CSVObject");
+        let mut out = Vec::new();
+        assert!(matches!(to_writer(&mut out, &csv_object, 0), Err(MawuError::JsonError(JsonError::WriteError(JsonWriteError::NotJSONType(_))))));
+        assert!(out.is_empty());
+
+        struct Broken;
+        impl std::io::Write for Broken {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("broken pipe"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let error = to_writer(Broken, &mawu::mawu!([1, 2]), 0).unwrap_err();
+        assert!(matches!(error, MawuError::JsonError(JsonError::WriteError(JsonWriteError::IoError(_)))));
+        assert_eq!(error.to_string(), "Unable to write JSON: broken pipe");
+    }
+
+    #[test]
+    fn non_finite_floats() {
+        use mawu::errors::{json_error::{JsonError, JsonWriteError}, MawuError};
+        use mawu::json::{to_string, to_string_with, JsonWriteOptions, NonFiniteFloats};
+
+        for float in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                to_string(&MawuValue::Float(float), 0),
+                Err(MawuError::JsonError(JsonError::WriteError(JsonWriteError::InvalidNumber(_))))
+            ));
+        }
+        let options = JsonWriteOptions::new().non_finite_floats(NonFiniteFloats::Null);
+        assert_eq!(to_string_with(&MawuValue::Float(f64::NAN), 0, &options).unwrap(), "null");
+        assert_eq!(to_string_with(&MawuValue::Float(f64::INFINITY), 0, &options).unwrap(), "null");
+        let value = mawu::mawu!({"finite": 0.5, "infinite": [(f64::NEG_INFINITY)]});
+        let written = to_string_with(&value, 4, &options).unwrap();
+        assert_eq!(mawu::json::from_str(&written).unwrap(), mawu::mawu!({"finite": 0.5, "infinite": [null]}));
+        assert!(to_string_with(&value, 4, &JsonWriteOptions::default()).is_err());
+    }
+
+    #[test]
+    fn canonicalize_ignores_key_order() {
+        let parsed = json("data/json/json-test-data/simple-object.json").unwrap();
+        let reordered = mawu::mawu!({"key5": null, "key4": true, "key3": -1, "key2": 1, "key1": "value1"});
+        let canonical = mawu::json::canonicalize(&parsed).unwrap();
+        assert_eq!(canonical, mawu::json::canonicalize(&reordered).unwrap());
+        assert_eq!(canonical, r#"{"key1":"value1","key2":1,"key3":-1,"key4":true,"key5":null}"#);
+    }
+
+    #[test]
+    fn public_types_are_send_sync() {
+        fn _assert_send_sync<T: Send + Sync>() {}
+        _assert_send_sync::<MawuValue>();
+        _assert_send_sync::<mawu::errors::MawuError>();
+        _assert_send_sync::<mawu::json::JsonEvent>();
+        _assert_send_sync::<mawu::json::JsonTokenizer>();
+        fn _assert_send<T: Send>(_: &T) {}
+        _assert_send(&mawu::json::array_stream("[1, 2]".as_bytes()));
+
+        let parsed = json("data/json/json-test-data/simple-object.json").unwrap();
+        let handle = std::thread::spawn(move || parsed.get("key1").cloned());
+        assert_eq!(handle.join().unwrap(), Some(MawuValue::from("value1")));
+    }
+
+    #[test]
+    #[cfg(feature = "flate2")]
+    fn gzip_compressed() {
+        let gzip = json("data/json/json-test-data/gzip-object.json.gz").unwrap();
+        assert_eq!(gzip.get("key1").unwrap(), &MawuValue::from("value1"));
+        assert_eq!(gzip.get("key2").unwrap().len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "btreemap")]
+    fn btreemap_sorted_objects() {
+        let value = mawu::mawu!({"b": 2, "c": {"z": null, "y": [3, 1]}, "a": 1});
+        let keys = value.as_object().unwrap().keys().cloned().collect::<Vec<String>>();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+
+        mawu::write("btreemap_delete_me.json", value.clone()).unwrap();
+        let written = std::fs::read_to_string("btreemap_delete_me.json").unwrap();
+        std::fs::remove_file("btreemap_delete_me.json").unwrap();
+        assert_eq!(written, r#"{"a":1,"b":2,"c":{"y":[3,1],"z":null}}"#);
+        assert_eq!(json("data/json/json-test-data/simple-object.json").unwrap().as_object().unwrap().keys().next().unwrap(), "key1");
+    }
+
+    #[test]
+    fn latin1_encoding() {
+        use mawu::read::{json_with_encoding, Encoding};
+        let path = "data/json/json-test-data/latin1-object.json";
+        let latin1 = json_with_encoding(path, Encoding::Latin1).unwrap();
+        assert_eq!(latin1.get("name").unwrap(), &MawuValue::from("M\u{fc}ller"));
+        assert_eq!(latin1.get("city").unwrap(), &MawuValue::from("K\u{f6}ln"));
+        assert!(json(path).is_err());
+    }
+
     #[test]
     fn write_weird_shit() {
         let weird = MawuValue::from(vec![
             MawuValue::from("\""),
             MawuValue::from("\\"),
             MawuValue::from("/"),
-            // I would include these if I knew how to escape them so that the equality test would pass
-            //MawuValue::from(r"\b"),
-            //MawuValue::from(r"\f"),
-            //MawuValue::from(r"\n"),
-            //MawuValue::from(r"\r"),
-            //MawuValue::from(r"\t"),
+            MawuValue::from(r"\b"),
+            MawuValue::from(r"\f"),
+            MawuValue::from(r"\n"),
+            MawuValue::from(r"\r"),
+            MawuValue::from(r"\t"),
             MawuValue::from("\u{0061}"),
             MawuValue::from("\u{30af}"),
             MawuValue::from("\u{30EA}"),
@@ -287,6 +585,364 @@ mod json_tests {
         let unterminated = json("data/json/json-test-data/microsoftEdge-json-test-data/json-dummy-data/invalid-json/unterminated.json");
         assert_eq!(unterminated.is_err(), true);
     }
+    #[cfg(test)]
+    mod round_trip {
+        use mawu::read::json;
+        use std::path::{Path, PathBuf};
+
+        const CORPUS: &str = "data/json/json-test-data";
+
+        /// Reads `round-trip-skip.txt`, one path or path prefix per line
+        fn skipped() -> Vec<String> {
+            std::fs::read_to_string(Path::new(CORPUS).join("round-trip-skip.txt"))
+                .unwrap()
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| line.to_string())
+                .collect()
+        }
+
+        fn json_files(dir: &Path, out: &mut Vec<PathBuf>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    json_files(&path, out);
+                } else if path.extension().is_some_and(|ext| ext == "json") {
+                    out.push(path);
+                }
+            }
+        }
+
+        // parse -> serialize -> parse has to return the same value for every file in the corpus
+        #[test]
+        fn corpus_round_trip() {
+            let skipped = skipped();
+            let mut files = Vec::new();
+            json_files(Path::new(CORPUS), &mut files);
+            files.sort();
+            let out_dir = std::env::temp_dir().join(format!("mawu-round-trip-{}", std::process::id()));
+            std::fs::create_dir_all(&out_dir).unwrap();
+            let mut failures = Vec::new();
+            let mut checked = 0;
+            for (index, file) in files.iter().enumerate() {
+                let relative = file.strip_prefix(CORPUS).unwrap().to_string_lossy().replace('\\', "/");
+                if skipped.iter().any(|skip| relative.starts_with(skip.as_str())) {
+                    continue;
+                }
+                checked += 1;
+                let parsed = match json(file) {
+                    Ok(parsed) => parsed,
+                    Err(err) => {
+                        failures.push(format!("{}: parse failed: {}", relative, err));
+                        continue;
+                    }
+                };
+                let out_file = out_dir.join(format!("{}.json", index));
+                if let Err(err) = mawu::write(&out_file, parsed.clone()) {
+                    failures.push(format!("{}: write failed: {}", relative, err));
+                    continue;
+                }
+                match json(&out_file) {
+                    Ok(reparsed) if reparsed == parsed => {}
+                    Ok(reparsed) => failures.push(format!("{}: {:?} != {:?}", relative, parsed, reparsed)),
+                    Err(err) => failures.push(format!("{}: re-parse failed: {}", relative, err)),
+                }
+            }
+            std::fs::remove_dir_all(&out_dir).unwrap();
+            assert!(checked > 100, "only {} files checked", checked);
+            assert!(failures.is_empty(), "{} files failed to round-trip:\n{}", failures.len(), failures.join("\n"));
+        }
+    }
+
+    #[cfg(test)]
+    mod conformance {
+        use mawu::json::{read_with, JsonParseOptions};
+
+        const TEST_PARSING: &str = "data/json/json-test-data/jsonTestSuite-data/test_parsing";
+
+        // `y_` files have to parse, `n_` files have to error and `i_` files may do either, but none may panic
+        #[test]
+        fn json_test_suite_conformance() {
+            let mut files = std::fs::read_dir(TEST_PARSING)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect::<Vec<_>>();
+            files.sort();
+            let mut failures = Vec::new();
+            for file in &files {
+                let name = file.file_name().unwrap().to_string_lossy().to_string();
+                let result = std::panic::catch_unwind(|| read_with(file, &JsonParseOptions::strict()));
+                let violation = match (name.split('_').next().unwrap(), result) {
+                    (_, Err(_)) => Some("panicked".to_string()),
+                    ("y", Ok(Err(err))) => Some(format!("should parse, but errored: {}", err)),
+                    ("n", Ok(Ok(value))) => Some(format!("should error, but parsed: {:?}", value)),
+                    ("y" | "n" | "i", _) => None,
+                    _ => Some("not prefixed with y_, n_ or i_".to_string()),
+                };
+                if let Some(violation) = violation {
+                    failures.push(format!("{}: {}", name, violation));
+                }
+            }
+            assert!(files.len() > 300, "only {} files found", files.len());
+            assert!(failures.is_empty(), "{} failures:\n{}", failures.len(), failures.join("\n"));
+        }
+    }
+
+    #[cfg(test)]
+    mod borrowed {
+        use mawu::json::{from_str_borrowed, from_str_borrowed_with, read_with, JsonParseOptions};
+        use mawu::mawu_value_ref::MawuValueRef;
+        use std::{path::Path, time::Instant};
+
+        const TEST_PARSING: &str = "data/json/json-test-data/jsonTestSuite-data/test_parsing";
+
+        // the borrowing parser has to agree with `read_with` on every `y_` file, and reject every `n_` file
+        #[test]
+        fn json_test_suite_matches_owned() {
+            let mut files = std::fs::read_dir(TEST_PARSING)
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect::<Vec<_>>();
+            files.sort();
+            let mut failures = Vec::new();
+            let mut checked = 0;
+            for file in &files {
+                let name = file.file_name().unwrap().to_string_lossy().to_string();
+                // invalid UTF-8 can not be handed over as a `&str` in the first place
+                let Ok(contents) = std::fs::read_to_string(file) else {
+                    continue;
+                };
+                checked += 1;
+                let options = JsonParseOptions::strict();
+                let result = std::panic::catch_unwind(|| from_str_borrowed_with(&contents, &options).map(|v| v.to_owned()));
+                match (name.split('_').next().unwrap(), result) {
+                    (_, Err(_)) => failures.push(format!("{}: panicked", name)),
+                    ("y", Ok(Ok(value))) => {
+                        let owned = read_with(file, &options).unwrap();
+                        if value != owned {
+                            failures.push(format!("{}: {:?} != {:?}", name, value, owned));
+                        }
+                    }
+                    ("y", Ok(Err(err))) => failures.push(format!("{}: should parse, but errored: {}", name, err)),
+                    ("n", Ok(Ok(value))) => failures.push(format!("{}: should error, but parsed: {:?}", name, value)),
+                    _ => {}
+                }
+            }
+            assert!(checked > 250, "only {} files checked", checked);
+            assert!(failures.is_empty(), "{} failures:\n{}", failures.len(), failures.join("\n"));
+        }
+
+        fn count_strings(value: &MawuValueRef, borrowed: &mut usize, total: &mut usize) {
+            match value {
+                MawuValueRef::Object(pairs) => {
+                    for (key, value) in pairs {
+                        *total += 1;
+                        if matches!(key, std::borrow::Cow::Borrowed(_)) {
+                            *borrowed += 1;
+                        }
+                        count_strings(value, borrowed, total);
+                    }
+                }
+                MawuValueRef::Array(values) => values.iter().for_each(|v| count_strings(v, borrowed, total)),
+                MawuValueRef::String(_) => {
+                    *total += 1;
+                    if value.is_borrowed() {
+                        *borrowed += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // run with `cargo test --release -- --ignored --nocapture borrowed_speed_test`
+        #[test]
+        #[ignore]
+        fn borrowed_speed_test() {
+            let path = Path::new("data/json/json-test-data/test_data_160k.json");
+            let contents = std::fs::read_to_string(path).unwrap();
+
+            let start = Instant::now();
+            let owned = mawu::json::from_str(&contents).unwrap();
+            let owned_time = start.elapsed();
+
+            let start = Instant::now();
+            let borrowed = from_str_borrowed(&contents).unwrap();
+            let borrowed_time = start.elapsed();
+
+            let (mut borrowed_strings, mut strings) = (0, 0);
+            count_strings(&borrowed, &mut borrowed_strings, &mut strings);
+            println!("owned:    {:?}", owned_time);
+            println!("borrowed: {:?}, {} of {} strings and keys borrowed", borrowed_time, borrowed_strings, strings);
+            assert_eq!(borrowed.to_owned(), owned);
+            assert_eq!(borrowed_strings, strings);
+        }
+    }
+
+    #[cfg(test)]
+    mod shared {
+        use std::time::Instant;
+
+        // run with `cargo test --release -- --ignored --nocapture shared_clone_speed_test`
+        #[test]
+        #[ignore]
+        fn shared_clone_speed_test() {
+            let value = mawu::read::json("data/json/json-test-data/test_data_160k.json").unwrap();
+            let clones = 10;
+
+            let start = Instant::now();
+            let deep = (0..clones).map(|_| value.clone()).collect::<Vec<_>>();
+            let deep_time = start.elapsed();
+
+            let shared = value.clone().into_shared();
+            let start = Instant::now();
+            let cheap = (0..clones).map(|_| shared.clone()).collect::<Vec<_>>();
+            let shared_time = start.elapsed();
+
+            println!("{} deep clones:   {:?}", clones, deep_time);
+            println!("{} shared clones: {:?}", clones, shared_time);
+            assert!(deep.iter().all(|v| v == &value));
+            assert!(cheap.iter().all(|v| v.ptr_eq(&shared)));
+        }
+    }
+
+    #[cfg(test)]
+    #[cfg(feature = "serde")]
+    mod typed {
+        use mawu::errors::{json_error::JsonError, MawuError};
+        use mawu::json::{from_str_typed, to_string_typed};
+        use pretty_assertions::assert_eq;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Author {
+            name: String,
+            email: Option<String>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Serialize)]
+        struct Package {
+            name: String,
+            version: (u8, u8, u8),
+            authors: Vec<Author>,
+            license: Option<String>,
+            downloads: u64,
+        }
+
+        #[test]
+        fn nested_struct() {
+            let input = r#"{
+                "name": "mawu",
+                "version": [0, 6, 2],
+                "authors": [{"name": "Xqhare", "email": null}, {"name": "Ada", "email": "ada@example.com"}],
+                "downloads": 12345,
+                "unknown": {"fields": "are ignored"}
+            }"#;
+            let package: Package = from_str_typed(input).unwrap();
+            assert_eq!(package, Package {
+                name: "mawu".to_string(),
+                version: (0, 6, 2),
+                authors: vec![
+                    Author { name: "Xqhare".to_string(), email: None },
+                    Author { name: "Ada".to_string(), email: Some("ada@example.com".to_string()) },
+                ],
+                license: None,
+                downloads: 12345,
+            });
+
+            let negative = from_str_typed::<Package>(&input.replace("12345", "-1"));
+            assert!(matches!(negative, Err(MawuError::JsonError(JsonError::DeserializeError(_)))));
+            let number_name = from_str_typed::<Package>(&input.replace(r#""name": "mawu""#, r#""name": 42"#));
+            assert!(matches!(number_name, Err(MawuError::JsonError(JsonError::DeserializeError(_)))));
+            assert!(matches!(from_str_typed::<Package>("[NaN]"), Err(MawuError::JsonError(JsonError::ParseError(_)))));
+        }
+
+        #[test]
+        fn serialize_pretty() {
+            // structs with a single field are written the same no matter the map backing `MawuObject`
+            #[derive(Serialize)]
+            struct Release {
+                version: (u8, u8, u8),
+            }
+            #[derive(Serialize)]
+            struct Changelog {
+                releases: Vec<Release>,
+            }
+
+            let changelog = Changelog { releases: vec![Release { version: (0, 6, 2) }, Release { version: (0, 6, 1) }] };
+            assert_eq!(
+                to_string_typed(&changelog, 4).unwrap(),
+                "{\n    \"releases\": [\n         \n        {\n            \"version\": [\n                 0, 6, 2\n            ]\n        }, \n        {\n            \"version\": [\n                 0, 6, 1\n            ]\n        }\n    ]\n}"
+            );
+            assert_eq!(to_string_typed(&(-1, "a", None::<u8>), 0).unwrap(), r#"[-1,"a",null]"#);
+
+            let package = Package {
+                name: "mawu".to_string(),
+                version: (0, 6, 2),
+                authors: vec![Author { name: "Xqhare".to_string(), email: None }],
+                license: Some("MIT".to_string()),
+                downloads: 12345,
+            };
+            assert_eq!(from_str_typed::<Package>(&to_string_typed(&package, 2).unwrap()).unwrap(), package);
+            #[cfg(feature = "btreemap")]
+            assert_eq!(
+                to_string_typed(&package, 2).unwrap(),
+                "{\n  \"authors\": [\n     \n    {\n      \"email\": null,\n      \"name\": \"Xqhare\"\n    }\n  ],\n  \"downloads\": 12345,\n  \"license\": \"MIT\",\n  \"name\": \"mawu\",\n  \"version\": [\n     0, 6, 2\n  ]\n}"
+            );
+        }
+    }
+
+    #[cfg(test)]
+    mod schema {
+        use mawu::json::from_str;
+        use mawu::mawu;
+        use mawu::validate::{against_schema, ValidationError, ValidationErrorKind};
+        use pretty_assertions::assert_eq;
+
+        #[test]
+        fn missing_required_and_out_of_range() {
+            let schema = mawu!({
+                "type": "object",
+                "required": ["id", "name"],
+                "properties": {
+                    "id": {"type": "integer", "minimum": 1},
+                    "name": {"type": "string"},
+                    "scores": {"type": "array", "items": {"type": "number", "minimum": 0, "maximum": 100}}
+                }
+            });
+            let valid = from_str(r#"{"id": 7, "name": "Ada", "scores": [0, 99.5, 100]}"#).unwrap();
+            assert_eq!(against_schema(&valid, &schema), Ok(()));
+
+            let invalid = from_str(r#"{"id": 7, "scores": [50, 101, -1]}"#).unwrap();
+            let errors = against_schema(&invalid, &schema).unwrap_err();
+            assert_eq!(errors, vec![
+                ValidationError { path: "".to_string(), kind: ValidationErrorKind::MissingRequired("name".to_string()) },
+                ValidationError { path: "/scores/1".to_string(), kind: ValidationErrorKind::AboveMaximum(100.0) },
+                ValidationError { path: "/scores/2".to_string(), kind: ValidationErrorKind::BelowMinimum(0.0) },
+            ]);
+            assert_eq!(errors[1].to_string(), "At /scores/1: Number is above the maximum of 100");
+        }
+
+        #[test]
+        fn infer_nested_object() {
+            let sample = from_str(r#"{"id": 7, "owner": {"name": "Ada", "tags": ["a", 1]}, "rows": [{"x": 1.5, "y": null}, {"x": -2}]}"#).unwrap();
+            let schema = mawu::schema::infer(&sample);
+            assert_eq!(schema, mawu::validate::infer_schema(&sample));
+            assert_eq!(schema.get("type").unwrap(), &mawu!("object"));
+            assert_eq!(schema.get("required").unwrap(), &mawu!(["id", "owner", "rows"]));
+            let properties = schema.get("properties").unwrap();
+            assert_eq!(properties.get("id").unwrap(), &mawu!({"type": "integer"}));
+            assert_eq!(properties.get("owner").unwrap().get("required").unwrap(), &mawu!(["name", "tags"]));
+            assert_eq!(properties.get("owner").unwrap().get("properties").unwrap().get("tags").unwrap(), &mawu!({"type": "array", "items": {"type": ["integer", "string"]}}));
+            let rows = properties.get("rows").unwrap().get("items").unwrap();
+            assert_eq!(rows.get("required").unwrap(), &mawu!(["x"]));
+            assert_eq!(rows.get("properties").unwrap().get("x").unwrap(), &mawu!({"type": "number"}));
+            assert_eq!(against_schema(&sample, &schema), Ok(()));
+        }
+    }
+
     #[cfg(test)]
     mod json_test_suite {
         use mawu::read::json;
@@ -562,9 +1218,10 @@ mod json_tests {
                 assert!(bind.is_array());
                 count += 1;
             }
-            // No BOM support!
+            // A leading BOM is stripped
             let structure_utf_8_bom_empty_object = json("data/json/json-test-data/jsonTestSuite-data/test_parsing/i_structure_UTF-8_BOM_empty_object.json");
-            assert!(structure_utf_8_bom_empty_object.is_err());
+            assert!(structure_utf_8_bom_empty_object.is_ok());
+            assert!(structure_utf_8_bom_empty_object.unwrap().is_object());
         }
 
         #[test]
@@ -0,0 +1,29 @@
+//! Only builds if mawu compiles without the `std` feature, and its macro expands in a `no_std`
+//! crate.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use mawu::{
+    csv::{self, CsvParseOptions},
+    errors::MawuError,
+    json::{self, JsonParseOptions},
+    mawu,
+    mawu_value::MawuValue,
+};
+
+pub fn round_trip_json(input: &str) -> Result<String, MawuError> {
+    let value = json::from_str_with(input, &JsonParseOptions::strict())?;
+    json::to_string(&value, 0)
+}
+
+pub fn round_trip_csv(input: &str) -> Result<String, MawuError> {
+    let value = csv::from_str_with(input, &CsvParseOptions::new())?;
+    csv::to_string(&value, 0)
+}
+
+pub fn build() -> MawuValue {
+    mawu!({"key": [1, -1, 1.5, "value", true, null]})
+}